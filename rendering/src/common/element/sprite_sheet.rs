@@ -37,7 +37,7 @@ pub fn check_element_sprite_sheet_loaded(
         let texture_atlas = TextureAtlas::from_grid(
             element_sprite_sheet_handle.0.clone(),
             Vec2::splat(128.0),
-            3,
+            ELEMENT_COLUMNS,
             16,
             None,
             None,
@@ -52,6 +52,9 @@ pub fn check_element_sprite_sheet_loaded(
     }
 }
 
+/// Dirt, food, sand, rock, water and waste, in that order, each drawn in a column of 16 exposures.
+const ELEMENT_COLUMNS: usize = 6;
+
 // TODO: super hardcoded to the order they appear in sprite_sheet.png
 // Spritesheet is organized as:
 // 0 - none exposed
@@ -174,8 +177,11 @@ pub fn get_element_index(exposure: ElementExposure, element: Element) -> usize {
         Element::Dirt => 0,
         Element::Food => 1,
         Element::Sand => 2,
+        Element::Rock => 3,
+        Element::Water => 4,
+        Element::Waste => 5,
         _ => panic!("Element {:?} not supported", element),
     };
 
-    row_index * 3 + column_index
+    row_index * ELEMENT_COLUMNS + column_index
 }
@@ -13,7 +13,7 @@ use self::{
     },
     pointer::{handle_pointer_tap, initialize_pointer_resources, remove_pointer_resources},
     selection::{
-        clear_selection, on_update_selected, on_update_selected_position, SelectedEntity,
+        clear_selection, on_update_selected, on_update_selected_position, Selection,
         SelectionSprite,
     },
     visible_grid::{set_visible_grid_state_none, VisibleGrid, VisibleGridState},
@@ -237,12 +237,12 @@ impl Plugin for CommonRenderingPlugin {
 
 fn initialize_common_resources(mut commands: Commands) {
     commands.init_resource::<ModelViewEntityMap>();
-    commands.init_resource::<SelectedEntity>();
+    commands.init_resource::<Selection>();
     commands.init_resource::<VisibleGrid>();
 }
 
 fn remove_common_resources(mut commands: Commands) {
-    commands.remove_resource::<SelectedEntity>();
+    commands.remove_resource::<Selection>();
     commands.remove_resource::<VisibleGrid>();
     // TODO: removing this causes issues because camera Update runs expecting the resource to exist.
     //commands.remove_resource::<ModelViewEntityMap>();
@@ -1,12 +1,12 @@
 use super::{
     camera::RenderingCamera,
-    selection::SelectedEntity,
+    selection::Selection,
     visible_grid::{world_to_grid_position, VisibleGrid},
 };
 use bevy::{prelude::*, window::PrimaryWindow};
 use simulation::{
     common::{
-        ant::Ant,
+        ant::position_map::AntPositionMap,
         grid::{Grid, GridElements},
         position::Position,
         Zone,
@@ -22,6 +22,8 @@ pub enum PointerAction {
     SpawnFood,
     SpawnDirt,
     SpawnSand,
+    SpawnRock,
+    SpawnWater,
     KillAnt,
     SpawnWorkerAnt,
     DespawnWorkerAnt,
@@ -40,6 +42,8 @@ pub fn pointer_action_to_simulation_event<Z: Zone>(
         PointerAction::SpawnFood => ExternalSimulationEvent::SpawnFood(position, zone),
         PointerAction::SpawnDirt => ExternalSimulationEvent::SpawnDirt(position, zone),
         PointerAction::SpawnSand => ExternalSimulationEvent::SpawnSand(position, zone),
+        PointerAction::SpawnRock => ExternalSimulationEvent::SpawnRock(position, zone),
+        PointerAction::SpawnWater => ExternalSimulationEvent::SpawnWater(position, zone),
         PointerAction::KillAnt => ExternalSimulationEvent::KillAnt(position, zone),
         PointerAction::SpawnWorkerAnt => ExternalSimulationEvent::SpawnWorkerAnt(position, zone),
         PointerAction::DespawnWorkerAnt => {
@@ -81,8 +85,8 @@ pub fn handle_pointer_tap<Z: Zone + Copy>(
     pointer_action: Res<PointerAction>,
     mut external_simulation_event_writer: EventWriter<ExternalSimulationEvent<Z>>,
     mut pointer_tap_state: ResMut<PointerTapState>,
-    ants_query: Query<(Entity, &Position), (With<Ant>, With<Z>)>,
-    mut selected_entity: ResMut<SelectedEntity>,
+    ant_position_map: Res<AntPositionMap<Z>>,
+    mut selection: ResMut<Selection>,
 ) {
     if is_pointer_captured.0 {
         return;
@@ -138,40 +142,35 @@ pub fn handle_pointer_tap<Z: Zone + Copy>(
     }
 
     // TODO: Support multiple ants at a given position. Need to select them in a fixed order so that there's a "last ant" so that selecting Element is possible afterward.
-    let ant_entity_at_position = ants_query
-        .iter()
-        .find(|(_, &position)| position == grid_position)
-        .map(|(entity, _)| entity);
+    let ant_entity_at_position = ant_position_map.get(&grid_position).first().copied();
 
-    let element_entity_at_position = grid_elements.get_entity(grid_position);
+    let element_at_position = grid_elements
+        .get_element(grid_position)
+        .map(|_| Selection::Element(grid_position));
 
-    let currently_selected_entity = selected_entity.0;
+    let currently_selected = *selection;
 
     if let Some(ant_entity) = ant_entity_at_position {
         // If tapping on an already selected ant then consider selecting element underneath ant instead.
-        if ant_entity_at_position == currently_selected_entity {
-            if let Some(element_entity) = element_entity_at_position {
-                selected_entity.0 = Some(*element_entity);
-            } else {
-                selected_entity.0 = None;
-            }
+        if Selection::Ant(ant_entity) == currently_selected {
+            *selection = element_at_position.unwrap_or(Selection::None);
         } else {
             // If there is an ant at the given position, and it's not selected, but the element underneath it is selected
             // then assume user wants to deselect element and not select the ant. They can select again after if they want the ant.
-            if element_entity_at_position == currently_selected_entity.as_ref() {
-                selected_entity.0 = None;
+            if element_at_position == Some(currently_selected) {
+                *selection = Selection::None;
             } else {
-                selected_entity.0 = Some(ant_entity);
+                *selection = Selection::Ant(ant_entity);
             }
         }
-    } else if let Some(element_entity) = element_entity_at_position {
-        if element_entity_at_position == currently_selected_entity.as_ref() {
-            selected_entity.0 = None;
+    } else if let Some(element_selection) = element_at_position {
+        if element_selection == currently_selected {
+            *selection = Selection::None;
         } else {
-            selected_entity.0 = Some(*element_entity);
+            *selection = element_selection;
         }
     } else {
-        selected_entity.0 = None;
+        *selection = Selection::None;
     }
 }
 
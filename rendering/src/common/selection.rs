@@ -3,27 +3,34 @@ use simulation::common::{grid::Grid, position::Position};
 
 use super::{visible_grid::grid_to_world_position, VisibleGrid};
 
-#[derive(Resource, Default)]
-pub struct SelectedEntity(pub Option<Entity>);
+/// Ants are selected by entity so the selection follows them as they move. Elements aren't entities so they're
+/// selected by position instead.
+#[derive(Resource, Default, PartialEq, Copy, Clone, Debug)]
+pub enum Selection {
+    #[default]
+    None,
+    Ant(Entity),
+    Element(Position),
+}
 
 #[derive(Component)]
 pub struct SelectionSprite;
 
-pub fn clear_selection(mut selected_entity: ResMut<SelectedEntity>) {
-    selected_entity.0 = None;
+pub fn clear_selection(mut selection: ResMut<Selection>) {
+    *selection = Selection::None;
 }
 
 /// When Selection is added to a component, decorate that component with a white outline sprite.
 pub fn on_update_selected(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    selected_entity: Res<SelectedEntity>,
-    entity_position_query: Query<Ref<Position>>,
+    selection: Res<Selection>,
+    entity_position_query: Query<&Position>,
     selection_sprite_query: Query<Entity, With<SelectionSprite>>,
     grid_query: Query<&Grid>,
     visible_grid: Res<VisibleGrid>,
 ) {
-    if !selected_entity.is_changed() {
+    if !selection.is_changed() {
         return;
     }
 
@@ -41,14 +48,13 @@ pub fn on_update_selected(
         Err(_) => return,
     };
 
-    let newly_selected_entity = match selected_entity.0 {
-        Some(entity) => entity,
-        None => return,
+    let position = match *selection {
+        Selection::None => return,
+        Selection::Ant(entity) => *entity_position_query.get(entity).unwrap(),
+        Selection::Element(position) => position,
     };
 
-    let position = entity_position_query.get(newly_selected_entity).unwrap();
-
-    let mut world_position = grid_to_world_position(grid, *position);
+    let mut world_position = grid_to_world_position(grid, position);
     // render selection UI above ants
     world_position.z = 3.0;
 
@@ -70,7 +76,7 @@ pub fn on_update_selected(
 }
 
 pub fn on_update_selected_position(
-    selected_entity: Res<SelectedEntity>,
+    selection: Res<Selection>,
     entity_position_query: Query<&Position, Changed<Position>>,
     mut selection_sprite_query: Query<&mut Transform, With<SelectionSprite>>,
     grid_query: Query<&Grid>,
@@ -86,9 +92,10 @@ pub fn on_update_selected_position(
         Err(_) => return,
     };
 
-    let selected_entity = match selected_entity.0 {
-        Some(entity) => entity,
-        None => return,
+    // Selected elements never move. If the element at a selected position changes then the new element is selected.
+    let selected_entity = match *selection {
+        Selection::Ant(entity) => entity,
+        Selection::None | Selection::Element(_) => return,
     };
 
     let selected_entity_position = match entity_position_query.get(selected_entity) {
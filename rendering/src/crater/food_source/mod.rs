@@ -0,0 +1,101 @@
+use crate::common::{
+    visible_grid::{grid_to_world_position, VisibleGrid},
+    ModelViewEntityMap,
+};
+use bevy::prelude::*;
+use simulation::{
+    common::{grid::Grid, position::Position},
+    crater_simulation::crater::{
+        food_source::{FoodSource, FoodSourceKind},
+        AtCrater,
+    },
+};
+
+pub fn on_spawn_food_source(
+    food_source_query: Query<(Entity, &Position, &FoodSource), (Added<FoodSource>, With<AtCrater>)>,
+    mut commands: Commands,
+    grid_query: Query<&Grid, With<AtCrater>>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+    visible_grid: Res<VisibleGrid>,
+) {
+    let visible_grid_entity = match visible_grid.0 {
+        Some(visible_grid_entity) => visible_grid_entity,
+        None => return,
+    };
+
+    let grid = match grid_query.get(visible_grid_entity) {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+
+    for (food_source_model_entity, position, food_source) in &food_source_query {
+        spawn_food_source(
+            food_source_model_entity,
+            food_source,
+            position,
+            grid,
+            &mut commands,
+            &mut model_view_entity_map,
+        );
+    }
+}
+
+pub fn spawn_food_sources(
+    food_source_model_query: Query<(Entity, &Position, &FoodSource), With<AtCrater>>,
+    mut commands: Commands,
+    grid_query: Query<&Grid, With<AtCrater>>,
+    mut model_view_entity_map: ResMut<ModelViewEntityMap>,
+) {
+    let grid = grid_query.single();
+
+    for (food_source_model_entity, position, food_source) in &food_source_model_query {
+        spawn_food_source(
+            food_source_model_entity,
+            food_source,
+            position,
+            grid,
+            &mut commands,
+            &mut model_view_entity_map,
+        );
+    }
+}
+
+/// Non-System Helper Functions:
+
+fn spawn_food_source(
+    food_source_model_entity: Entity,
+    food_source: &FoodSource,
+    food_source_position: &Position,
+    grid: &Grid,
+    commands: &mut Commands,
+    model_view_entity_map: &mut ResMut<ModelViewEntityMap>,
+) {
+    let food_source_view_entity = commands
+        .spawn((
+            SpriteBundle {
+                transform: Transform::from_translation(grid_to_world_position(
+                    grid,
+                    *food_source_position,
+                )),
+                sprite: get_food_source_sprite(food_source),
+                ..default()
+            },
+            AtCrater,
+        ))
+        .id();
+
+    model_view_entity_map.insert(food_source_model_entity, food_source_view_entity);
+}
+
+fn get_food_source_sprite(food_source: &FoodSource) -> Sprite {
+    let color = match food_source.kind() {
+        FoodSourceKind::Bush => Color::rgb(0.18, 0.49, 0.2),
+        FoodSourceKind::SeedDrop => Color::rgb(0.76, 0.6, 0.42),
+    };
+
+    Sprite {
+        color,
+        custom_size: Some(Vec2::new(1.0, 1.0)),
+        ..default()
+    }
+}
@@ -1,5 +1,6 @@
 pub mod ant;
 pub mod background;
+pub mod food_source;
 pub mod nest_entrance;
 pub mod pheromone;
 
@@ -9,13 +10,15 @@ use self::{
         on_update_ant_position, spawn_ants,
     },
     background::{cleanup_background, spawn_background, CraterBackground},
+    food_source::{on_spawn_food_source, spawn_food_sources},
     nest_entrance::{cleanup_nest_entrance, spawn_nest_entrance, NestEntrance},
     pheromone::{on_spawn_pheromone, spawn_pheromones},
 };
 use crate::common::{
     despawn_view, despawn_view_by_model,
     element::{
-        cleanup_elements, insert_element_exposure_map, on_spawn_element, process_element_exposure_changed_events, remove_element_exposure_map, spawn_element_tilemap, spawn_elements, update_element_exposure_map, ElementTilemap
+        cleanup_elements, on_update_elements, spawn_element_tilemap, spawn_elements, ElementTile,
+        ElementTilemap,
     },
     on_despawn, on_model_removed_zone,
     visible_grid::{VisibleGrid, VisibleGridState},
@@ -23,8 +26,8 @@ use crate::common::{
 use bevy::prelude::*;
 use simulation::{
     app_state::AppState,
-    common::{ant::Ant, element::Element, pheromone::Pheromone},
-    crater_simulation::crater::{AtCrater, Crater},
+    common::{ant::Ant, pheromone::Pheromone},
+    crater_simulation::crater::{food_source::FoodSource, AtCrater, Crater},
     CleanupSet,
 };
 
@@ -35,20 +38,15 @@ impl Plugin for CraterRenderingPlugin {
         app.add_systems(
             Update,
             (
-                (
-                    update_element_exposure_map::<AtCrater>,
-                    apply_deferred,
-                    process_element_exposure_changed_events::<AtCrater>,
-                )
-                    .chain(),
+                on_update_elements::<AtCrater>,
                 (
                     // Spawn
-                    (on_spawn_element::<AtCrater>, on_spawn_pheromone),
+                    (on_spawn_pheromone, on_spawn_food_source),
                     // Despawn
                     (
                         on_despawn::<Ant, AtCrater>,
-                        on_despawn::<Element, AtCrater>,
                         on_despawn::<Pheromone, AtCrater>,
+                        on_despawn::<FoodSource, AtCrater>,
                     ),
                     // Added
                     (on_added_ant_at_crater),
@@ -71,10 +69,7 @@ impl Plugin for CraterRenderingPlugin {
         app.add_systems(
             OnEnter(VisibleGridState::Crater),
             (
-                (
-                    spawn_element_tilemap,
-                    insert_element_exposure_map::<AtCrater>,
-                ),
+                spawn_element_tilemap::<AtCrater>,
                 apply_deferred,
                 (
                     spawn_background,
@@ -82,6 +77,7 @@ impl Plugin for CraterRenderingPlugin {
                     spawn_ants,
                     spawn_elements::<AtCrater>,
                     spawn_pheromones,
+                    spawn_food_sources,
                     mark_crater_visible,
                 ),
             )
@@ -95,10 +91,10 @@ impl Plugin for CraterRenderingPlugin {
                 despawn_view::<CraterBackground>,
                 despawn_view::<NestEntrance>,
                 despawn_view_by_model::<Ant, AtCrater>,
-                despawn_view_by_model::<Element, AtCrater>,
+                despawn_view::<ElementTile>,
                 despawn_view::<ElementTilemap>,
                 despawn_view_by_model::<Pheromone, AtCrater>,
-                remove_element_exposure_map,
+                despawn_view_by_model::<FoodSource, AtCrater>,
                 mark_crater_hidden,
             )
                 .run_if(in_state(AppState::TellStory)),
@@ -113,9 +109,10 @@ impl Plugin for CraterRenderingPlugin {
                 cleanup_nest_entrance,
                 despawn_view_by_model::<Ant, AtCrater>,
                 cleanup_ants,
-                despawn_view_by_model::<Element, AtCrater>,
+                despawn_view::<ElementTile>,
                 cleanup_elements,
                 despawn_view_by_model::<Pheromone, AtCrater>,
+                despawn_view_by_model::<FoodSource, AtCrater>,
             )
                 .in_set(CleanupSet::BeforeSimulationCleanup),
         );
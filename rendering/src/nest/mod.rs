@@ -23,11 +23,9 @@ use self::{
 use super::common::{
     despawn_view, despawn_view_by_model,
     element::{
-        cleanup_elements, initialize_element_resources, insert_element_exposure_map,
-        on_spawn_element, on_update_element_position, process_element_exposure_changed_events,
-        remove_element_exposure_map, spawn_element_tilemap, spawn_elements,
+        cleanup_elements, on_update_elements, spawn_element_tilemap, spawn_elements,
         sprite_sheet::{check_element_sprite_sheet_loaded, start_load_element_sprite_sheet},
-        update_element_exposure_map, ElementTilemap,
+        ElementTile, ElementTilemap,
     },
     on_despawn,
     visible_grid::{VisibleGrid, VisibleGridState},
@@ -35,7 +33,7 @@ use super::common::{
 use bevy::prelude::*;
 use simulation::{
     app_state::AppState,
-    common::{ant::Ant, element::Element, pheromone::Pheromone},
+    common::{ant::Ant, pheromone::Pheromone},
     nest_simulation::nest::{AtNest, Nest},
     CleanupSet, FinishSetupSet,
 };
@@ -57,31 +55,18 @@ impl Plugin for NestRenderingPlugin {
 
         app.add_systems(
             OnEnter(AppState::FinishSetup),
-            (
-                initialize_background_resources,
-                initialize_element_resources,
-            )
-                .in_set(FinishSetupSet::AfterSimulationFinishSetup),
+            initialize_background_resources.in_set(FinishSetupSet::AfterSimulationFinishSetup),
         );
 
         app.add_systems(
             Update,
             (
-                (
-                    update_element_exposure_map::<AtNest>,
-                    apply_deferred,
-                    process_element_exposure_changed_events::<AtNest>,
-                )
-                    .chain(),
+                on_update_elements::<AtNest>,
                 (
                     // Spawn
-                    (on_spawn_element::<AtNest>, on_spawn_pheromone),
+                    on_spawn_pheromone,
                     // Despawn
-                    (
-                        on_despawn::<Ant, AtNest>,
-                        on_despawn::<Element, AtNest>,
-                        on_despawn::<Pheromone, AtNest>,
-                    ),
+                    (on_despawn::<Ant, AtNest>, on_despawn::<Pheromone, AtNest>),
                     // Added
                     (on_added_ant_emote, on_added_ant_dead, on_added_ant_at_nest),
                     // Removed
@@ -92,7 +77,6 @@ impl Plugin for NestRenderingPlugin {
                         on_update_ant_orientation,
                         on_update_ant_color,
                         on_update_ant_inventory,
-                        on_update_element_position::<AtNest>,
                     ),
                     // Misc
                     (
@@ -118,11 +102,7 @@ impl Plugin for NestRenderingPlugin {
         app.add_systems(
             OnEnter(VisibleGridState::Nest),
             (
-                (
-                    spawn_background_tilemap,
-                    spawn_element_tilemap,
-                    insert_element_exposure_map::<AtNest>,
-                ),
+                (spawn_background_tilemap, spawn_element_tilemap::<AtNest>),
                 apply_deferred,
                 (
                     spawn_background,
@@ -142,10 +122,9 @@ impl Plugin for NestRenderingPlugin {
                 despawn_view::<Background>,
                 despawn_view::<BackgroundTilemap>,
                 despawn_view_by_model::<Ant, AtNest>,
-                despawn_view_by_model::<Element, AtNest>,
+                despawn_view::<ElementTile>,
                 despawn_view::<ElementTilemap>,
                 despawn_view_by_model::<Pheromone, AtNest>,
-                remove_element_exposure_map,
                 mark_nest_hidden,
             )
                 .run_if(in_state(AppState::TellStory)),
@@ -159,7 +138,7 @@ impl Plugin for NestRenderingPlugin {
                 cleanup_background,
                 despawn_view_by_model::<Ant, AtNest>,
                 cleanup_ants,
-                despawn_view_by_model::<Element, AtNest>,
+                despawn_view::<ElementTile>,
                 cleanup_elements,
                 despawn_view_by_model::<Pheromone, AtNest>,
             )
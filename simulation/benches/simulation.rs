@@ -0,0 +1,250 @@
+//! Benchmarks for the systems which do the most work per tick, measured against worlds of increasing size.
+//! Run with `cargo bench -p simulation`. Criterion reports regressions against the previous run.
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use simulation::{
+    common::{
+        ant::{
+            hunger::{ants_hunger_regurgitate, Hunger},
+            initiative::ants_initiative,
+            AntRole, Dead,
+        },
+        element::{commands::ElementCommandsExt, Element},
+        grid::{ElementGrid, GridElements},
+        pheromone::{
+            commands::PheromoneCommandsExt, pheromone_duration_tick, Pheromone, PheromoneStrength,
+        },
+        position::Position,
+    },
+    headless::{begin_story, tick, HeadlessPlugin},
+    nest_simulation::{
+        ant::{nest_expansion::ants_nest_expansion, walk::ants_walk},
+        gravity::gravity_elements,
+        nest::{AtNest, Nest},
+    },
+    save::create_save_data,
+    settings::Settings,
+};
+use std::time::{Duration, Instant};
+
+const SEED: u64 = 42;
+
+/// Let setup settle, e.g. gravity, before measuring.
+const WARMUP_TICKS: usize = 10;
+
+/// (width and height of the nest and crater, worker ant count)
+/// The largest world is the size Settings hopes the nest can grow to without lag.
+const WORLD_SIZES: [(isize, isize); 3] = [(144, 100), (256, 500), (400, 1_000)];
+
+struct BenchWorld {
+    app: App,
+    label: String,
+}
+
+/// Build a seeded world, of the given size, and dig out a chamber full of ants so every benchmarked system has work to do.
+fn build_world(size: isize, ant_count: isize) -> BenchWorld {
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin);
+    app.insert_resource(Settings {
+        nest_width: size,
+        nest_height: size,
+        crater_width: size,
+        crater_height: size,
+        initial_ant_worker_count: ant_count,
+        seed: SEED,
+        ..default()
+    });
+
+    begin_story(&mut app);
+
+    app.world.run_system_once(dig_crowded_chamber);
+
+    for _ in 0..WARMUP_TICKS {
+        tick(&mut app);
+    }
+
+    BenchWorld {
+        app,
+        label: format!("{}x{} {} ants", size, size, ant_count),
+    }
+}
+
+/// Ants in the nest which are still alive.
+type LivingNestAntFilter = (With<AtNest>, Without<Dead>);
+
+/// Worker ants are spread along the floor of a wide chamber, just below the surface, and marked with tunnel pheromone.
+/// Packing them in makes them crowded, which is when they expand the nest, and peckish, which is when they share food.
+fn dig_crowded_chamber(
+    mut ants_query: Query<(&AntRole, &mut Position, &mut Hunger), LivingNestAntFilter>,
+    nest_query: Query<&Nest>,
+    grid_elements: GridElements<AtNest>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) {
+    let nest = nest_query.single();
+    let top = nest.surface_level() + 2;
+    let bottom = top + (settings.nest_height - top) / 4;
+    let left = 2;
+    let right = settings.nest_width - 2;
+
+    for y in top..bottom {
+        for x in left..right {
+            let position = Position::new(x, y);
+            let element = grid_elements.element(position);
+
+            commands.replace_element(position, Element::Air, element, AtNest);
+            commands.spawn_pheromone(
+                position,
+                Pheromone::Tunnel,
+                PheromoneStrength::new(settings.tunnel_length, settings.tunnel_length),
+                AtNest,
+            );
+        }
+    }
+
+    let floor_width = right - left;
+    let worker_ants = ants_query
+        .iter_mut()
+        .filter(|(ant_role, _, _)| **ant_role == AntRole::Worker);
+
+    for (index, (_, mut position, mut hunger)) in worker_ants.enumerate() {
+        *position = Position::new(left + index as isize % floor_width, bottom - 1);
+        hunger.set_value(30.0);
+    }
+}
+
+/// A fresh grid, with the same elements, is dirty everywhere.
+fn mark_every_chunk_dirty(mut element_grid_query: Query<&mut ElementGrid, With<AtNest>>) {
+    let mut element_grid = element_grid_query.single_mut();
+    let elements = element_grid.iter().map(|(_, element)| element).collect();
+
+    *element_grid = ElementGrid::new(element_grid.width(), element_grid.height(), elements);
+}
+
+/// Dig a handful of tiles out of the dirt, like a few ants would in a tick, so only their chunks are dirty.
+fn dig_a_few_tiles(
+    mut element_grid_query: Query<&mut ElementGrid, With<AtNest>>,
+    nest_query: Query<&Nest>,
+) {
+    let mut element_grid = element_grid_query.single_mut();
+    let nest = nest_query.single();
+    let dirt_positions = element_grid
+        .iter()
+        .filter(|&(position, element)| element == Element::Dirt && nest.is_underground(&position))
+        .map(|(position, _)| position)
+        .step_by(97)
+        .take(8)
+        .collect::<Vec<_>>();
+
+    for position in dirt_positions {
+        element_grid.set(position, Element::Air);
+    }
+}
+
+/// Every benchmark gets fresh worlds because the worst cases being measured would skew the benchmarks which follow.
+fn build_worlds() -> Vec<BenchWorld> {
+    WORLD_SIZES
+        .iter()
+        .map(|&(size, ant_count)| build_world(size, ant_count))
+        .collect()
+}
+
+fn schedule<M>(systems: impl IntoSystemConfigs<M>) -> Schedule {
+    let mut schedule = Schedule::default();
+    schedule.add_systems(systems);
+    schedule
+}
+
+/// Run `setup` before every iteration, without measuring it, then measure a single run of `measured`.
+/// Both run against the same world so it evolves between iterations like it would between ticks.
+fn bench_schedule(
+    c: &mut Criterion,
+    name: &str,
+    mut setup: impl FnMut() -> Schedule,
+    mut measured: impl FnMut() -> Schedule,
+) {
+    let mut group = c.benchmark_group(name);
+
+    for world in build_worlds().iter_mut() {
+        let mut setup = setup();
+        let mut measured = measured();
+
+        group.bench_function(BenchmarkId::from_parameter(&world.label), |b| {
+            b.iter_custom(|iters| {
+                let mut elapsed = Duration::ZERO;
+
+                for _ in 0..iters {
+                    setup.run(&mut world.app.world);
+
+                    let start = Instant::now();
+                    measured.run(&mut world.app.world);
+                    elapsed += start.elapsed();
+                }
+
+                elapsed
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn simulation_benchmarks(c: &mut Criterion) {
+    // Worst case: every chunk is dirty, e.g. after loading an old save, and each element must check whether it can fall.
+    bench_schedule(
+        c,
+        "gravity_elements",
+        || schedule(mark_every_chunk_dirty),
+        || schedule(gravity_elements),
+    );
+
+    // Typical case: ants dug a few tiles last tick so only the chunks around them are dirty.
+    bench_schedule(
+        c,
+        "gravity_elements_dig",
+        || schedule(dig_a_few_tiles),
+        || schedule(gravity_elements),
+    );
+
+    // Ants act when their initiative comes around so give them the initiative they'd have had each tick.
+    bench_schedule(
+        c,
+        "ants_walk",
+        || schedule(ants_initiative::<AtNest>),
+        || schedule(ants_walk),
+    );
+
+    bench_schedule(
+        c,
+        "ants_hunger_regurgitate",
+        || schedule(ants_initiative::<AtNest>),
+        || schedule((ants_hunger_regurgitate::<AtNest>, apply_deferred).chain()),
+    );
+
+    bench_schedule(
+        c,
+        "ants_nest_expansion",
+        || schedule(ants_initiative::<AtNest>),
+        || schedule((ants_nest_expansion, apply_deferred).chain()),
+    );
+
+    bench_schedule(c, "pheromone_duration_tick", Schedule::default, || {
+        schedule((pheromone_duration_tick::<AtNest>, apply_deferred).chain())
+    });
+
+    // Snapshots serialize every persisted entity so they're slow. Take fewer samples to keep the run reasonable.
+    let mut group = c.benchmark_group("save");
+    group.sample_size(10);
+
+    for world in build_worlds().iter_mut() {
+        group.bench_function(BenchmarkId::from_parameter(&world.label), |b| {
+            b.iter(|| create_save_data(&mut world.app.world))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, simulation_benchmarks);
+criterion_main!(benches);
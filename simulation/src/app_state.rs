@@ -11,6 +11,9 @@ pub enum AppState {
     Loading,
     // TODO: The fact that I need a "MainMenu" state, but that AppState exists in `simulation` not `rendering` is a code smell.
     MainMenu,
+    // A new story's world is created here, rather than when exiting MainMenu, because exiting MainMenu
+    // to continue a saved story should not create a world just to overwrite it.
+    CreateNewStory,
     FinishSetup,
     // Bevy does not currently support adding systems at runtime. So, systems
     // which monitor for Added<_> have a backlog to process, but this is not desirable
@@ -29,6 +32,10 @@ pub fn restart(
     next_app_state.set(AppState::Loading);
 }
 
+pub fn finish_setup(mut next_app_state: ResMut<NextState<AppState>>) {
+    next_app_state.set(AppState::FinishSetup);
+}
+
 pub fn post_setup_clear_change_detection(mut next_app_state: ResMut<NextState<AppState>>) {
     next_app_state.set(AppState::PostSetupClearChangeDetection);
 }
@@ -0,0 +1,238 @@
+use crate::{
+    common::{
+        ant::{death::DeathCause, AntAteFoodEvent, AntDiedEvent, AntDugEvent},
+        element::Element,
+        pheromone::PheromoneLaidEvent,
+    },
+    nest_simulation::ant::birthing::AntGaveBirthEvent,
+    story_time::StoryPlaybackState,
+};
+use bevy::{prelude::*, utils::HashMap};
+
+/// A tally of what happened while the player was away, i.e. while a long absence was summarized and while the story
+/// fast-forwarded through the rest of the time missed. It's complete once fast-forwarding finishes.
+///
+/// Note the intentional omission of reflection/serialization. The report is about the current session.
+#[derive(Resource, Default, Clone, PartialEq, Debug)]
+pub struct AwayReport {
+    pub ticks: isize,
+    pub births: usize,
+    pub deaths: HashMap<DeathCause, usize>,
+    pub food_eaten: usize,
+    pub tiles_dug: usize,
+    pub pheromones_laid: usize,
+    is_complete: bool,
+}
+
+impl AwayReport {
+    pub fn is_complete(&self) -> bool {
+        self.is_complete
+    }
+
+    pub fn deaths_by(&self, cause: DeathCause) -> usize {
+        self.deaths.get(&cause).copied().unwrap_or_default()
+    }
+
+    pub fn total_deaths(&self) -> usize {
+        self.deaths.values().sum()
+    }
+
+    pub fn record_death(&mut self, cause: DeathCause) {
+        *self.deaths.entry(cause).or_default() += 1;
+    }
+}
+
+/// Start over whenever the previous report is complete. Summarizing a long absence starts a report, too,
+/// and that report continues through the fast-forward which follows it.
+pub fn begin_away_report(away_report: Option<Res<AwayReport>>, mut commands: Commands) {
+    let is_recording = away_report.is_some_and(|away_report| !away_report.is_complete());
+
+    if !is_recording {
+        commands.insert_resource(AwayReport::default());
+    }
+}
+
+pub fn complete_away_report(away_report: Option<ResMut<AwayReport>>) {
+    if let Some(mut away_report) = away_report {
+        away_report.is_complete = true;
+    }
+}
+
+pub fn remove_away_report_resources(mut commands: Commands) {
+    commands.remove_resource::<AwayReport>();
+}
+
+/// Events are read every tick, even when there's no report being recorded, so that a report never includes
+/// events from before it began.
+pub fn record_away_report(
+    mut ant_gave_birth_events: EventReader<AntGaveBirthEvent>,
+    mut ant_died_events: EventReader<AntDiedEvent>,
+    mut ant_ate_food_events: EventReader<AntAteFoodEvent>,
+    mut ant_dug_events: EventReader<AntDugEvent>,
+    mut pheromone_laid_events: EventReader<PheromoneLaidEvent>,
+    away_report: Option<ResMut<AwayReport>>,
+    story_playback_state: Res<State<StoryPlaybackState>>,
+) {
+    let births = ant_gave_birth_events.read().count();
+    let death_causes = ant_died_events
+        .read()
+        .map(|&AntDiedEvent(_, cause)| cause)
+        .collect::<Vec<_>>();
+    let food_eaten = ant_ate_food_events.read().count();
+    // Ants dig up food, too, but that's gathering rather than digging.
+    let tiles_dug = ant_dug_events
+        .read()
+        .filter(|&&AntDugEvent(_, element)| element == Element::Dirt || element == Element::Sand)
+        .count();
+    let pheromones_laid = pheromone_laid_events.read().count();
+
+    let Some(mut away_report) = away_report else {
+        return;
+    };
+
+    if away_report.is_complete() || *story_playback_state != StoryPlaybackState::FastForwarding {
+        return;
+    }
+
+    away_report.ticks += 1;
+    away_report.births += births;
+    away_report.food_eaten += food_eaten;
+    away_report.tiles_dug += tiles_dug;
+    away_report.pheromones_laid += pheromones_laid;
+
+    for cause in death_causes {
+        away_report.record_death(cause);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        catch_up::{catch_up_absence, PendingCatchUp},
+        common::{
+            ant::{
+                hunger::{ants_hunger_act, Hunger},
+                initiative::ants_initiative,
+            },
+            position::Position,
+        },
+        headless::{begin_story, tick, HeadlessPlugin},
+        nest_simulation::nest::AtNest,
+        story_time::FastForwardPendingTicks,
+        test_support::{run_ticks, NestFixture},
+    };
+    use bevy::ecs::system::RunSystemOnce;
+
+    /// Fast-forward through `ticks` ticks, the way the story does after a short absence, and return how many ticks
+    /// were simulated while fast-forwarding.
+    fn fast_forward(app: &mut App, ticks: isize) -> isize {
+        app.world
+            .resource_mut::<FastForwardPendingTicks>()
+            .set(ticks);
+        app.world
+            .resource_mut::<NextState<StoryPlaybackState>>()
+            .set(StoryPlaybackState::FastForwarding);
+        app.world.run_schedule(StateTransition);
+
+        let mut fast_forwarded_ticks = 0;
+
+        while *app.world.resource::<State<StoryPlaybackState>>()
+            == StoryPlaybackState::FastForwarding
+        {
+            assert!(tick(app));
+            fast_forwarded_ticks += 1;
+        }
+
+        fast_forwarded_ticks
+    }
+
+    #[test]
+    fn report_tallies_fast_forward() {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        begin_story(&mut app);
+
+        let fast_forwarded_ticks = fast_forward(&mut app, 1_000);
+
+        // Ticks after fast-forwarding aren't part of the report.
+        tick(&mut app);
+
+        let away_report = app.world.resource::<AwayReport>();
+        assert!(away_report.is_complete());
+        assert_eq!(away_report.ticks, fast_forwarded_ticks);
+        assert!(away_report.tiles_dug > 0);
+        assert!(away_report.pheromones_laid > 0);
+    }
+
+    #[test]
+    fn report_tallies_deaths_and_meals() {
+        let mut app = NestFixture::new(
+            "
+            >.>F
+            DDDD
+            ",
+        )
+        .build();
+        app.add_event::<AntGaveBirthEvent>();
+        app.add_event::<AntDiedEvent>();
+        app.add_event::<AntAteFoodEvent>();
+        app.add_event::<AntDugEvent>();
+        app.add_event::<PheromoneLaidEvent>();
+        app.insert_resource(State::new(StoryPlaybackState::FastForwarding));
+        app.insert_resource(AwayReport::default());
+        app.add_systems(
+            Update,
+            (
+                ants_initiative::<AtNest>,
+                ants_hunger_act::<AtNest>,
+                apply_deferred,
+                record_away_report,
+            )
+                .chain(),
+        );
+
+        // The ant on the left has starved while the ant on the right is peckish and facing food.
+        for (position, mut hunger) in app
+            .world
+            .query::<(&Position, &mut Hunger)>()
+            .iter_mut(&mut app.world)
+        {
+            let value = if position.x == 0 { 100.0 } else { 30.0 };
+            hunger.set_value(value);
+        }
+
+        // Time enough to dig up the food and then eat it.
+        run_ticks(&mut app, 20);
+
+        let away_report = app.world.resource::<AwayReport>();
+        assert_eq!(away_report.deaths_by(DeathCause::Starvation), 1);
+        assert_eq!(away_report.total_deaths(), 1);
+        assert_eq!(away_report.food_eaten, 1);
+        assert_eq!(away_report.tiles_dug, 0);
+    }
+
+    #[test]
+    fn report_continues_from_summarized_absence() {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        begin_story(&mut app);
+
+        app.world.insert_resource(PendingCatchUp(2_000));
+        app.world.run_system_once(catch_up_absence);
+
+        let fast_forwarded_ticks = fast_forward(&mut app, 10);
+
+        let away_report = app.world.resource::<AwayReport>();
+        assert!(away_report.is_complete());
+        assert_eq!(away_report.ticks, 2_000 + fast_forwarded_ticks);
+
+        // The next fast-forward starts a new report.
+        let fast_forwarded_ticks = fast_forward(&mut app, 10);
+
+        assert_eq!(
+            app.world.resource::<AwayReport>().ticks,
+            fast_forwarded_ticks
+        );
+    }
+}
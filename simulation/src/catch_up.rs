@@ -0,0 +1,376 @@
+use crate::{
+    away_report::{begin_away_report, AwayReport},
+    chronicle::{ChronicleEvent, ChronicleWriter},
+    colony_stats::sample_colony_stats,
+    common::{
+        ant::{
+            death::DeathCause, digestion::Digestion, hunger::Hunger, AntColor, AntInventory,
+            AntName, AntOrientation, Dead, Initiative,
+        },
+        element::Element,
+        grid::ElementGrid,
+        pheromone::{commands::PheromoneCommandsExt, PheromoneDuration},
+        position::Position,
+        Zone,
+    },
+    crater_simulation::crater::AtCrater,
+    journal::{Journal, JournaledEvent},
+    nest_simulation::{
+        ant::birthing::{give_birth, Birthing},
+        nest::AtNest,
+        spoilage::spoil_food,
+    },
+    settings::Settings,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND, SECONDS_PER_HOUR},
+};
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use bevy_turborand::GlobalRng;
+use std::marker::PhantomData;
+
+/// How much time is summarized at once. Hunger, digestion, birthing and pheromones all change slowly enough that
+/// an hour at a time captures what matters, e.g. who starved and how many ants were born, without simulating each tick.
+const CATCH_UP_STEP_TICKS: isize = SECONDS_PER_HOUR * DEFAULT_TICKS_PER_SECOND;
+
+/// Ticks missed during a long absence which are too many to fast-forward through and so will be summarized instead.
+/// Inserted when a story is loaded after a long absence, or when a replay reaches a journaled absence.
+#[derive(Resource, Debug)]
+pub struct PendingCatchUp(pub isize);
+
+/// Summarize a long absence in large steps, rather than tick by tick, advancing only what changes predictably with time:
+/// ants grow hungry, digest and eat stored food, or starve, queens give birth, pheromones expire and stored food spoils.
+/// Ants don't move, dig or explore while being summarized. Those behaviors resume once the story is told normally.
+///
+/// The absence is journaled so that replaying the story summarizes it in the same way at the same point.
+/// What happened is tallied in an AwayReport which fast-forwarding through the rest of the absence continues.
+pub fn catch_up_absence(world: &mut World) {
+    let Some(PendingCatchUp(ticks)) = world.remove_resource::<PendingCatchUp>() else {
+        return;
+    };
+
+    let elapsed_ticks = world.resource::<StoryTime>().elapsed_ticks();
+    if let Some(mut journal) = world.get_resource_mut::<Journal>() {
+        journal.record(elapsed_ticks, JournaledEvent::Absence(ticks));
+    }
+
+    world.run_system_once(begin_away_report);
+    world.resource_mut::<AwayReport>().ticks += ticks;
+
+    let mut remaining_ticks = ticks;
+
+    while remaining_ticks > 0 {
+        let step_ticks = remaining_ticks.min(CATCH_UP_STEP_TICKS);
+
+        world.run_system_once_with(step_ticks, summarize_hunger);
+        world.run_system_once_with(step_ticks, summarize_birthing);
+        world.run_system_once_with(step_ticks, summarize_pheromones::<AtNest>);
+        world.run_system_once_with(step_ticks, summarize_pheromones::<AtCrater>);
+
+        // Advance the clock as each step is summarized so that what happened is chronicled, and the colony is sampled,
+        // at about the right time.
+        world.resource_mut::<StoryTime>().skip_ticks(step_ticks);
+        world.run_system_once(summarize_spoilage);
+        world.run_system_once(sample_colony_stats);
+        remaining_ticks -= step_ticks;
+    }
+}
+
+/// Ants digest, grow hungry and then, if peckish, eat the food they're carrying or, failing that, some of the food
+/// stored in the nest. Ants eat at most once per step, which is plenty because a meal takes about an hour to digest.
+fn summarize_hunger(
+    In(ticks): In<isize>,
+    mut ants_query: Query<
+        (
+            Entity,
+            &mut Hunger,
+            &mut Digestion,
+            &mut AntInventory,
+            &AntName,
+        ),
+        Without<Dead>,
+    >,
+    elements_query: Query<&Element>,
+    mut element_grid_query: Query<&mut ElementGrid, With<AtNest>>,
+    mut away_report: ResMut<AwayReport>,
+    mut chronicle_writer: ChronicleWriter,
+    mut commands: Commands,
+) {
+    let mut element_grid = element_grid_query.get_single_mut().ok();
+    let mut food_positions = element_grid
+        .as_ref()
+        .map(|element_grid| {
+            element_grid
+                .iter()
+                .filter(|&(_, element)| element == Element::Food)
+                .map(|(position, _)| position)
+                .collect::<Vec<Position>>()
+        })
+        .unwrap_or_default()
+        .into_iter();
+
+    for (ant_entity, mut hunger, mut digestion, mut inventory, name) in ants_query.iter_mut() {
+        let digested = digestion.tick_by(ticks);
+        let value = hunger.value() - digested;
+        hunger.set_value(value);
+        hunger.tick_by(ticks);
+
+        if hunger.is_starved() {
+            commands
+                .entity(ant_entity)
+                .insert(Dead)
+                .remove::<Initiative>();
+
+            away_report.record_death(DeathCause::Starvation);
+            chronicle_writer.record(ChronicleEvent::Death {
+                name: name.0.clone(),
+                cause: DeathCause::Starvation,
+            });
+
+            continue;
+        }
+
+        if !hunger.is_peckish() {
+            continue;
+        }
+
+        let carried_food = inventory
+            .0
+            .filter(|&item_entity| elements_query.get(item_entity) == Ok(&Element::Food));
+
+        if let Some(item_entity) = carried_food {
+            commands.entity(item_entity).despawn();
+            inventory.0 = None;
+        } else if let (Some(element_grid), Some(food_position)) =
+            (element_grid.as_mut(), food_positions.next())
+        {
+            element_grid.set(food_position, Element::Air);
+        } else {
+            continue;
+        }
+
+        digestion.increment(-0.20);
+        away_report.food_eaten += 1;
+    }
+}
+
+/// What a mother needs to give birth to, and record the birth of, a child.
+type BirthingAnt = (
+    &'static mut Birthing,
+    &'static Position,
+    &'static AntColor,
+    &'static AntOrientation,
+    &'static AntName,
+);
+
+fn summarize_birthing(
+    In(ticks): In<isize>,
+    mut ants_birthing_query: Query<BirthingAnt, (Without<Dead>, With<AtNest>)>,
+    mut rng: ResMut<GlobalRng>,
+    mut away_report: ResMut<AwayReport>,
+    mut chronicle_writer: ChronicleWriter,
+    mut commands: Commands,
+) {
+    for (mut birthing, position, color, orientation, name) in ants_birthing_query.iter_mut() {
+        for _ in 0..birthing.tick_by(ticks) {
+            let child_name = give_birth(position, color, orientation, &mut rng, &mut commands);
+            away_report.births += 1;
+            chronicle_writer.record(ChronicleEvent::Birth {
+                mother: name.0.clone(),
+                child: child_name.0,
+            });
+        }
+    }
+}
+
+/// Food is checked for spoilage once per step, rather than every minute, so it can last up to a step longer.
+fn summarize_spoilage(
+    mut element_grid_query: Query<&mut ElementGrid, With<AtNest>>,
+    story_time: Res<StoryTime>,
+    settings: Res<Settings>,
+) {
+    if let Ok(mut element_grid) = element_grid_query.get_single_mut() {
+        spoil_food(&mut element_grid, story_time.elapsed_ticks(), &settings);
+    }
+}
+
+fn summarize_pheromones<Z: Zone>(
+    In(ticks): In<isize>,
+    mut pheromone_query: Query<(&mut PheromoneDuration, &Position, Entity), With<Z>>,
+    mut commands: Commands,
+) {
+    for (mut pheromone_duration, position, pheromone_entity) in pheromone_query.iter_mut() {
+        let rate_of_pheromone_expiration = pheromone_duration.rate_of_expiration();
+
+        pheromone_duration.tick(rate_of_pheromone_expiration * ticks as f32);
+
+        if pheromone_duration.is_expired() {
+            commands.despawn_pheromone(pheromone_entity, *position, PhantomData::<Z>);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app_state::AppState,
+        common::ant::{Ant, AntRole},
+        headless::{begin_story, load_story, HeadlessPlugin},
+        save::create_save_data,
+        settings::{Settings, Terrain},
+        story_time::{StoryRealWorldTime, SECONDS_PER_DAY},
+    };
+    use chrono::Utc;
+
+    const TICKS_PER_DAY: isize = SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND;
+
+    fn story_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        // Without buried food, the only food in the nest is what a test stores.
+        app.insert_resource(Settings {
+            terrain: Terrain::flat(),
+            ..default()
+        });
+        begin_story(&mut app);
+        app
+    }
+
+    fn catch_up(app: &mut App, ticks: isize) {
+        app.world.insert_resource(PendingCatchUp(ticks));
+        app.world.run_system_once(catch_up_absence);
+    }
+
+    fn queen(world: &mut World) -> Entity {
+        world
+            .query::<(Entity, &AntRole)>()
+            .iter(world)
+            .find(|(_, &ant_role)| ant_role == AntRole::Queen)
+            .map(|(entity, _)| entity)
+            .unwrap()
+    }
+
+    /// Fill the bottom row of the nest with food.
+    fn store_food(world: &mut World) -> usize {
+        let mut element_grid = world
+            .query_filtered::<&mut ElementGrid, With<AtNest>>()
+            .single_mut(world);
+        let bottom = element_grid.height() - 1;
+
+        (0..element_grid.width())
+            .filter(|&x| element_grid.set(Position::new(x, bottom), Element::Food))
+            .count()
+    }
+
+    fn count_food(world: &mut World) -> usize {
+        world
+            .query_filtered::<&ElementGrid, With<AtNest>>()
+            .single(world)
+            .iter()
+            .filter(|&(_, element)| element == Element::Food)
+            .count()
+    }
+
+    #[test]
+    fn ants_starve_without_food() {
+        let mut app = story_app();
+        let elapsed_ticks = app.world.resource::<StoryTime>().elapsed_ticks();
+
+        catch_up(&mut app, 2 * TICKS_PER_DAY);
+
+        let queen = queen(&mut app.world);
+        assert!(app.world.get::<Dead>(queen).is_some());
+        assert_eq!(
+            app.world.resource::<StoryTime>().elapsed_ticks(),
+            elapsed_ticks + 2 * TICKS_PER_DAY
+        );
+    }
+
+    #[test]
+    fn ants_eat_stored_food() {
+        let mut app = story_app();
+        let stored_food = store_food(&mut app.world);
+
+        catch_up(&mut app, 2 * TICKS_PER_DAY);
+
+        let queen = queen(&mut app.world);
+        assert!(app.world.get::<Dead>(queen).is_none());
+        assert!(count_food(&mut app.world) < stored_food);
+    }
+
+    #[test]
+    fn stored_food_spoils() {
+        let mut app = story_app();
+        store_food(&mut app.world);
+        let food_spoil_time = app.world.resource::<Settings>().food_spoil_time;
+
+        catch_up(
+            &mut app,
+            (food_spoil_time + SECONDS_PER_DAY) * DEFAULT_TICKS_PER_SECOND,
+        );
+
+        let waste = app
+            .world
+            .query_filtered::<&ElementGrid, With<AtNest>>()
+            .single(&app.world)
+            .iter()
+            .filter(|&(_, element)| element == Element::Waste)
+            .count();
+
+        assert_eq!(count_food(&mut app.world), 0);
+        assert!(waste > 0);
+    }
+
+    #[test]
+    fn queens_give_birth() {
+        let mut app = story_app();
+        let max_birthing_time = app.world.resource::<Settings>().max_birthing_time;
+        let queen = queen(&mut app.world);
+        app.world
+            .entity_mut(queen)
+            .insert(Birthing::new(max_birthing_time));
+        let ant_count = app.world.query::<&Ant>().iter(&app.world).count();
+
+        catch_up(&mut app, 5 * max_birthing_time * DEFAULT_TICKS_PER_SECOND);
+
+        assert_eq!(
+            app.world.query::<&Ant>().iter(&app.world).count(),
+            ant_count + 5
+        );
+    }
+
+    #[test]
+    fn loading_after_long_absence_summarizes_all_but_last_day() {
+        let mut app = story_app();
+        let elapsed_ticks = app.world.resource::<StoryTime>().elapsed_ticks();
+        app.world.resource_mut::<StoryRealWorldTime>().0 =
+            Utc::now().timestamp_millis() - 3 * SECONDS_PER_DAY as i64 * 1_000;
+        let data = create_save_data(&mut app.world).unwrap();
+
+        let mut loaded_app = App::new();
+        loaded_app.add_plugins(HeadlessPlugin);
+        load_story(&mut loaded_app, &data).unwrap();
+        loaded_app
+            .world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::FinishSetup);
+        begin_story(&mut loaded_app);
+
+        let journal_entry = *loaded_app
+            .world
+            .resource::<Journal>()
+            .entries()
+            .last()
+            .unwrap();
+        assert_eq!(journal_entry.tick, elapsed_ticks);
+
+        let JournaledEvent::Absence(ticks) = journal_entry.event else {
+            panic!("Expected an absence to be journaled");
+        };
+        // Allow for a second or so of real-world time to have passed while the test ran.
+        assert!((ticks - 2 * TICKS_PER_DAY).abs() <= 2 * DEFAULT_TICKS_PER_SECOND);
+        assert_eq!(
+            loaded_app.world.resource::<StoryTime>().elapsed_ticks(),
+            elapsed_ticks + ticks
+        );
+    }
+}
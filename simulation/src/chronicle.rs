@@ -0,0 +1,322 @@
+use crate::{
+    common::{
+        ant::{death::DeathCause, AntDiedEvent, AntName},
+        Zone,
+    },
+    external_event::ExternalSimulationEvent,
+    story_time::StoryTime,
+};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// A change the player made to the story while playing in the sandbox.
+#[derive(Reflect, PartialEq, Eq, Copy, Clone, Debug)]
+pub enum SandboxEdit {
+    SpawnFood,
+    SpawnDirt,
+    SpawnSand,
+    DespawnElement,
+    KillAnt,
+    SpawnWorkerAnt,
+    DespawnWorkerAnt,
+    SpawnRock,
+    SpawnWater,
+}
+
+impl<Z: Zone> From<ExternalSimulationEvent<Z>> for SandboxEdit {
+    fn from(event: ExternalSimulationEvent<Z>) -> Self {
+        match event {
+            ExternalSimulationEvent::SpawnFood(..) => SandboxEdit::SpawnFood,
+            ExternalSimulationEvent::SpawnDirt(..) => SandboxEdit::SpawnDirt,
+            ExternalSimulationEvent::SpawnSand(..) => SandboxEdit::SpawnSand,
+            ExternalSimulationEvent::DespawnElement(..) => SandboxEdit::DespawnElement,
+            ExternalSimulationEvent::KillAnt(..) => SandboxEdit::KillAnt,
+            ExternalSimulationEvent::SpawnWorkerAnt(..) => SandboxEdit::SpawnWorkerAnt,
+            ExternalSimulationEvent::DespawnWorkerAnt(..) => SandboxEdit::DespawnWorkerAnt,
+            ExternalSimulationEvent::SpawnRock(..) => SandboxEdit::SpawnRock,
+            ExternalSimulationEvent::SpawnWater(..) => SandboxEdit::SpawnWater,
+        }
+    }
+}
+
+/// Something notable which happened in the story. Ants are remembered by name because they might not be around later.
+#[derive(Reflect, PartialEq, Clone, Debug)]
+pub enum ChronicleEvent {
+    NestFounded {
+        queen: String,
+    },
+    Birth {
+        mother: String,
+        child: String,
+    },
+    Death {
+        name: String,
+        cause: DeathCause,
+    },
+    FirstCraterTrip {
+        name: String,
+    },
+    /// Repeated edits, e.g. placing food one tile at a time, are counted in a single entry.
+    SandboxEdit {
+        edit: SandboxEdit,
+        count: usize,
+    },
+    FirstFoodFromCrater {
+        name: String,
+    },
+}
+
+/// When, in story time, an event happened.
+#[derive(Reflect, PartialEq, Clone, Debug)]
+pub struct ChronicleEntry {
+    pub day: isize,
+    pub hour: isize,
+    pub event: ChronicleEvent,
+}
+
+/// A history of the story's notable events, oldest first, which is saved along with the story.
+/// Unlike Journal, which records inputs so the story can be replayed, this is written for the player to read.
+/// Stories saved before chronicling existed begin with an empty chronicle.
+#[derive(Resource, Reflect, Default, Clone, Debug)]
+#[reflect(Resource)]
+pub struct Chronicle {
+    entries: Vec<ChronicleEntry>,
+}
+
+impl Chronicle {
+    pub fn entries(&self) -> &[ChronicleEntry] {
+        &self.entries
+    }
+
+    pub fn has_visited_crater(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| matches!(entry.event, ChronicleEvent::FirstCraterTrip { .. }))
+    }
+
+    pub fn has_brought_food_from_crater(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| matches!(entry.event, ChronicleEvent::FirstFoodFromCrater { .. }))
+    }
+
+    pub fn record(&mut self, day: isize, hour: isize, event: ChronicleEvent) {
+        if let ChronicleEvent::SandboxEdit { edit, count } = event {
+            if let Some(ChronicleEntry {
+                day: last_day,
+                hour: last_hour,
+                event:
+                    ChronicleEvent::SandboxEdit {
+                        edit: last_edit,
+                        count: last_count,
+                    },
+            }) = self.entries.last_mut()
+            {
+                if (*last_day, *last_hour, *last_edit) == (day, hour, edit) {
+                    *last_count += count;
+                    return;
+                }
+            }
+        }
+
+        self.entries.push(ChronicleEntry { day, hour, event });
+    }
+}
+
+/// Records events in the Chronicle at the current story time.
+#[derive(SystemParam)]
+pub struct ChronicleWriter<'w> {
+    chronicle: ResMut<'w, Chronicle>,
+    story_time: Res<'w, StoryTime>,
+}
+
+impl<'w> ChronicleWriter<'w> {
+    pub fn record(&mut self, event: ChronicleEvent) {
+        let time_info = self.story_time.as_time_info();
+
+        self.chronicle
+            .record(time_info.days(), time_info.hours(), event);
+    }
+
+    pub fn has_visited_crater(&self) -> bool {
+        self.chronicle.has_visited_crater()
+    }
+
+    pub fn has_brought_food_from_crater(&self) -> bool {
+        self.chronicle.has_brought_food_from_crater()
+    }
+}
+
+pub fn register_chronicle(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Chronicle>();
+    app_type_registry.write().register::<ChronicleEntry>();
+    app_type_registry.write().register::<Vec<ChronicleEntry>>();
+    app_type_registry.write().register::<ChronicleEvent>();
+    app_type_registry.write().register::<SandboxEdit>();
+    app_type_registry.write().register::<DeathCause>();
+}
+
+/// Keeps the chronicle of a loaded story, if it has one.
+pub fn initialize_chronicle_resources(mut commands: Commands) {
+    commands.init_resource::<Chronicle>();
+}
+
+pub fn remove_chronicle_resources(mut commands: Commands) {
+    commands.remove_resource::<Chronicle>();
+}
+
+/// Deaths are chronicled from AntDiedEvent, rather than when Dead is added, because only the event knows the cause.
+pub fn chronicle_ant_deaths(
+    mut ant_died_events: EventReader<AntDiedEvent>,
+    ants_query: Query<&AntName>,
+    mut chronicle_writer: ChronicleWriter,
+) {
+    for &AntDiedEvent(ant_entity, cause) in ant_died_events.read() {
+        if let Ok(name) = ants_query.get(ant_entity) {
+            chronicle_writer.record(ChronicleEvent::Death {
+                name: name.0.clone(),
+                cause,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app_state::AppState,
+        catch_up::{catch_up_absence, PendingCatchUp},
+        common::{ant::AntRole, position::Position},
+        headless::{begin_story, load_story, tick, HeadlessPlugin},
+        nest_simulation::nest::AtNest,
+        save::create_save_data,
+        settings::{Settings, Terrain},
+        story_time::{DEFAULT_TICKS_PER_SECOND, SECONDS_PER_DAY},
+    };
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn repeated_sandbox_edits_are_counted_together() {
+        let mut chronicle = Chronicle::default();
+        let spawn_food = ChronicleEvent::SandboxEdit {
+            edit: SandboxEdit::SpawnFood,
+            count: 1,
+        };
+
+        chronicle.record(0, 8, spawn_food.clone());
+        chronicle.record(0, 8, spawn_food.clone());
+        chronicle.record(0, 9, spawn_food.clone());
+
+        assert_eq!(
+            chronicle
+                .entries()
+                .iter()
+                .map(|entry| (entry.hour, entry.event.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    8,
+                    ChronicleEvent::SandboxEdit {
+                        edit: SandboxEdit::SpawnFood,
+                        count: 2,
+                    }
+                ),
+                (9, spawn_food),
+            ]
+        );
+    }
+
+    #[test]
+    fn sandbox_edits_which_change_nothing_arent_chronicled() {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        app.insert_resource(Settings {
+            terrain: Terrain::flat(),
+            ..default()
+        });
+        begin_story(&mut app);
+
+        // The sky is already empty, and there's no room for food in the ground.
+        app.world.send_event(ExternalSimulationEvent::DespawnElement(
+            Position::new(10, 0),
+            AtNest,
+        ));
+        app.world.send_event(ExternalSimulationEvent::SpawnFood(
+            Position::new(10, 100),
+            AtNest,
+        ));
+        tick(&mut app);
+
+        assert!(!app
+            .world
+            .resource::<Chronicle>()
+            .entries()
+            .iter()
+            .any(|entry| matches!(entry.event, ChronicleEvent::SandboxEdit { .. })));
+    }
+
+    #[test]
+    fn chronicle_is_saved_with_story() {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        // Without buried food the colony starves during the absence.
+        app.insert_resource(Settings {
+            terrain: Terrain::flat(),
+            ..default()
+        });
+        begin_story(&mut app);
+
+        app.world.send_event(ExternalSimulationEvent::SpawnFood(
+            Position::new(10, 20),
+            AtNest,
+        ));
+        tick(&mut app);
+
+        app.world.insert_resource(PendingCatchUp(
+            2 * SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND,
+        ));
+        app.world.run_system_once(catch_up_absence);
+
+        let queen_name = app
+            .world
+            .query::<(&AntName, &AntRole)>()
+            .iter(&app.world)
+            .find(|(_, &ant_role)| ant_role == AntRole::Queen)
+            .map(|(name, _)| name.0.clone())
+            .unwrap();
+
+        let chronicle = app.world.resource::<Chronicle>().clone();
+        let events = chronicle
+            .entries()
+            .iter()
+            .map(|entry| entry.event.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events[0],
+            ChronicleEvent::SandboxEdit {
+                edit: SandboxEdit::SpawnFood,
+                count: 1,
+            }
+        );
+        assert!(events.contains(&ChronicleEvent::Death {
+            name: queen_name,
+            cause: DeathCause::Starvation,
+        }));
+
+        let data = create_save_data(&mut app.world).unwrap();
+
+        let mut loaded_app = App::new();
+        loaded_app.add_plugins(HeadlessPlugin);
+        load_story(&mut loaded_app, &data).unwrap();
+        loaded_app
+            .world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::FinishSetup);
+        begin_story(&mut loaded_app);
+
+        assert_eq!(
+            loaded_app.world.resource::<Chronicle>().entries(),
+            chronicle.entries()
+        );
+    }
+}
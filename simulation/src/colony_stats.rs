@@ -0,0 +1,183 @@
+use crate::{
+    common::{
+        ant::{hunger::Hunger, AntRole, Dead},
+        element::Element,
+        grid::ElementGrid,
+        pheromone::Pheromone,
+    },
+    nest_simulation::{
+        ant::birthing::Birthing,
+        nest::{AtNest, Nest},
+    },
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND, SECONDS_PER_HOUR},
+};
+use bevy::prelude::*;
+
+const TICKS_PER_HOUR: isize = SECONDS_PER_HOUR * DEFAULT_TICKS_PER_SECOND;
+
+/// The state of the colony at the start of an hour of story time.
+#[derive(Reflect, PartialEq, Clone, Debug, Default)]
+pub struct ColonyStatsSample {
+    /// Hours since the story began.
+    pub hour: isize,
+    pub queens: usize,
+    pub workers: usize,
+    pub food: usize,
+    pub average_hunger: f32,
+    pub queen_birthing: f32,
+    /// Tiles of air below the surface of the nest.
+    pub tunnel_area: usize,
+    pub pheromones: usize,
+}
+
+impl ColonyStatsSample {
+    pub fn days(&self) -> f32 {
+        self.hour as f32 / 24.0
+    }
+}
+
+/// Hourly samples of the colony, oldest first, which are saved along with the story so it can be charted over days.
+/// Stories saved before sampling existed begin charting from when they're next loaded.
+#[derive(Resource, Reflect, Default, Clone, Debug)]
+#[reflect(Resource)]
+pub struct ColonyStats {
+    samples: Vec<ColonyStatsSample>,
+}
+
+impl ColonyStats {
+    pub fn samples(&self) -> &[ColonyStatsSample] {
+        &self.samples
+    }
+
+    fn is_sampled(&self, hour: isize) -> bool {
+        self.samples
+            .last()
+            .is_some_and(|sample| sample.hour >= hour)
+    }
+}
+
+pub fn register_colony_stats(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<ColonyStats>();
+    app_type_registry.write().register::<ColonyStatsSample>();
+    app_type_registry
+        .write()
+        .register::<Vec<ColonyStatsSample>>();
+}
+
+/// Keeps the samples of a loaded story, if it has any.
+pub fn initialize_colony_stats_resources(mut commands: Commands) {
+    commands.init_resource::<ColonyStats>();
+}
+
+pub fn remove_colony_stats_resources(mut commands: Commands) {
+    commands.remove_resource::<ColonyStats>();
+}
+
+/// Sample the colony once per hour of story time. Runs every tick, but does nothing until the next hour begins.
+pub fn sample_colony_stats(
+    ants_query: Query<(&AntRole, &Hunger, Option<&Birthing>), Without<Dead>>,
+    element_grid_query: Query<(&ElementGrid, Has<AtNest>)>,
+    nest_query: Query<&Nest>,
+    pheromone_query: Query<(), With<Pheromone>>,
+    story_time: Res<StoryTime>,
+    mut colony_stats: ResMut<ColonyStats>,
+) {
+    let hour = story_time.elapsed_ticks() / TICKS_PER_HOUR;
+
+    if colony_stats.is_sampled(hour) {
+        return;
+    }
+
+    let queens = ants_query
+        .iter()
+        .filter(|(&role, _, _)| role == AntRole::Queen)
+        .count();
+    let workers = ants_query
+        .iter()
+        .filter(|(&role, _, _)| role == AntRole::Worker)
+        .count();
+
+    let ant_count = ants_query.iter().count();
+    let average_hunger = if ant_count > 0 {
+        ants_query
+            .iter()
+            .map(|(_, hunger, _)| hunger.value())
+            .sum::<f32>()
+            / ant_count as f32
+    } else {
+        0.0
+    };
+
+    let queen_birthing = ants_query
+        .iter()
+        .find(|(&role, _, _)| role == AntRole::Queen)
+        .and_then(|(_, _, birthing)| birthing.map(|birthing| birthing.value()))
+        .unwrap_or(0.0);
+
+    let food = element_grid_query
+        .iter()
+        .flat_map(|(element_grid, _)| element_grid.iter())
+        .filter(|&(_, element)| element == Element::Food)
+        .count();
+
+    let tunnel_area = match (
+        element_grid_query.iter().find(|&(_, at_nest)| at_nest),
+        nest_query.get_single(),
+    ) {
+        (Some((element_grid, _)), Ok(nest)) => element_grid
+            .iter()
+            .filter(|&(position, element)| {
+                element == Element::Air && nest.is_underground(&position)
+            })
+            .count(),
+        _ => 0,
+    };
+
+    colony_stats.samples.push(ColonyStatsSample {
+        hour,
+        queens,
+        workers,
+        food,
+        average_hunger,
+        queen_birthing,
+        tunnel_area,
+        pheromones: pheromone_query.iter().count(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        catch_up::{catch_up_absence, PendingCatchUp},
+        headless::{begin_story, tick, HeadlessPlugin},
+        settings::{Settings, Terrain},
+    };
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn colony_is_sampled_hourly() {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        app.insert_resource(Settings {
+            terrain: Terrain::flat(),
+            ..default()
+        });
+        begin_story(&mut app);
+        tick(&mut app);
+
+        app.world
+            .insert_resource(PendingCatchUp(48 * TICKS_PER_HOUR));
+        app.world.run_system_once(catch_up_absence);
+        tick(&mut app);
+
+        let samples = app.world.resource::<ColonyStats>().samples();
+        let hours = samples.iter().map(|sample| sample.hour).collect::<Vec<_>>();
+        assert_eq!(hours, (0..=48).collect::<Vec<_>>());
+
+        let (first, last) = (&samples[0], &samples[samples.len() - 1]);
+        assert_eq!(first.queens, 1);
+        // Nobody eats while the colony has no food, so every ant starves before the absence is over.
+        assert_eq!((last.queens, last.workers), (0, 0));
+    }
+}
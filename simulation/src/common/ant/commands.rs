@@ -1,11 +1,11 @@
 use crate::{
     common::{
         ant::{
-            digestion::Digestion, hunger::Hunger, AntBundle, AntColor, AntInventory, AntName,
-            AntOrientation, AntRole, Initiative, InventoryItemBundle,
+            digestion::Digestion, hunger::Hunger, AntBundle, AntColor, AntDugEvent, AntInventory,
+            AntName, AntOrientation, AntRole, Initiative, InventoryItemBundle,
         },
-        element::{Element, ElementBundle},
-        grid::{GridElements, GridElementsMut},
+        element::Element,
+        grid::GridElementsMut,
         position::Position,
         Zone,
     },
@@ -16,6 +16,7 @@ use bevy::{
     prelude::*,
 };
 use core::panic;
+use std::marker::PhantomData;
 
 pub trait AntCommandsExt {
     fn spawn_ant<Z: Zone>(
@@ -33,14 +34,14 @@ pub trait AntCommandsExt {
         &mut self,
         ant_entity: Entity,
         target_position: Position,
-        target_element_entity: Entity,
+        target_element: Element,
         zone: Z,
     );
     fn drop<Z: Zone>(
         &mut self,
         ant_entity: Entity,
         target_position: Position,
-        target_element_entity: Entity,
+        target_element: Element,
         zone: Z,
     );
 }
@@ -73,13 +74,13 @@ impl<'w, 's> AntCommandsExt for Commands<'w, 's> {
         &mut self,
         ant_entity: Entity,
         target_position: Position,
-        target_element_entity: Entity,
+        target_element: Element,
         zone: Z,
     ) {
         self.add(DigElementCommand {
             ant_entity,
             target_position,
-            target_element_entity,
+            target_element,
             zone,
         });
     }
@@ -88,21 +89,21 @@ impl<'w, 's> AntCommandsExt for Commands<'w, 's> {
         &mut self,
         ant_entity: Entity,
         target_position: Position,
-        target_element_entity: Entity,
-        zone: Z,
+        target_element: Element,
+        _zone: Z,
     ) {
-        self.add(DropElementCommand {
+        self.add(DropElementCommand::<Z> {
             ant_entity,
             target_position,
-            target_element_entity,
-            zone,
+            target_element,
+            zone: PhantomData,
         });
     }
 }
 
 struct DigElementCommand<Z: Zone + Copy> {
     ant_entity: Entity,
-    target_element_entity: Entity,
+    target_element: Element,
     target_position: Position,
     zone: Z,
 }
@@ -110,47 +111,23 @@ struct DigElementCommand<Z: Zone + Copy> {
 // TODO: Confirm that ant and element are adjacent to one another at time action is taken.
 impl<Z: Zone + Copy> Command for DigElementCommand<Z> {
     fn apply(self, world: &mut World) {
-        let mut system_state: SystemState<GridElements<Z>> = SystemState::new(world);
-        let grid_elements = system_state.get(world);
+        let mut system_state: SystemState<GridElementsMut<Z>> = SystemState::new(world);
+        let mut grid_elements = system_state.get_mut(world);
 
-        let element_entity = match grid_elements.get_entity(self.target_position) {
-            Some(entity) => *entity,
+        let element = match grid_elements.get_element(self.target_position) {
+            Some(element) => element,
             None => {
-                info!("No entity found at position {:?}", self.target_position);
+                info!("No element found at position {:?}", self.target_position);
                 return;
             }
         };
 
-        if element_entity != self.target_element_entity {
-            info!("Existing element entity doesn't match the target element entity.");
+        if element != self.target_element {
+            info!("Existing element doesn't match the target element.");
             return;
         }
 
-        let element = match world.get::<Element>(element_entity) {
-            Some(element) => *element,
-            None => {
-                info!(
-                    "Failed to get Element component for element entitity {:?}.",
-                    element_entity
-                );
-                return;
-            }
-        };
-
-        world.entity_mut(element_entity).despawn();
-
-        let air_entity = world
-            .spawn(ElementBundle::new(
-                Element::Air,
-                self.target_position,
-                self.zone,
-            ))
-            .id();
-
-        let mut system_state: SystemState<GridElementsMut<Z>> = SystemState::new(world);
-        let mut grid_elements = system_state.get_mut(world);
-
-        grid_elements.set(self.target_position, air_entity);
+        grid_elements.set(self.target_position, Element::Air);
 
         // TODO: There's probably a more elegant way to express this - "denseness" of sand rather than changing between dirt/sand.
         let mut inventory_element = element;
@@ -171,37 +148,36 @@ impl<Z: Zone + Copy> Command for DigElementCommand<Z> {
             Some(mut initiative) => initiative.consume(),
             None => panic!("Failed to get initiative for ant {:?}", self.ant_entity),
         };
+
+        world.send_event(AntDugEvent(self.ant_entity, element));
     }
 }
 
 struct DropElementCommand<Z: Zone> {
     ant_entity: Entity,
-    target_element_entity: Entity,
+    target_element: Element,
     target_position: Position,
-    zone: Z,
+    zone: PhantomData<Z>,
 }
 
 impl<Z: Zone> Command for DropElementCommand<Z> {
     fn apply(self, world: &mut World) {
-        let mut system_state: SystemState<GridElements<Z>> = SystemState::new(world);
-        let grid_elements = system_state.get(world);
+        let mut system_state: SystemState<GridElementsMut<Z>> = SystemState::new(world);
+        let grid_elements = system_state.get_mut(world);
 
-        let air_entity = match grid_elements.get_entity(self.target_position) {
-            Some(entity) => *entity,
+        let existing_element = match grid_elements.get_element(self.target_position) {
+            Some(element) => element,
             None => {
-                info!("No entity found at position {:?}", self.target_position);
+                info!("No element found at position {:?}", self.target_position);
                 return;
             }
         };
 
-        if air_entity != self.target_element_entity {
-            info!("Existing element entity doesn't match the target element entity.");
+        if existing_element != self.target_element {
+            info!("Existing element doesn't match the target element.");
             return;
         }
 
-        // Remove air element from world.
-        world.entity_mut(air_entity).despawn();
-
         let inventory = match world.get::<AntInventory>(self.ant_entity) {
             Some(inventory) => inventory,
             None => panic!("Failed to get inventory for ant {:?}", self.ant_entity),
@@ -212,21 +188,11 @@ impl<Z: Zone> Command for DropElementCommand<Z> {
             None => panic!("Ant {:?} has no element in inventory", self.ant_entity),
         };
 
-        let element = world.get::<Element>(inventory_item_entity).unwrap();
+        let element = *world.get::<Element>(inventory_item_entity).unwrap();
 
         // Add element to world.
-        let element_entity = world
-            .spawn(ElementBundle::new(
-                *element,
-                self.target_position,
-                self.zone,
-            ))
-            .id();
-
-        let mut system_state: SystemState<GridElementsMut<Z>> = SystemState::new(world);
         let mut grid_elements = system_state.get_mut(world);
-
-        grid_elements.set(self.target_position, element_entity);
+        grid_elements.set(self.target_position, element);
 
         // Remove element from ant inventory.
         world.entity_mut(inventory_item_entity).despawn();
@@ -7,22 +7,32 @@ use crate::common::{
 };
 use bevy::prelude::*;
 
+#[derive(Reflect, PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum DeathCause {
+    Starvation,
+    /// Killed by the player.
+    Killed,
+    Drowned,
+}
+
+/// Ants in zone `Z` which have just died.
+type NewlyDeadAntFilter<Z> = (Added<Dead>, With<Z>);
+
 /// Force ants to drop, or despawn, their inventory upon death.
 /// TODO:
 ///     * It might be preferable to find an adjacent, available zone to move inventory to rather than despawning.
 pub fn on_ants_add_dead<Z: Zone + Copy>(
-    ants_query: Query<(Entity, &Position, &AntInventory, &Z), (Added<Dead>, With<Z>)>,
+    mut ants_query: Query<(Entity, &Position, &mut AntInventory, &Z), NewlyDeadAntFilter<Z>>,
     mut commands: Commands,
     grid_elements: GridElements<Z>,
 ) {
-    for (ant_entity, ant_position, ant_inventory, zone) in ants_query.iter() {
-        if ant_inventory.0 != None {
-            let element_entity = grid_elements.entity(*ant_position);
-
+    for (ant_entity, ant_position, mut ant_inventory, zone) in ants_query.iter_mut() {
+        if let Some(inventory_item_entity) = ant_inventory.0 {
             if grid_elements.is(*ant_position, Element::Air) {
-                commands.drop(ant_entity, *ant_position, *element_entity, *zone);
+                commands.drop(ant_entity, *ant_position, Element::Air, *zone);
             } else {
-                commands.entity(*element_entity).remove_parent().despawn();
+                commands.entity(inventory_item_entity).despawn();
+                ant_inventory.0 = None;
             }
         }
     }
@@ -46,7 +46,12 @@ impl Digestion {
     }
 
     pub fn tick(&mut self) -> f32 {
-        let new_value = (self.value + self.rate).min(self.max);
+        self.tick_by(1)
+    }
+
+    /// Equivalent to calling `tick` repeatedly. Returns the total amount digested.
+    pub fn tick_by(&mut self, ticks: isize) -> f32 {
+        let new_value = (self.value + self.rate * ticks as f32).min(self.max);
         let change = new_value - self.value;
         self.value = new_value;
         change
@@ -2,8 +2,8 @@ use super::digestion::Digestion;
 use crate::{
     common::{
         ant::{
-            commands::AntCommandsExt, AntAteFoodEvent, AntInventory, AntOrientation, AntRole, Dead,
-            Initiative,
+            commands::AntCommandsExt, death::DeathCause, position_map::AntPositionMap,
+            AntAteFoodEvent, AntDiedEvent, AntInventory, AntOrientation, AntRole, Dead, Initiative,
         },
         element::Element,
         grid::GridElements,
@@ -47,6 +47,11 @@ impl Hunger {
         self.value = (self.value + self.rate).min(self.max);
     }
 
+    /// Equivalent to calling `tick` repeatedly.
+    pub fn tick_by(&mut self, ticks: isize) {
+        self.value = (self.value + self.rate * ticks as f32).min(self.max);
+    }
+
     pub fn is_full(&self) -> bool {
         self.value < self.max * 0.25
     }
@@ -96,6 +101,7 @@ pub fn ants_hunger_act<Z: Zone + Copy>(
     elements_query: Query<&Element, With<Z>>,
     mut commands: Commands,
     mut ant_ate_food_event_writer: EventWriter<AntAteFoodEvent>,
+    mut ant_died_event_writer: EventWriter<AntDiedEvent>,
 ) {
     for (
         ant_entity,
@@ -113,6 +119,8 @@ pub fn ants_hunger_act<Z: Zone + Copy>(
                 .entity(ant_entity)
                 .insert(Dead)
                 .remove::<Initiative>();
+
+            ant_died_event_writer.send(AntDiedEvent(ant_entity, DeathCause::Starvation));
         } else if hunger.is_peckish() {
             if !initiative.can_act() {
                 continue;
@@ -122,8 +130,7 @@ pub fn ants_hunger_act<Z: Zone + Copy>(
             if inventory.0 == None {
                 let ahead_position = orientation.get_ahead_position(position);
                 if grid_elements.is(ahead_position, Element::Food) {
-                    let food_entity = grid_elements.entity(ahead_position);
-                    commands.dig(ant_entity, ahead_position, *food_entity, *zone);
+                    commands.dig(ant_entity, ahead_position, Element::Food, *zone);
                 }
             } else {
                 let element = elements_query.get(inventory.0.unwrap()).unwrap();
@@ -150,6 +157,7 @@ pub fn ants_hunger_act<Z: Zone + Copy>(
 // Step 1: Find all ants which are hungry or worse.
 // Step 2: For each hungry-or-worse ant, look at the position directly in front of it.
 // Step 3: If there is an ant in that position, and if that ant is facing towards the hungry ant, then transfer food to the hungry ant.
+// Partners are looked up in AntPositionMap, rather than by checking every other ant, because only adjacent ants can be partners.
 pub fn ants_hunger_regurgitate<Z: Zone>(
     mut ants_hunger_query: Query<
         (
@@ -164,6 +172,7 @@ pub fn ants_hunger_regurgitate<Z: Zone>(
         ),
         With<Z>,
     >,
+    ant_position_map: Res<AntPositionMap<Z>>,
     mut ant_ate_food_event_writer: EventWriter<AntAteFoodEvent>,
 ) {
     let peckish_ants = ants_hunger_query
@@ -178,41 +187,44 @@ pub fn ants_hunger_regurgitate<Z: Zone>(
     for (ant_entity, ant_hunger, _, ant_orientation, ant_position, _, _, ant_role) in peckish_ants {
         let ahead_position = ant_orientation.get_ahead_position(ant_position);
 
+        // Support ontop of as well as in front because its kinda challenging to ensure queen can have an ant directly in front of them.
+        let other_ant_entity = ant_position_map.get_nearest(ant_position, 1, |&other_ant_entity| {
+            let Ok((
+                _,
+                _,
+                _,
+                other_ant_orientation,
+                &other_ant_position,
+                other_ant_inventory,
+                other_ant_initiative,
+                _,
+            )) = ants_hunger_query.get(other_ant_entity)
+            else {
+                return false;
+            };
+
+            if !other_ant_initiative.can_act() || other_ant_inventory.0.is_some() {
+                return false;
+            }
+
+            // If ants are adjacent and facing one another - allow regurgitation.
+            if other_ant_position == ahead_position
+                && other_ant_orientation.get_ahead_position(&other_ant_position) == *ant_position
+            {
+                return true;
+            }
+
+            // If ants are standing ontop of one another (and not the same ant) - allow regurgitation
+            if other_ant_position == *ant_position && other_ant_entity != ant_entity {
+                return true;
+            }
+
+            false
+        });
+
         if let Some((other_ant_entity, other_ant_hunger, other_ant_digestion, _, _, _, _, _)) =
-            ants_hunger_query
-                .iter()
-                // Support ontop of as well as in front because its kinda challenging to ensure queen can have an ant directly in front of them.
-                .find(
-                    |(
-                        other_ant_entity,
-                        _,
-                        _,
-                        other_ant_orientation,
-                        &other_ant_position,
-                        other_ant_inventory,
-                        other_ant_initiative,
-                        _,
-                    )| {
-                        if !other_ant_initiative.can_act() || other_ant_inventory.0 != None {
-                            return false;
-                        }
-
-                        // If ants are adjacent and facing one another - allow regurgitation.
-                        if other_ant_position == ahead_position
-                            && other_ant_orientation.get_ahead_position(&other_ant_position)
-                                == *ant_position
-                        {
-                            return true;
-                        }
-
-                        // If ants are standing ontop of one another (and not the same ant) - allow regurgitation
-                        if other_ant_position == *ant_position && *other_ant_entity != ant_entity {
-                            return true;
-                        }
-
-                        return false;
-                    },
-                )
+            other_ant_entity
+                .and_then(|other_ant_entity| ants_hunger_query.get(other_ant_entity).ok())
         {
             if *ant_role == AntRole::Queen
                 || (ant_hunger.is_starving() && !other_ant_hunger.is_hungry())
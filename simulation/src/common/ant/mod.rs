@@ -3,10 +3,11 @@ pub mod death;
 pub mod digestion;
 pub mod hunger;
 pub mod initiative;
+pub mod position_map;
 // pub mod sleep;
 mod name_list;
 
-use self::{digestion::Digestion, hunger::Hunger, name_list::get_random_name};
+use self::{death::DeathCause, digestion::Digestion, hunger::Hunger, name_list::get_random_name};
 use crate::common::{element::Element, position::Position, Zone};
 use bevy::{
     ecs::{
@@ -97,6 +98,13 @@ impl MapEntities for AntInventory {
 #[derive(Event, PartialEq, Copy, Clone, Debug)]
 pub struct AntAteFoodEvent(pub Entity);
 
+#[derive(Event, PartialEq, Copy, Clone, Debug)]
+pub struct AntDiedEvent(pub Entity, pub DeathCause);
+
+/// Sent once an ant has dug up an element, and is carrying it, with the element as it was before being dug.
+#[derive(Event, PartialEq, Copy, Clone, Debug)]
+pub struct AntDugEvent(pub Entity, pub Element);
+
 #[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
 #[reflect(Component)]
 pub struct Dead;
@@ -0,0 +1,276 @@
+use crate::common::{ant::Ant, position::Position, Zone};
+use bevy::{prelude::*, utils::HashMap};
+use std::marker::PhantomData;
+
+/// Note the intentional omission of reflection/serialization.
+/// Like PheromoneMap, this is a cache that is trivially regenerated on app startup from persisted state.
+///
+/// Ants are kept in the order they arrived at each position so that lookups are deterministic.
+#[derive(Resource, Debug)]
+pub struct AntPositionMap<Z: Zone> {
+    map: HashMap<Position, Vec<Entity>>,
+    positions: HashMap<Entity, Position>,
+    _marker: PhantomData<Z>,
+}
+
+impl<Z: Zone> Default for AntPositionMap<Z> {
+    fn default() -> Self {
+        Self {
+            map: HashMap::default(),
+            positions: HashMap::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<Z: Zone> AntPositionMap<Z> {
+    pub fn new(ants: impl IntoIterator<Item = (Entity, Position)>) -> Self {
+        let mut ant_position_map = Self::default();
+
+        for (entity, position) in ants {
+            ant_position_map.insert(entity, position);
+        }
+
+        ant_position_map
+    }
+
+    /// Ants standing at `position`.
+    pub fn get(&self, position: &Position) -> &[Entity] {
+        self.map.get(position).map_or(&[], |entities| entities)
+    }
+
+    /// Ants whose Manhattan distance from `position` is no more than `radius`, nearest first.
+    pub fn get_within_radius(&self, position: &Position, radius: isize) -> Vec<Entity> {
+        self.iter_within_radius(*position, radius).collect()
+    }
+
+    /// The nearest ant, no more than `radius` away from `position`, for which `filter` returns true.
+    /// Ants at the same distance are considered top to bottom and then left to right.
+    pub fn get_nearest(
+        &self,
+        position: &Position,
+        radius: isize,
+        filter: impl FnMut(&Entity) -> bool,
+    ) -> Option<Entity> {
+        self.iter_within_radius(*position, radius).find(filter)
+    }
+
+    /// Walk outward from `position` one ring at a time. When the area to search dwarfs the number of occupied positions,
+    /// it's cheaper to sort the occupied positions instead.
+    fn iter_within_radius(
+        &self,
+        position: Position,
+        radius: isize,
+    ) -> Box<dyn Iterator<Item = Entity> + '_> {
+        if radius < 0 {
+            return Box::new(std::iter::empty());
+        }
+
+        let area = 2 * radius * (radius + 1) + 1;
+
+        if area as usize <= self.map.len() {
+            return Box::new(
+                (0..=radius)
+                    .flat_map(move |distance| get_ring_positions(position, distance))
+                    .flat_map(|ring_position| self.get(&ring_position).iter().copied()),
+            );
+        }
+
+        let mut occupied_positions = self
+            .map
+            .keys()
+            .filter(|occupied_position| occupied_position.distance(&position) <= radius)
+            .collect::<Vec<_>>();
+
+        occupied_positions.sort_by_key(|occupied_position| {
+            (
+                occupied_position.distance(&position),
+                occupied_position.y,
+                occupied_position.x,
+            )
+        });
+
+        Box::new(
+            occupied_positions
+                .into_iter()
+                .flat_map(|occupied_position| self.get(occupied_position).iter().copied()),
+        )
+    }
+
+    /// Move an ant to `position`, adding it if it's new. Does nothing if it's already there.
+    fn insert(&mut self, entity: Entity, position: Position) {
+        match self.positions.insert(entity, position) {
+            Some(previous_position) if previous_position == position => return,
+            Some(previous_position) => self.remove_from_position(entity, previous_position),
+            None => {}
+        }
+
+        self.map.entry(position).or_default().push(entity);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(position) = self.positions.remove(&entity) {
+            self.remove_from_position(entity, position);
+        }
+    }
+
+    fn remove_from_position(&mut self, entity: Entity, position: Position) {
+        if let Some(entities) = self.map.get_mut(&position) {
+            entities.retain(|&other_entity| other_entity != entity);
+
+            if entities.is_empty() {
+                self.map.remove(&position);
+            }
+        }
+    }
+}
+
+/// Positions at exactly `distance` from `position`, top to bottom and then left to right.
+fn get_ring_positions(position: Position, distance: isize) -> impl Iterator<Item = Position> {
+    (-distance..=distance).flat_map(move |y_offset| {
+        let x_offset = distance - y_offset.abs();
+        let x_offsets = if x_offset == 0 {
+            vec![0]
+        } else {
+            vec![-x_offset, x_offset]
+        };
+
+        x_offsets
+            .into_iter()
+            .map(move |x_offset| position + Position::new(x_offset, y_offset))
+    })
+}
+
+/// Ants in zone `Z`.
+type ZoneAntFilter<Z> = (With<Ant>, With<Z>);
+
+/// Ants in zone `Z` which have moved, or arrived in the zone, since last checked.
+type MovedZoneAntFilter<Z> = (With<Ant>, With<Z>, Or<(Changed<Position>, Added<Z>)>);
+
+/// Called after creating a new story, or loading an existing story from storage.
+/// Creates a cache that maps positions to ant entities so ants can be found without searching through every ant.
+pub fn initialize_ant_position_resources<Z: Zone>(
+    ants_query: Query<(Entity, &Position), ZoneAntFilter<Z>>,
+    mut commands: Commands,
+) {
+    let ants = ants_query
+        .iter()
+        .map(|(entity, position)| (entity, *position));
+
+    commands.insert_resource(AntPositionMap::<Z>::new(ants));
+}
+
+pub fn remove_ant_position_resources<Z: Zone>(mut commands: Commands) {
+    commands.remove_resource::<AntPositionMap<Z>>();
+}
+
+/// Ants move by writing to their Position directly, from many systems, so, unlike PheromoneMap, the cache can't be
+/// updated alongside each change. Instead, this catches up with every ant which has moved, spawned, despawned,
+/// or traveled between zones since it last ran. Run it before reading AntPositionMap to see where ants are right now.
+pub fn update_ant_position_map<Z: Zone>(
+    ants_query: Query<(Entity, &Position), MovedZoneAntFilter<Z>>,
+    mut removed_zones: RemovedComponents<Z>,
+    mut ant_position_map: ResMut<AntPositionMap<Z>>,
+) {
+    for entity in removed_zones.read() {
+        ant_position_map.remove(entity);
+    }
+
+    for (entity, position) in ants_query.iter() {
+        ant_position_map.insert(entity, *position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        headless::{begin_story, tick, HeadlessPlugin},
+        nest_simulation::nest::AtNest,
+    };
+
+    fn entities(count: u32) -> Vec<Entity> {
+        (0..count).map(Entity::from_raw).collect()
+    }
+
+    #[test]
+    fn moved_ants_leave_their_previous_position() {
+        let [ant, other_ant] = entities(2)[..] else {
+            unreachable!()
+        };
+        let mut ant_position_map = AntPositionMap::<AtNest>::new([
+            (ant, Position::new(1, 1)),
+            (other_ant, Position::new(1, 1)),
+        ]);
+
+        ant_position_map.insert(ant, Position::new(2, 1));
+        ant_position_map.remove(other_ant);
+
+        assert_eq!(ant_position_map.get(&Position::new(1, 1)), &[]);
+        assert_eq!(ant_position_map.get(&Position::new(2, 1)), &[ant]);
+    }
+
+    #[test]
+    fn ants_within_radius_are_nearest_first() {
+        let ants = entities(5);
+        let ant_position_map = AntPositionMap::<AtNest>::new([
+            (ants[0], Position::new(5, 7)),
+            (ants[1], Position::new(5, 6)),
+            (ants[2], Position::new(5, 5)),
+            (ants[3], Position::new(4, 5)),
+            (ants[4], Position::new(50, 50)),
+        ]);
+
+        // A radius of 1 is searched ring by ring, but a radius of 2 covers more positions than are occupied so they're sorted instead.
+        assert_eq!(
+            ant_position_map.get_within_radius(&Position::new(5, 5), 1),
+            vec![ants[2], ants[3], ants[1]]
+        );
+        assert_eq!(
+            ant_position_map.get_within_radius(&Position::new(5, 5), 2),
+            vec![ants[2], ants[3], ants[1], ants[0]]
+        );
+    }
+
+    #[test]
+    fn nearest_ant_matches_filter() {
+        let ants = entities(3);
+        let ant_position_map = AntPositionMap::<AtNest>::new([
+            (ants[0], Position::new(0, 0)),
+            (ants[1], Position::new(2, 0)),
+            (ants[2], Position::new(4, 0)),
+        ]);
+
+        assert_eq!(
+            ant_position_map.get_nearest(&Position::new(0, 0), 10, |&ant| ant != ants[0]),
+            Some(ants[1])
+        );
+        assert_eq!(
+            ant_position_map.get_nearest(&Position::new(0, 0), 3, |&ant| ant == ants[2]),
+            None
+        );
+    }
+
+    #[test]
+    fn map_follows_ants_through_a_story() {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        begin_story(&mut app);
+
+        for _ in 0..500 {
+            tick(&mut app);
+        }
+
+        let ants = app
+            .world
+            .query_filtered::<(Entity, &Position), (With<Ant>, With<AtNest>)>()
+            .iter(&app.world)
+            .map(|(entity, position)| (entity, *position))
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(
+            app.world.resource::<AntPositionMap<AtNest>>().positions,
+            ants
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use super::Element;
+use crate::common::position::Position;
+
+/// A round blob of one element, e.g. a pocket of sand buried in the nest or a patch of food in the crater.
+pub struct Deposit {
+    pub element: Element,
+    pub center: Position,
+    pub radius: isize,
+}
+
+impl Deposit {
+    pub fn contains(&self, position: Position) -> bool {
+        let delta = position - self.center;
+
+        delta.x * delta.x + delta.y * delta.y <= self.radius * self.radius
+    }
+}
@@ -1,25 +1,11 @@
 pub mod commands;
+pub mod deposit;
 
-use crate::common::{position::Position, Zone};
+use crate::common::grid::ElementGrid;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Component, Reflect, Default)]
-#[reflect(Component)]
-pub struct Air;
-
-#[derive(Component, Reflect, Default)]
-#[reflect(Component)]
-pub struct Dirt;
-
-#[derive(Component, Reflect, Default)]
-#[reflect(Component)]
-pub struct Sand;
-
-#[derive(Component, Reflect, Default)]
-#[reflect(Component)]
-pub struct Food;
-
+/// Elements live in their zone's ElementGrid rather than being entities. Ants carry them as InventoryItem entities.
 #[derive(
     Component, Eq, Hash, PartialEq, Copy, Clone, Debug, Serialize, Deserialize, Reflect, Default,
 )]
@@ -30,60 +16,29 @@ pub enum Element {
     Dirt,
     Sand,
     Food,
+    /// Immovable. Rock is never dug, and never falls, so it dissuades ants from digging where it lies.
+    Rock,
+    /// Flows down, and then sideways, until it pools. Ants can't carry water, but can drown in it.
+    Water,
+    /// What food spoils into. Ants won't eat it and haul it out of the nest.
+    Waste,
 }
 
-#[derive(Bundle)]
-pub struct ElementBundle<Z>
-where
-    Z: Zone,
-{
-    element: Element,
-    position: Position,
-    zone: Z,
-}
+impl Element {
+    /// Whether an ant digging into this element can pick it up. Air is nothing to pick up, rock won't budge and
+    /// water slips through an ant's mandibles.
+    pub fn is_diggable(&self) -> bool {
+        !matches!(self, Element::Air | Element::Rock | Element::Water)
+    }
 
-impl<Z: Zone> ElementBundle<Z> {
-    pub fn new(element: Element, position: Position, zone: Z) -> Self {
-        Self {
-            element,
-            position,
-            zone,
-        }
+    /// Whether an ant can move through this element rather than stand on, or cling to, it. Water doesn't hold ants
+    /// up, so they sink through it, and drown, rather than walking across it.
+    pub fn is_passable(&self) -> bool {
+        matches!(self, Element::Air | Element::Water)
     }
 }
 
 pub fn register_element(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<Element>();
-    app_type_registry.write().register::<Air>();
-    app_type_registry.write().register::<Food>();
-    app_type_registry.write().register::<Dirt>();
-    app_type_registry.write().register::<Sand>();
-}
-
-/// Element entities are represented by their Element enum, but the value of this enum isn't Queryable.
-/// As such, map the Element enum into its values, represented as specific Components, and apply those.
-/// This allows systems to Query for Elements of a specific type efficiently.
-pub fn map_element_to_marker(
-    element_query: Query<
-        (Entity, &Element),
-        (Without<Air>, Without<Dirt>, Without<Sand>, Without<Food>),
-    >,
-    mut commands: Commands,
-) {
-    for (entity, element) in element_query.iter() {
-        match element {
-            Element::Air => {
-                commands.entity(entity).insert(Air);
-            }
-            Element::Dirt => {
-                commands.entity(entity).insert(Dirt);
-            }
-            Element::Sand => {
-                commands.entity(entity).insert(Sand);
-            }
-            Element::Food => {
-                commands.entity(entity).insert(Food);
-            }
-        }
-    }
+    app_type_registry.write().register::<ElementGrid>();
 }
@@ -1,9 +1,8 @@
 use super::Zone;
 use crate::common::{element::Element, position::Position};
 use bevy::{ecs::system::SystemParam, prelude::*};
-
-#[derive(Component, Debug)]
-pub struct ElementEntityPositionCache(pub Vec<Vec<Entity>>);
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
 
 #[derive(Component, Debug)]
 pub struct Grid {
@@ -32,46 +31,305 @@ impl Grid {
     }
 }
 
-#[derive(SystemParam)]
-pub struct GridElements<'w, 's, Z: Zone> {
-    elements_cache: Query<'w, 's, &'static ElementEntityPositionCache, With<Z>>,
-    elements: Query<'w, 's, &'static Element, With<Z>>,
+/// Width and height, in tiles, of the square regions an ElementGrid is divided into for tracking where it has changed.
+pub const CHUNK_SIZE: isize = 16;
+
+/// Every Element in a zone, stored densely in row-major order, rather than as one entity per tile.
+/// Each change bumps `revision` and stamps the changed tile, and its row, with it so that readers, e.g. rendering,
+/// can find the tiles which changed since they last looked without change detection on individual entities.
+///
+/// Changes also mark the chunks around them as dirty. Unlike revisions, dirty chunks are persisted and only
+/// have one consumer, gravity, which needs to know exactly where to look even if the story was saved mid-tick.
+///
+/// Tiles can also remember the tick their element was placed at, e.g. so food can age. Changing a tile's element
+/// forgets when it was placed because the new element is yet to be noticed.
+///
+/// Revisions start over when a grid is loaded because everything is new to its readers.
+/// Reflected as a value, rather than a struct, so that it's always (de)serialized with the impls below.
+#[derive(Component, Reflect, Default, Clone, Debug)]
+#[reflect_value(Component, Serialize, Deserialize)]
+pub struct ElementGrid {
+    width: isize,
+    height: isize,
+    elements: Vec<Element>,
+    revisions: Vec<u64>,
+    row_revisions: Vec<u64>,
+    revision: u64,
+    dirty_chunks: Vec<bool>,
+    placed_ticks: HashMap<usize, isize>,
 }
 
-// TODO: The interface here is a little unclear - sometimes querying by Entity other times by Position
-impl<'w, 's, Z: Zone> GridElements<'w, 's, Z> {
-    pub fn entity(&self, position: Position) -> &Entity {
-        self.get_entity(position).expect(&format!(
-            "Element entity not found at the position: {:?}",
-            position
-        ))
+impl ElementGrid {
+    /// Every chunk of a new grid starts off dirty because nothing has looked at it yet.
+    pub fn new(width: isize, height: isize, elements: Vec<Element>) -> Self {
+        assert_eq!(
+            elements.len(),
+            (width * height) as usize,
+            "ElementGrid must have exactly one element per tile"
+        );
+
+        let chunk_count = (get_chunk_count(width) * get_chunk_count(height)) as usize;
+
+        Self {
+            width,
+            height,
+            revisions: vec![0; elements.len()],
+            row_revisions: vec![0; height as usize],
+            elements,
+            revision: 0,
+            dirty_chunks: vec![true; chunk_count],
+            placed_ticks: HashMap::new(),
+        }
+    }
+
+    /// Create a grid by asking for the element at each position, top to bottom and left to right.
+    pub fn from_fn(width: isize, height: isize, f: impl FnMut(Position) -> Element) -> Self {
+        let elements = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Position::new(x, y)))
+            .map(f)
+            .collect();
+
+        Self::new(width, height, elements)
+    }
+
+    pub fn width(&self) -> isize {
+        self.width
+    }
+
+    pub fn height(&self) -> isize {
+        self.height
+    }
+
+    fn get_index(&self, position: Position) -> Option<usize> {
+        let is_within_bounds = position.x >= 0
+            && position.x < self.width
+            && position.y >= 0
+            && position.y < self.height;
+
+        is_within_bounds.then(|| (position.y * self.width + position.x) as usize)
+    }
+
+    fn get_position(&self, index: usize) -> Position {
+        Position::new(index as isize % self.width, index as isize / self.width)
+    }
+
+    pub fn get(&self, position: Position) -> Option<Element> {
+        self.get_index(position).map(|index| self.elements[index])
+    }
+
+    pub fn is(&self, position: Position, element: Element) -> bool {
+        self.get(position) == Some(element)
+    }
+
+    /// Returns false, and changes nothing, if the position is out of bounds or already contains `element`.
+    pub fn set(&mut self, position: Position, element: Element) -> bool {
+        let Some(index) = self.get_index(position) else {
+            return false;
+        };
+
+        if self.elements[index] == element {
+            return false;
+        }
+
+        self.revision += 1;
+        self.elements[index] = element;
+        self.revisions[index] = self.revision;
+        self.row_revisions[position.y as usize] = self.revision;
+        self.placed_ticks.remove(&index);
+
+        // An element appearing might fall, and an element disappearing might let those above it fall.
+        for dirty_position in [
+            position,
+            position + Position::new(-1, -1),
+            position + Position::new(0, -1),
+            position + Position::new(1, -1),
+        ] {
+            self.mark_dirty(dirty_position);
+        }
+
+        true
+    }
+
+    /// The tick the element at `position` was placed at, if it's been recorded since the element last changed.
+    pub fn placed_tick(&self, position: Position) -> Option<isize> {
+        self.get_index(position)
+            .and_then(|index| self.placed_ticks.get(&index).copied())
+    }
+
+    /// Remember that the element at `position` was placed at `tick`. Does nothing if the position is out of bounds.
+    pub fn set_placed_tick(&mut self, position: Position, tick: isize) {
+        if let Some(index) = self.get_index(position) {
+            self.placed_ticks.insert(index, tick);
+        }
+    }
+
+    /// Look at a position again, next time dirty positions are taken, even though nothing there has changed.
+    /// Useful for elements which are at rest but might yet change by chance.
+    pub fn mark_dirty(&mut self, position: Position) {
+        if let Some(chunk_index) = self.get_chunk_index(position) {
+            self.dirty_chunks[chunk_index] = true;
+        }
+    }
+
+    fn get_chunk_index(&self, position: Position) -> Option<usize> {
+        self.get_index(position)?;
+
+        let chunk_columns = get_chunk_count(self.width);
+
+        Some(((position.y / CHUNK_SIZE) * chunk_columns + position.x / CHUNK_SIZE) as usize)
+    }
+
+    /// Positions within every dirty chunk, chunk by chunk, and top to bottom and left to right within each chunk.
+    /// The chunks are clean afterward so only one system should take them.
+    pub fn take_dirty_positions(&mut self) -> Vec<Position> {
+        let chunk_columns = get_chunk_count(self.width);
+        let mut positions = vec![];
+
+        for (chunk_index, is_dirty) in self.dirty_chunks.iter_mut().enumerate() {
+            if !std::mem::take(is_dirty) {
+                continue;
+            }
+
+            let left = (chunk_index as isize % chunk_columns) * CHUNK_SIZE;
+            let top = (chunk_index as isize / chunk_columns) * CHUNK_SIZE;
+
+            for y in top..(top + CHUNK_SIZE).min(self.height) {
+                for x in left..(left + CHUNK_SIZE).min(self.width) {
+                    positions.push(Position::new(x, y));
+                }
+            }
+        }
+
+        positions
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, Element)> + '_ {
+        self.elements
+            .iter()
+            .enumerate()
+            .map(|(index, &element)| (self.get_position(index), element))
+    }
+
+    /// Incremented each time an element changes.
+    pub fn revision(&self) -> u64 {
+        self.revision
     }
 
-    pub fn get_entity(&self, position: Position) -> Option<&Entity> {
-        self.elements_cache
-            .single()
-            .0
-            .get(position.y as usize)
-            .and_then(|row| row.get(position.x as usize))
+    /// Positions whose element has changed after `revision`, top to bottom and left to right.
+    /// Rows which haven't changed are skipped so this is cheap when little has changed.
+    pub fn changed_since(&self, revision: u64) -> impl Iterator<Item = Position> + '_ {
+        let width = self.width as usize;
+
+        self.row_revisions
+            .iter()
+            .enumerate()
+            .filter(move |(_, &row_revision)| row_revision > revision)
+            .flat_map(move |(y, _)| y * width..(y + 1) * width)
+            .filter(move |&index| self.revisions[index] > revision)
+            .map(|index| self.get_position(index))
     }
+}
+
+fn get_chunk_count(length: isize) -> isize {
+    (length + CHUNK_SIZE - 1) / CHUNK_SIZE
+}
+
+/// Written as `(width, height, elements, dirty chunk indices, placed ticks)` where placed ticks are written as
+/// `(index, tick)` pairs in order of index.
+impl Serialize for ElementGrid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let dirty_chunk_indices = self
+            .dirty_chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, &is_dirty)| is_dirty)
+            .map(|(chunk_index, _)| chunk_index)
+            .collect::<Vec<_>>();
+
+        let mut placed_ticks = self
+            .placed_ticks
+            .iter()
+            .map(|(&index, &tick)| (index, tick))
+            .collect::<Vec<_>>();
+        placed_ticks.sort_unstable();
 
-    pub fn element(&self, entity: Entity) -> &Element {
-        self.get_element(entity)
-            .expect(&format!("Element not found for the entity: {:?}", entity))
+        (
+            self.width,
+            self.height,
+            &self.elements,
+            dirty_chunk_indices,
+            placed_ticks,
+        )
+            .serialize(serializer)
     }
+}
+
+impl<'de> Deserialize<'de> for ElementGrid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (width, height, elements, dirty_chunk_indices, placed_ticks) =
+            <(isize, isize, Vec<Element>, Vec<usize>, Vec<(usize, isize)>)>::deserialize(
+                deserializer,
+            )?;
+
+        if elements.len() != (width * height) as usize {
+            return Err(serde::de::Error::custom(
+                "ElementGrid must have exactly one element per tile",
+            ));
+        }
+
+        let mut element_grid = Self::new(width, height, elements);
+        element_grid.dirty_chunks.fill(false);
+
+        for chunk_index in dirty_chunk_indices {
+            match element_grid.dirty_chunks.get_mut(chunk_index) {
+                Some(is_dirty) => *is_dirty = true,
+                None => {
+                    return Err(serde::de::Error::custom(
+                        "ElementGrid dirty chunk is out of bounds",
+                    ))
+                }
+            }
+        }
+
+        for (index, tick) in placed_ticks {
+            if index >= element_grid.elements.len() {
+                return Err(serde::de::Error::custom(
+                    "ElementGrid placed tick is out of bounds",
+                ));
+            }
 
-    pub fn get_element(&self, entity: Entity) -> Option<&Element> {
-        match self.elements.get(entity) {
-            Ok(element) => Some(element),
-            Err(_) => None,
+            element_grid.placed_ticks.insert(index, tick);
         }
+
+        Ok(element_grid)
+    }
+}
+
+/// Remembers how far through a zone's element changes a system has read. Keep one in a `Local` per reading system.
+#[derive(Default, Debug)]
+pub struct ElementChangeCursor(u64);
+
+#[derive(SystemParam)]
+pub struct GridElements<'w, 's, Z: Zone> {
+    element_grid: Query<'w, 's, Ref<'static, ElementGrid>, With<Z>>,
+}
+
+impl<'w, 's, Z: Zone> GridElements<'w, 's, Z> {
+    fn grid(&self) -> &ElementGrid {
+        self.element_grid.single().into_inner()
+    }
+
+    pub fn element(&self, position: Position) -> Element {
+        self.get_element(position)
+            .unwrap_or_else(|| panic!("Element not found at the position: {:?}", position))
+    }
+
+    pub fn get_element(&self, position: Position) -> Option<Element> {
+        self.grid().get(position)
     }
 
     pub fn is(&self, position: Position, element: Element) -> bool {
-        self.get_entity(position).map_or(false, |&element_entity| {
-            self.get_element(element_entity)
-                .map_or(false, |&queried_element| queried_element == element)
-        })
+        self.get_element(position) == Some(element)
     }
 
     // Returns true if every element in `positions` matches the provided Element type.
@@ -79,15 +337,37 @@ impl<'w, 's, Z: Zone> GridElements<'w, 's, Z> {
     pub fn is_all(&self, positions: &[Position], element: Element) -> bool {
         positions.iter().all(|&position| self.is(position, element))
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, Element)> + '_ {
+        self.grid().iter()
+    }
+
+    /// Positions whose element changed since this cursor last read them. Every position is new to a cursor when
+    /// the grid itself is new, e.g. after loading, so nothing is reported as changed until the grid is next modified.
+    pub fn changed_positions(&self, cursor: &mut ElementChangeCursor) -> Vec<Position> {
+        let element_grid = self.element_grid.single();
+        let since = if element_grid.is_added() { 0 } else { cursor.0 };
+        cursor.0 = element_grid.revision();
+
+        element_grid.into_inner().changed_since(since).collect()
+    }
 }
 
 #[derive(SystemParam)]
 pub struct GridElementsMut<'w, 's, Z: Zone> {
-    elements_cache: Query<'w, 's, &'static mut ElementEntityPositionCache, With<Z>>,
+    element_grid: Query<'w, 's, &'static mut ElementGrid, With<Z>>,
 }
 
 impl<'w, 's, Z: Zone> GridElementsMut<'w, 's, Z> {
-    pub fn set(&mut self, position: Position, entity: Entity) {
-        self.elements_cache.single_mut().0[position.y as usize][position.x as usize] = entity;
+    pub fn get_element(&self, position: Position) -> Option<Element> {
+        self.element_grid.single().get(position)
+    }
+
+    pub fn is(&self, position: Position, element: Element) -> bool {
+        self.get_element(position) == Some(element)
+    }
+
+    pub fn set(&mut self, position: Position, element: Element) -> bool {
+        self.element_grid.single_mut().set(position, element)
     }
 }
@@ -5,8 +5,26 @@ pub mod pheromone;
 pub mod position;
 
 use crate::{
-    app_state::check_story_over, crater_simulation::crater::AtCrater,
-    nest_simulation::nest::AtNest, story_time::set_rate_of_time,
+    app_state::check_story_over,
+    away_report::{
+        begin_away_report, complete_away_report, record_away_report, remove_away_report_resources,
+    },
+    catch_up::{catch_up_absence, PendingCatchUp},
+    chronicle::{
+        chronicle_ant_deaths, initialize_chronicle_resources, register_chronicle,
+        remove_chronicle_resources,
+    },
+    colony_stats::{
+        initialize_colony_stats_resources, register_colony_stats, remove_colony_stats_resources,
+        sample_colony_stats,
+    },
+    crater_simulation::crater::AtCrater,
+    nest_simulation::nest::AtNest,
+    scenario::{apply_scenario_settings, register_scenario, remove_scenario_resources, Scenario},
+    story_mode::{
+        check_story_mode_goals, register_story_mode, remove_story_mode_resources, StoryMode,
+    },
+    story_time::set_rate_of_time,
 };
 
 use self::{
@@ -15,24 +33,32 @@ use self::{
         digestion::ants_digestion,
         hunger::{ants_hunger_act, ants_hunger_regurgitate, ants_hunger_tick},
         initiative::ants_initiative,
-        register_ant, AntAteFoodEvent,
+        position_map::update_ant_position_map,
+        register_ant, AntAteFoodEvent, AntDiedEvent, AntDugEvent,
     },
     element::register_element,
-    pheromone::register_pheromone,
+    pheromone::{register_pheromone, PheromoneLaidEvent},
     position::Position,
 };
 use super::{
-    app_state::{begin_story, post_setup_clear_change_detection, restart, AppState},
-    common::element::map_element_to_marker,
+    app_state::{begin_story, finish_setup, post_setup_clear_change_detection, restart, AppState},
     external_event::{
         initialize_external_event_resources, process_external_event,
         remove_external_event_resources,
     },
+    journal::{
+        initialize_journal, register_journal, remove_journal_resources, replay_journal,
+        JournalReplay,
+    },
     save::{
-        bind_save_onbeforeunload, delete_save_file, initialize_save_resources, load_save_file,
-        remove_save_resources, save, unbind_save_onbeforeunload,
+        bind_save_onbeforeunload, close_save_slot, export_save_file, import_save_files,
+        initialize_save_resources, initialize_save_slot_resources, load_save_file,
+        register_save, remove_save_resources, save, unbind_save_onbeforeunload, ExportSaveEvent,
+    },
+    settings::{
+        initialize_settings_resources, register_settings, remove_settings_resources,
+        seed_global_rng,
     },
-    settings::{initialize_settings_resources, register_settings, remove_settings_resources},
     story_time::{
         initialize_story_time_resources, register_story_time, remove_story_time_resources,
         setup_story_time, update_story_elapsed_ticks, update_story_real_world_time,
@@ -50,6 +76,8 @@ pub fn register_common(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<Entity>();
     app_type_registry.write().register::<Option<Entity>>();
     app_type_registry.write().register::<Position>();
+    // Color is normally registered by RenderPlugin, but it's persisted by the simulation and headless runs have no renderer.
+    app_type_registry.write().register::<Color>();
 }
 
 pub fn despawn_model<Model: Component, Z: Zone>(
@@ -88,6 +116,12 @@ pub struct CommonSimulationPlugin;
 impl Plugin for CommonSimulationPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<AntAteFoodEvent>();
+        app.add_event::<AntDiedEvent>();
+        app.add_event::<AntDugEvent>();
+        app.add_event::<PheromoneLaidEvent>();
+        app.add_event::<ExportSaveEvent>();
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_event::<crate::save::ImportSaveEvent>();
 
         app.add_systems(
             Startup,
@@ -98,6 +132,13 @@ impl Plugin for CommonSimulationPlugin {
                 register_element,
                 register_pheromone,
                 register_ant,
+                register_save,
+                register_journal,
+                register_chronicle,
+                register_colony_stats,
+                register_scenario,
+                register_story_mode,
+                initialize_save_slot_resources,
             ),
         );
 
@@ -112,7 +153,19 @@ impl Plugin for CommonSimulationPlugin {
                 .chain(),
         );
 
-        app.add_systems(OnExit(AppState::MainMenu), initialize_settings_resources);
+        app.add_systems(
+            OnEnter(AppState::CreateNewStory),
+            (
+                (
+                    initialize_settings_resources,
+                    apply_deferred,
+                    apply_scenario_settings.run_if(resource_exists::<Scenario>()),
+                    (seed_global_rng, initialize_journal),
+                )
+                    .chain(),
+                finish_setup,
+            ),
+        );
 
         app.add_systems(
             OnEnter(AppState::FinishSetup),
@@ -125,15 +178,26 @@ impl Plugin for CommonSimulationPlugin {
                 )
                     .chain(),
                 initialize_external_event_resources,
+                initialize_chronicle_resources,
+                initialize_colony_stats_resources,
                 bind_save_onbeforeunload,
-                // TODO: This needs to run once before Simulation runs because UI update runs before first simulation tick.
-                // If this doesn't run, UI filter queries like Without<Air> won't properly exclude.
-                map_element_to_marker,
                 post_setup_clear_change_detection,
             )
                 .in_set(FinishSetupSet::SimulationFinishSetup),
         );
 
+        // Summarize a long absence once the story has been loaded and its zones have been set up.
+        app.add_systems(
+            OnEnter(AppState::FinishSetup),
+            (
+                // Call `apply_deferred` to ensure PendingCatchUp (via `setup_story_time`) is available for use.
+                apply_deferred,
+                catch_up_absence.run_if(resource_exists::<PendingCatchUp>()),
+            )
+                .chain()
+                .in_set(FinishSetupSet::AfterSimulationFinishSetup),
+        );
+
         app.add_systems(
             OnEnter(AppState::PostSetupClearChangeDetection),
             begin_story,
@@ -142,11 +206,11 @@ impl Plugin for CommonSimulationPlugin {
         app.add_systems(
             FixedUpdate,
             (
+                replay_journal.run_if(resource_exists::<JournalReplay>()),
                 process_external_event::<AtNest>,
                 process_external_event::<AtCrater>,
                 apply_deferred,
-                map_element_to_marker,
-                apply_deferred,
+                catch_up_absence.run_if(resource_exists::<PendingCatchUp>()),
             )
                 .chain()
                 .in_set(SimulationTickSet::First)
@@ -165,6 +229,8 @@ impl Plugin for CommonSimulationPlugin {
                     ants_hunger_act::<AtNest>,
                     ants_hunger_act::<AtCrater>,
                     apply_deferred,
+                    update_ant_position_map::<AtNest>,
+                    update_ant_position_map::<AtCrater>,
                     ants_hunger_regurgitate::<AtNest>,
                     ants_hunger_regurgitate::<AtCrater>,
                     apply_deferred,
@@ -190,6 +256,7 @@ impl Plugin for CommonSimulationPlugin {
                 ants_initiative::<AtNest>,
                 ants_initiative::<AtCrater>,
                 update_story_elapsed_ticks,
+                sample_colony_stats,
             )
                 .chain()
                 .in_set(SimulationTickSet::PostSimulationTick)
@@ -206,10 +273,13 @@ impl Plugin for CommonSimulationPlugin {
             (
                 // If this doesn't run then when user spawns elements they won't gain exposure if simulation is paused.
                 apply_deferred,
-                // TODO: Need to run this after simulation (as well as before) to ensure UI layer reads fresh data.
-                map_element_to_marker,
-                apply_deferred,
+                // Catch up with ants which moved this tick, or were spawned while paused, so user input finds them.
+                update_ant_position_map::<AtNest>,
+                update_ant_position_map::<AtCrater>,
                 check_story_over,
+                record_away_report,
+                chronicle_ant_deaths,
+                check_story_mode_goals.run_if(resource_exists::<StoryMode>()),
                 // rate_of_time needs to run when app is paused because fixed_time accumulations need to be cleared while app is paused
                 // to prevent running FixedUpdate schedule repeatedly (while no-oping) when coming back to a hidden tab with a paused sim.
                 set_rate_of_time,
@@ -219,6 +289,17 @@ impl Plugin for CommonSimulationPlugin {
                 .run_if(in_state(AppState::TellStory)),
         );
 
+        // Tally what happens while fast-forwarding so the player can be told what they missed.
+        app.add_systems(
+            OnEnter(StoryPlaybackState::FastForwarding),
+            begin_away_report,
+        );
+
+        app.add_systems(
+            OnExit(StoryPlaybackState::FastForwarding),
+            complete_away_report,
+        );
+
         app.add_systems(
             Update,
             update_time_scale.run_if(in_state(AppState::TellStory)),
@@ -240,16 +321,40 @@ impl Plugin for CommonSimulationPlugin {
             ),
         );
 
+        app.add_systems(
+            Update,
+            (
+                export_save_file.run_if(on_event::<ExportSaveEvent>()),
+                import_save_files,
+            )
+                .run_if(in_state(AppState::TellStory)),
+        );
+
+        // Native builds have no beforeunload event so listen for the app closing instead.
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(Last, crate::save::save_on_app_exit);
+
+        // Save before anything is despawned or removed so that the story can be continued from the main menu.
+        app.add_systems(
+            OnEnter(AppState::Cleanup),
+            close_save_slot.in_set(CleanupSet::BeforeSimulationCleanup),
+        );
+
         app.add_systems(
             OnEnter(AppState::Cleanup),
             (
                 unbind_save_onbeforeunload,
-                delete_save_file,
                 remove_story_time_resources,
                 remove_settings_resources,
                 remove_save_resources,
                 remove_external_event_resources,
+                remove_journal_resources,
                 remove_loading_resources,
+                remove_away_report_resources,
+                remove_chronicle_resources,
+                remove_colony_stats_resources,
+                remove_scenario_resources,
+                remove_story_mode_resources,
                 restart,
             )
                 .in_set(CleanupSet::SimulationCleanup),
@@ -1,7 +1,9 @@
 use std::marker::PhantomData;
 
 use crate::common::{
-    pheromone::{Pheromone, PheromoneDuration, PheromoneMap, PheromoneStrength},
+    pheromone::{
+        Pheromone, PheromoneDuration, PheromoneLaidEvent, PheromoneMap, PheromoneStrength,
+    },
     position::Position,
     Zone,
 };
@@ -98,6 +100,8 @@ impl<Z: Zone> Command for SpawnPheromoneCommand<Z> {
             .entry(self.position)
             .and_modify(|entities| entities.push(pheromone_entity))
             .or_insert_with(|| vec![pheromone_entity]);
+
+        world.send_event(PheromoneLaidEvent(pheromone_entity));
     }
 }
 
@@ -65,6 +65,11 @@ impl PheromoneDuration {
         self.max
     }
 
+    /// Get 100% expired once every hour.
+    pub fn rate_of_expiration(&self) -> f32 {
+        self.max / (SECONDS_PER_HOUR * DEFAULT_TICKS_PER_SECOND) as f32
+    }
+
     pub fn tick(&mut self, rate_of_pheromone_expiration: f32) {
         self.value = (self.value + rate_of_pheromone_expiration).min(self.max);
     }
@@ -95,6 +100,10 @@ impl<Z: Zone> PheromoneMap<Z> {
     }
 }
 
+/// Sent when a pheromone is laid where there wasn't one of its kind already.
+#[derive(Event, PartialEq, Copy, Clone, Debug)]
+pub struct PheromoneLaidEvent(pub Entity);
+
 /// Note the intentional omission of PheromoneMap. It would be wasteful to persist
 /// because it's able to be trivially regenerated at runtime.
 pub fn register_pheromone(app_type_registry: ResMut<AppTypeRegistry>) {
@@ -130,9 +139,7 @@ pub fn pheromone_duration_tick<Z: Zone>(
     mut commands: Commands,
 ) {
     for (mut pheromone_duration, position, pheromone_entity) in pheromone_query.iter_mut() {
-        // Get 100% expired once every hour
-        let rate_of_pheromone_expiration =
-            pheromone_duration.max() / (SECONDS_PER_HOUR * DEFAULT_TICKS_PER_SECOND) as f32;
+        let rate_of_pheromone_expiration = pheromone_duration.rate_of_expiration();
 
         pheromone_duration.tick(rate_of_pheromone_expiration);
 
@@ -49,26 +49,17 @@ pub fn ants_dig(
         .collect::<Vec<_>>();
 
         let food_positions = positions
-            .iter()
-            .filter_map(|&position| {
-                let element_entity = grid_elements.entity(position);
-                let element = grid_elements.element(*element_entity);
-
-                if *element == Element::Food {
-                    return Some((position, *element_entity));
-                }
-
-                None
-            })
+            .into_iter()
+            .filter(|&position| grid_elements.is(position, Element::Food))
             .collect::<Vec<_>>();
 
         if food_positions.is_empty() {
             return;
         }
 
-        let (dig_position, dig_element_entity) = rng.sample(&food_positions).unwrap();
+        let dig_position = rng.sample(&food_positions).unwrap();
 
-        commands.dig(ant_entity, *dig_position, *dig_element_entity, AtCrater);
+        commands.dig(ant_entity, *dig_position, Element::Food, AtCrater);
         // TODO: This isn't right. I should express this as a separate system because `commands.dig` could fail
         *orientation = orientation.turn_around();
     }
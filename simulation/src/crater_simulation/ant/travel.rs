@@ -1,8 +1,10 @@
 use bevy::prelude::*;
 
 use crate::{
+    chronicle::{ChronicleEvent, ChronicleWriter},
     common::{
-        ant::{AntInventory, AntOrientation, Facing, Initiative},
+        ant::{AntInventory, AntName, AntOrientation, Facing, Initiative},
+        element::Element,
         position::Position,
     },
     crater_simulation::{ant::emit_pheromone::LeavingNest, crater::AtCrater},
@@ -24,16 +26,20 @@ pub fn ants_travel_to_nest(
             &mut AntOrientation,
             &Position,
             &AntInventory,
+            &AntName,
         ),
         With<AtCrater>,
     >,
     nest_query: Query<&Nest>,
+    elements_query: Query<&Element>,
     mut commands: Commands,
     settings: Res<Settings>,
+    mut chronicle_writer: ChronicleWriter,
 ) {
     let nest = nest_query.single();
 
-    for (ant_entity, mut initiative, mut orientation, position, inventory) in ants_query.iter_mut()
+    for (ant_entity, mut initiative, mut orientation, position, inventory, name) in
+        ants_query.iter_mut()
     {
         if !initiative.can_move() {
             continue;
@@ -75,6 +81,16 @@ pub fn ants_travel_to_nest(
             ant_entity_commands.insert(Position::new(0, nest.surface_level()));
         }
 
+        let is_carrying_food = inventory
+            .0
+            .is_some_and(|item_entity| elements_query.get(item_entity) == Ok(&Element::Food));
+
+        if is_carrying_food && !chronicle_writer.has_brought_food_from_crater() {
+            chronicle_writer.record(ChronicleEvent::FirstFoodFromCrater {
+                name: name.0.clone(),
+            });
+        }
+
         initiative.consume();
     }
 }
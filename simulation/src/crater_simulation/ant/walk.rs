@@ -43,11 +43,7 @@ pub fn ants_walk(
         let below_position = orientation.get_below_position(&position);
         let above_position = orientation.get_above_position(&position);
 
-        let has_air_ahead = grid_elements
-            .get_entity(ahead_position)
-            .map_or(false, |entity| {
-                *grid_elements.element(*entity) == Element::Air
-            });
+        let has_air_ahead = grid_elements.is(ahead_position, Element::Air);
 
         // If ant is carrying food, it should follow the pheromone that leads home.
         // Otherwise, it should follow the pheromone that leads to food.
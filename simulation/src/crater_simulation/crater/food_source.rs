@@ -0,0 +1,226 @@
+use super::AtCrater;
+use crate::{
+    common::{
+        ant::position_map::AntPositionMap, element::Element, grid::GridElementsMut,
+        position::Position,
+    },
+    settings::Settings,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND},
+};
+use bevy::prelude::*;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+pub enum FoodSourceKind {
+    /// Grows food right up against itself.
+    #[default]
+    Bush,
+    /// Scatters food further afield.
+    SeedDrop,
+}
+
+impl FoodSourceKind {
+    /// How far from the food source, in tiles, its food may grow.
+    pub fn spread(&self) -> isize {
+        match self {
+            FoodSourceKind::Bush => 1,
+            FoodSourceKind::SeedDrop => 3,
+        }
+    }
+}
+
+/// Grows food on empty tiles around itself, during the day, until it has grown all of its food. Then it lies dormant
+/// until it has restocked, so the crater never runs out of food for good.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component)]
+pub struct FoodSource {
+    kind: FoodSourceKind,
+    food_remaining: isize,
+    /// Ticks spent growing, or restocking, the next piece of food.
+    growth: isize,
+    is_dormant: bool,
+}
+
+impl FoodSource {
+    pub fn new(kind: FoodSourceKind, food_remaining: isize) -> Self {
+        Self {
+            kind,
+            food_remaining,
+            growth: 0,
+            is_dormant: false,
+        }
+    }
+
+    pub fn kind(&self) -> FoodSourceKind {
+        self.kind
+    }
+
+    pub fn is_dormant(&self) -> bool {
+        self.is_dormant
+    }
+}
+
+pub fn register_food_source(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<FoodSource>();
+    app_type_registry.write().register::<FoodSourceKind>();
+}
+
+/// Food sources grow a piece of food once every `food_source_growth_time`, but only during the day. Food grows on
+/// a random tile nearby. When that tile isn't empty the food source tries again next tick, so food sources whose
+/// surroundings are full of food stop growing until ants forage some of it.
+///
+/// Depleted food sources go dormant and restock, just as slowly, until they hold `food_source_capacity` food again.
+pub fn food_sources_grow(
+    mut food_source_query: Query<(&mut FoodSource, &Position), With<AtCrater>>,
+    mut grid_elements: GridElementsMut<AtCrater>,
+    ant_position_map: Res<AntPositionMap<AtCrater>>,
+    story_time: Res<StoryTime>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+) {
+    if story_time.is_nighttime() {
+        return;
+    }
+
+    let growth_ticks = settings.food_source_growth_time * DEFAULT_TICKS_PER_SECOND;
+
+    for (mut food_source, position) in food_source_query.iter_mut() {
+        if food_source.growth < growth_ticks {
+            food_source.growth += 1;
+            continue;
+        }
+
+        if food_source.is_dormant {
+            food_source.growth = 0;
+            food_source.food_remaining += 1;
+            food_source.is_dormant =
+                food_source.food_remaining < settings.crater_terrain.food_source_capacity;
+            continue;
+        }
+
+        let spread = food_source.kind.spread();
+        let food_position =
+            *position + Position::new(rng.isize(-spread..=spread), rng.isize(-spread..=spread));
+
+        if food_position == *position
+            || grid_elements.get_element(food_position) != Some(Element::Air)
+            || !ant_position_map.get(&food_position).is_empty()
+        {
+            continue;
+        }
+
+        grid_elements.set(food_position, Element::Food);
+        food_source.growth = 0;
+        food_source.food_remaining -= 1;
+
+        if food_source.food_remaining <= 0 {
+            food_source.is_dormant = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::grid::ElementGrid,
+        story_time::SECONDS_PER_HOUR,
+        test_support::{run_ticks, TEST_SEED},
+    };
+    use bevy::prelude::default;
+
+    const GROWTH_TICKS: usize = DEFAULT_TICKS_PER_SECOND as usize;
+
+    fn setup(food_remaining: isize) -> App {
+        let mut app = App::new();
+        app.insert_resource(Settings {
+            food_source_growth_time: 1,
+            ..default()
+        });
+        app.insert_resource(GlobalRng::with_seed(TEST_SEED));
+        app.insert_resource(StoryTime::default());
+        app.insert_resource(AntPositionMap::<AtCrater>::default());
+        app.world
+            .spawn((ElementGrid::from_fn(5, 5, |_| Element::Air), AtCrater));
+        app.world.spawn((
+            FoodSource::new(FoodSourceKind::Bush, food_remaining),
+            Position::new(2, 2),
+            AtCrater,
+        ));
+        app.add_systems(Update, food_sources_grow);
+
+        app
+    }
+
+    fn count_food(app: &mut App) -> usize {
+        app.world
+            .query::<&ElementGrid>()
+            .single(&app.world)
+            .iter()
+            .filter(|&(_, element)| element == Element::Food)
+            .count()
+    }
+
+    fn count_food_sources(app: &mut App) -> usize {
+        app.world.query::<&FoodSource>().iter(&app.world).count()
+    }
+
+    fn is_dormant(app: &mut App) -> bool {
+        app.world
+            .query::<&FoodSource>()
+            .single(&app.world)
+            .is_dormant()
+    }
+
+    #[test]
+    fn food_grows_around_food_source_until_depleted() {
+        let mut app = setup(2);
+
+        run_ticks(&mut app, GROWTH_TICKS);
+        assert_eq!(count_food(&mut app), 0);
+
+        // Growth may take a few extra ticks while the food source finds an empty tile.
+        run_ticks(&mut app, GROWTH_TICKS * 3);
+        assert_eq!(count_food(&mut app), 2);
+        assert!(is_dormant(&mut app));
+
+        let grid = app.world.query::<&ElementGrid>().single(&app.world);
+        assert!(grid.iter().all(|(position, element)| {
+            element == Element::Air
+                || (position != Position::new(2, 2)
+                    && (position.x - 2).abs() <= 1
+                    && (position.y - 2).abs() <= 1)
+        }));
+    }
+
+    #[test]
+    fn depleted_food_source_restocks_and_grows_again() {
+        let mut app = setup(1);
+        app.world
+            .resource_mut::<Settings>()
+            .crater_terrain
+            .food_source_capacity = 1;
+
+        run_ticks(&mut app, GROWTH_TICKS * 2);
+        assert_eq!(count_food(&mut app), 1);
+        assert!(is_dormant(&mut app));
+
+        // Restocking takes as long as growing so the food source grows its next piece of food a while later.
+        run_ticks(&mut app, GROWTH_TICKS * 3);
+        assert_eq!(count_food(&mut app), 2);
+        assert_eq!(count_food_sources(&mut app), 1);
+    }
+
+    #[test]
+    fn food_doesnt_grow_at_night() {
+        let mut app = setup(2);
+        // The story begins at 8AM, so skipping 16 hours brings it to midnight.
+        app.world
+            .resource_mut::<StoryTime>()
+            .skip_ticks(16 * SECONDS_PER_HOUR * DEFAULT_TICKS_PER_SECOND);
+
+        run_ticks(&mut app, GROWTH_TICKS * 3);
+        assert_eq!(count_food(&mut app), 0);
+    }
+}
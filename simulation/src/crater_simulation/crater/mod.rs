@@ -1,14 +1,18 @@
+pub mod food_source;
+pub mod terrain;
+
+use self::terrain::{generate_crater_elements, generate_food_sources};
 use crate::{
     common::{
         ant::{
             digestion::Digestion, hunger::Hunger, Angle, AntBundle, AntColor, AntInventory,
             AntName, AntOrientation, AntRole, Facing, Initiative,
         },
-        element::{Element, ElementBundle},
-        grid::{ElementEntityPositionCache, Grid},
+        grid::{ElementGrid, Grid},
         position::Position,
         Zone,
     },
+    scenario::{get_default_crater_element, Scenario},
     settings::Settings,
 };
 use bevy::prelude::*;
@@ -38,51 +42,74 @@ pub fn spawn_crater(mut commands: Commands) {
 
 pub fn insert_crater_grid(
     crater_query: Query<Entity, With<Crater>>,
-    element_query: Query<(&mut Position, Entity), (With<Element>, With<AtCrater>)>,
     settings: Res<Settings>,
     mut commands: Commands,
 ) {
-    let mut elements_cache = vec![
-        vec![Entity::PLACEHOLDER; settings.crater_width as usize];
-        settings.crater_height as usize
-    ];
-
-    for (position, entity) in element_query.iter() {
-        elements_cache[position.y as usize][position.x as usize] = entity;
-    }
-
     commands.entity(crater_query.single()).insert(Grid::new(
         settings.crater_width,
         settings.crater_height,
     ));
+}
+
+/// Creates a new grid of Elements. The grid is densley populated with generated terrain, unless a scenario lays out
+/// the crater, and its pheromones, instead.
+pub fn spawn_crater_elements(
+    settings: Res<Settings>,
+    scenario: Option<Res<Scenario>>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    if let Some(scenario) = scenario {
+        let element_grid = scenario.crater.to_element_grid(get_default_crater_element);
+
+        commands.spawn((element_grid, AtCrater));
+        scenario
+            .crater
+            .spawn_pheromones(AtCrater, &settings, &mut commands);
 
-    commands.spawn((ElementEntityPositionCache(elements_cache), AtCrater));
+        return;
+    }
+
+    let element_grid = generate_crater_elements(&settings, &mut rng);
+
+    commands.spawn((element_grid, AtCrater));
 }
 
-/// Creates a new grid of Elements. The grid is densley populated.
-/// Note the intentional omission of calling `commands.spawn_element`. This is because
-/// `spawn_element` writes to the grid cache, which is not yet initialized. The grid cache will
-/// be updated after this function is called. This keeps cache initialization parity between
-/// creating a new world and loading an existing world.
-pub fn spawn_crater_elements(settings: Res<Settings>, mut commands: Commands) {
-    for y in 0..settings.crater_height {
-        for x in 0..settings.crater_width {
-            commands.spawn(ElementBundle::new(
-                Element::Air,
-                Position::new(x, y),
-                AtCrater,
-            ));
-        }
+/// Food sources are generated from the crater's terrain, so they're spawned after it. Scenarios don't have any.
+pub fn spawn_crater_food_sources(
+    element_grid_query: Query<&ElementGrid, With<AtCrater>>,
+    settings: Res<Settings>,
+    scenario: Option<Res<Scenario>>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    if scenario.is_some() {
+        return;
+    }
+
+    for (position, food_source) in
+        generate_food_sources(element_grid_query.single(), &settings, &mut rng)
+    {
+        commands.spawn((food_source, position, AtCrater));
     }
 }
 
 pub fn spawn_crater_ants(
     settings: Res<Settings>,
+    scenario: Option<Res<Scenario>>,
     mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
 ) {
     let mut rng = rng.reborrow();
 
+    if let Some(scenario) = scenario {
+        scenario
+            .crater
+            .spawn_ants(AtCrater, &settings, &mut rng, &mut commands);
+
+        return;
+    }
+
     // Just spawn one worker ant for now for prototyping.
     let worker_ant_bundle = AntBundle::new(
         // Spawn adjacent to the nest entrance
@@ -0,0 +1,211 @@
+use super::food_source::{FoodSource, FoodSourceKind};
+use crate::{
+    common::{
+        element::{deposit::Deposit, Element},
+        grid::ElementGrid,
+        position::Position,
+    },
+    settings::Settings,
+};
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+/// How far around the nest entrance is kept clear, in tiles, so ants are never walled in as they come and go.
+const NEST_ENTRANCE_CLEARING: isize = 4;
+
+/// How many random tiles are considered for each food source before giving up on a crater with little room.
+const FOOD_SOURCE_ATTEMPTS: usize = 100;
+
+/// Air, ringed by a rim of rock, with patches of food and obstacles of rock or dirt scattered across it. Beyond the
+/// rim is dirt. The nest entrance, at the center of the crater, is always kept clear.
+pub fn generate_crater_elements(settings: &Settings, rng: &mut GlobalRng) -> ElementGrid {
+    let terrain = settings.crater_terrain;
+    let center = Position::new(settings.crater_width / 2, settings.crater_height / 2);
+    let rim_radius = settings.crater_width.min(settings.crater_height) / 2 - 1;
+    let rim_inner_radius = (rim_radius - terrain.rim_width).max(0);
+
+    let food_patches = (0..terrain.food_patch_count)
+        .map(|_| generate_deposit(Element::Food, terrain.food_patch_size, settings, rng))
+        .collect::<Vec<_>>();
+    let obstacles = (0..terrain.obstacle_count)
+        .map(|_| {
+            let element = if rng.bool() {
+                Element::Rock
+            } else {
+                Element::Dirt
+            };
+
+            generate_deposit(element, terrain.obstacle_size, settings, rng)
+        })
+        .collect::<Vec<_>>();
+
+    ElementGrid::from_fn(settings.crater_width, settings.crater_height, |position| {
+        let delta = position - center;
+        let distance_squared = delta.x * delta.x + delta.y * delta.y;
+
+        if distance_squared <= NEST_ENTRANCE_CLEARING * NEST_ENTRANCE_CLEARING {
+            return Element::Air;
+        }
+
+        if terrain.rim_width > 0 {
+            if distance_squared > rim_radius * rim_radius {
+                return Element::Dirt;
+            }
+
+            if distance_squared > rim_inner_radius * rim_inner_radius {
+                return Element::Rock;
+            }
+        }
+
+        food_patches
+            .iter()
+            .chain(obstacles.iter())
+            .find(|deposit| deposit.contains(position))
+            .map_or(Element::Air, |deposit| deposit.element)
+    })
+}
+
+/// Bushes and seed drops, each on an empty tile of the crater away from the nest entrance, and each as likely as the
+/// other.
+pub fn generate_food_sources(
+    element_grid: &ElementGrid,
+    settings: &Settings,
+    rng: &mut GlobalRng,
+) -> Vec<(Position, FoodSource)> {
+    let terrain = settings.crater_terrain;
+    let center = Position::new(settings.crater_width / 2, settings.crater_height / 2);
+
+    (0..terrain.food_source_count)
+        .filter_map(|_| {
+            let kind = if rng.bool() {
+                FoodSourceKind::Bush
+            } else {
+                FoodSourceKind::SeedDrop
+            };
+
+            (0..FOOD_SOURCE_ATTEMPTS)
+                .map(|_| {
+                    Position::new(
+                        rng.isize(0..settings.crater_width),
+                        rng.isize(0..settings.crater_height),
+                    )
+                })
+                .find(|&position| {
+                    let delta = position - center;
+
+                    delta.x * delta.x + delta.y * delta.y
+                        > NEST_ENTRANCE_CLEARING * NEST_ENTRANCE_CLEARING
+                        && element_grid.is(position, Element::Air)
+                })
+                .map(|position| {
+                    (
+                        position,
+                        FoodSource::new(kind, terrain.food_source_capacity),
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Anywhere in the crater, anywhere from a single tile up to `max_radius` in size.
+fn generate_deposit(
+    element: Element,
+    max_radius: isize,
+    settings: &Settings,
+    rng: &mut GlobalRng,
+) -> Deposit {
+    Deposit {
+        element,
+        center: Position::new(
+            rng.isize(0..settings.crater_width),
+            rng.isize(0..settings.crater_height),
+        ),
+        radius: rng.isize(0..=max_radius),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::CraterTerrain;
+    use bevy::prelude::default;
+
+    fn generate(settings: &Settings) -> ElementGrid {
+        generate_crater_elements(settings, &mut GlobalRng::with_seed(settings.seed))
+    }
+
+    #[test]
+    fn same_seed_generates_same_crater() {
+        let settings = Settings {
+            seed: 7,
+            ..default()
+        };
+        let element_grid = generate(&settings);
+
+        assert_eq!(
+            generate(&settings).iter().collect::<Vec<_>>(),
+            element_grid.iter().collect::<Vec<_>>()
+        );
+        assert_ne!(
+            generate(&Settings {
+                seed: 8,
+                ..settings
+            })
+            .iter()
+            .collect::<Vec<_>>(),
+            element_grid.iter().collect::<Vec<_>>()
+        );
+        assert!(element_grid
+            .iter()
+            .any(|(_, element)| element == Element::Food));
+    }
+
+    #[test]
+    fn food_sources_grow_on_empty_tiles() {
+        let settings = Settings::default();
+        let mut rng = GlobalRng::with_seed(settings.seed);
+        let element_grid = generate_crater_elements(&settings, &mut rng);
+        let food_sources = generate_food_sources(&element_grid, &settings, &mut rng);
+
+        assert_eq!(
+            food_sources.len(),
+            settings.crater_terrain.food_source_count as usize
+        );
+        assert!(food_sources
+            .iter()
+            .all(|&(position, _)| element_grid.is(position, Element::Air)));
+    }
+
+    #[test]
+    fn nest_entrance_is_clear_and_crater_is_rimmed() {
+        let settings = Settings {
+            crater_terrain: CraterTerrain {
+                // Enough obstacles to cover the crater, were the entrance not kept clear.
+                obstacle_count: 500,
+                obstacle_size: 8,
+                ..CraterTerrain::empty()
+            },
+            ..default()
+        };
+        let element_grid = generate(&settings);
+        let center = Position::new(settings.crater_width / 2, settings.crater_height / 2);
+
+        for offset in [Position::ZERO, Position::new(1, 1), Position::new(-4, 0)] {
+            assert_eq!(element_grid.get(center + offset), Some(Element::Air));
+        }
+
+        let element_grid = generate(&Settings {
+            crater_terrain: CraterTerrain {
+                rim_width: 2,
+                ..CraterTerrain::empty()
+            },
+            ..settings
+        });
+        let rim_radius = settings.crater_width / 2 - 1;
+        let at = |x: isize| element_grid.get(Position::new(x, center.y));
+
+        assert_eq!(at(center.x + rim_radius - 2), Some(Element::Air));
+        assert_eq!(at(center.x + rim_radius - 1), Some(Element::Rock));
+        assert_eq!(at(center.x + rim_radius), Some(Element::Rock));
+        assert_eq!(at(0), Some(Element::Dirt));
+    }
+}
@@ -3,10 +3,16 @@ pub mod crater;
 
 use crate::{
     common::{
-        ant::Ant, element::Element, grid::ElementEntityPositionCache, pheromone::{
+        ant::{
+            position_map::{initialize_ant_position_resources, remove_ant_position_resources},
+            Ant,
+        },
+        element::Element,
+        grid::ElementGrid,
+        pheromone::{
             initialize_pheromone_resources, pheromone_duration_tick, remove_pheromone_resources,
             Pheromone,
-        }
+        },
     },
     story_time::StoryPlaybackState,
     SimulationTickSet,
@@ -19,12 +25,14 @@ use self::{
         walk::ants_walk,
     },
     crater::{
-        register_crater, spawn_crater, spawn_crater_ants, spawn_crater_elements, AtCrater, Crater,
+        food_source::{food_sources_grow, register_food_source, FoodSource},
+        register_crater, spawn_crater, spawn_crater_ants, spawn_crater_elements,
+        spawn_crater_food_sources, AtCrater, Crater,
     },
 };
 use super::{
-    apply_deferred, despawn_model, insert_crater_grid, settings::initialize_settings_resources,
-    AppState, CleanupSet, FinishSetupSet,
+    apply_deferred, despawn_model, insert_crater_grid, nest_simulation::nest::spawn_nest_ants,
+    settings::seed_global_rng, AppState, CleanupSet, FinishSetupSet,
 };
 use bevy::prelude::*;
 
@@ -32,19 +40,25 @@ pub struct CraterSimulationPlugin;
 
 impl Plugin for CraterSimulationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, (register_crater, register_ant));
+        app.add_systems(Startup, (register_crater, register_food_source, register_ant));
 
         app.add_systems(
-            OnExit(AppState::MainMenu),
+            OnEnter(AppState::CreateNewStory),
             (
                 // Call `apply_deferred` to ensure Settings (via `initialize_settings_resources`) is available for use.
                 apply_deferred,
                 spawn_crater,
                 apply_deferred,
-                (spawn_crater_elements, spawn_crater_ants),
+                spawn_crater_elements,
+                apply_deferred,
+                spawn_crater_food_sources,
+                spawn_crater_ants,
             )
                 .chain()
-                .after(initialize_settings_resources),
+                // Spawn after seeding GlobalRng, and after the nest, so that a story's initial world is determined by
+                // its seed.
+                .after(seed_global_rng)
+                .after(spawn_nest_ants),
         );
 
         app.add_systems(
@@ -52,7 +66,10 @@ impl Plugin for CraterSimulationPlugin {
             (
                 insert_crater_grid,
                 apply_deferred,
-                initialize_pheromone_resources::<AtCrater>,
+                (
+                    initialize_pheromone_resources::<AtCrater>,
+                    initialize_ant_position_resources::<AtCrater>,
+                ),
             )
                 .chain()
                 .in_set(FinishSetupSet::SimulationFinishSetup),
@@ -69,6 +86,7 @@ impl Plugin for CraterSimulationPlugin {
                 ants_travel_to_nest,
                 ants_walk,
                 ants_dig,
+                food_sources_grow,
             )
                 .run_if(
                     in_state(AppState::TellStory)
@@ -82,11 +100,14 @@ impl Plugin for CraterSimulationPlugin {
             OnEnter(AppState::Cleanup),
             (
                 despawn_model::<Ant, AtCrater>,
+                // Elements which aren't in the grid are being carried by ants.
                 despawn_model::<Element, AtCrater>,
-                despawn_model::<ElementEntityPositionCache, AtCrater>,
+                despawn_model::<ElementGrid, AtCrater>,
                 despawn_model::<Pheromone, AtCrater>,
+                despawn_model::<FoodSource, AtCrater>,
                 despawn_model::<Crater, AtCrater>,
                 remove_pheromone_resources::<AtCrater>,
+                remove_ant_position_resources::<AtCrater>,
             )
                 .in_set(CleanupSet::SimulationCleanup),
         );
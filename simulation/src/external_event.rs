@@ -1,22 +1,26 @@
 use crate::{
+    chronicle::{ChronicleEvent, ChronicleWriter},
     common::{
         ant::{
-            commands::AntCommandsExt, Angle, AntColor, AntInventory, AntName, AntOrientation,
-            AntRole, Dead, Facing, Initiative,
+            commands::AntCommandsExt, death::DeathCause, position_map::AntPositionMap, Angle,
+            AntColor, AntDiedEvent, AntInventory, AntName, AntOrientation, AntRole, Dead, Facing,
+            Initiative,
         },
-        element::{commands::ElementCommandsExt, Element},
-        grid::GridElements,
+        element::Element,
+        grid::GridElementsMut,
         position::Position,
         Zone,
     },
     crater_simulation::crater::AtCrater,
+    journal::{Journal, JournaledEvent},
     nest_simulation::nest::AtNest,
     settings::Settings,
+    story_time::StoryTime,
 };
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 use bevy_turborand::GlobalRng;
 
-#[derive(Event, PartialEq, Copy, Clone, Debug)]
+#[derive(Event, Reflect, PartialEq, Copy, Clone, Debug)]
 pub enum ExternalSimulationEvent<Z: Zone> {
     DespawnElement(Position, Z),
     SpawnFood(Position, Z),
@@ -25,6 +29,8 @@ pub enum ExternalSimulationEvent<Z: Zone> {
     KillAnt(Position, Z),
     SpawnWorkerAnt(Position, Z),
     DespawnWorkerAnt(Position, Z),
+    SpawnRock(Position, Z),
+    SpawnWater(Position, Z),
 }
 
 pub fn initialize_external_event_resources(mut commands: Commands) {
@@ -38,79 +44,139 @@ pub fn remove_external_event_resources(mut commands: Commands) {
     commands.remove_resource::<Events<ExternalSimulationEvent<AtCrater>>>();
 }
 
-/// Process user input events at the start of the FixedUpdate simulation loop.
-/// Need to process them manually because they'd be cleared at the end of the next Update
-/// which might occur before the next time FixedUpdate runs.
-pub fn process_external_event<Z: Zone + Copy>(
-    mut external_simulation_events: ResMut<Events<ExternalSimulationEvent<Z>>>,
-    mut commands: Commands,
-    settings: Res<Settings>,
-    mut rng: ResMut<GlobalRng>,
-    ants_query: Query<(Entity, &Position, &AntRole, &AntInventory), With<Z>>,
-    grid_elements: GridElements<Z>,
-) {
-    for event in external_simulation_events.drain() {
+/// Everything needed to apply an external event to zone `Z`.
+#[derive(SystemParam)]
+pub struct SandboxEditor<'w, 's, Z: Zone> {
+    commands: Commands<'w, 's>,
+    settings: Res<'w, Settings>,
+    rng: ResMut<'w, GlobalRng>,
+    ants_query: Query<'w, 's, (&'static AntRole, &'static AntInventory, Has<Dead>), With<Z>>,
+    ant_position_map: Res<'w, AntPositionMap<Z>>,
+    grid_elements: GridElementsMut<'w, 's, Z>,
+    ant_died_event_writer: EventWriter<'w, AntDiedEvent>,
+}
+
+impl<'w, 's, Z: Zone + Copy> SandboxEditor<'w, 's, Z> {
+    /// Returns false if the event changed nothing, e.g. spawning food where there's already dirt.
+    fn apply(&mut self, event: ExternalSimulationEvent<Z>) -> bool {
         match event {
-            ExternalSimulationEvent::SpawnFood(grid_position, zone) => {
-                if grid_elements.is(grid_position, Element::Air) {
-                    let entity = grid_elements.entity(grid_position);
-                    commands.replace_element(grid_position, Element::Food, *entity, zone);
-                }
+            ExternalSimulationEvent::SpawnFood(grid_position, _zone) => {
+                self.spawn_element(grid_position, Element::Food)
             }
-            ExternalSimulationEvent::SpawnSand(grid_position, zone) => {
-                if grid_elements.is(grid_position, Element::Air) {
-                    let entity = grid_elements.entity(grid_position);
-                    commands.replace_element(grid_position, Element::Sand, *entity, zone);
-                }
+            ExternalSimulationEvent::SpawnSand(grid_position, _zone) => {
+                self.spawn_element(grid_position, Element::Sand)
             }
-            ExternalSimulationEvent::SpawnDirt(grid_position, zone) => {
-                if grid_elements.is(grid_position, Element::Air) {
-                    let entity = grid_elements.entity(grid_position);
-                    commands.replace_element(grid_position, Element::Dirt, *entity, zone);
-                }
+            ExternalSimulationEvent::SpawnDirt(grid_position, _zone) => {
+                self.spawn_element(grid_position, Element::Dirt)
             }
-            ExternalSimulationEvent::DespawnElement(grid_position, zone) => {
-                // TODO: Why is this allowed to fail?
-                if let Some(entity) = grid_elements.get_entity(grid_position) {
-                    commands.replace_element(grid_position, Element::Air, *entity, zone);
-                }
+            ExternalSimulationEvent::SpawnRock(grid_position, _zone) => {
+                self.spawn_element(grid_position, Element::Rock)
+            }
+            ExternalSimulationEvent::SpawnWater(grid_position, _zone) => {
+                self.spawn_element(grid_position, Element::Water)
+            }
+            ExternalSimulationEvent::DespawnElement(grid_position, _zone) => {
+                self.grid_elements.set(grid_position, Element::Air)
             }
             ExternalSimulationEvent::SpawnWorkerAnt(grid_position, zone) => {
-                if grid_elements.is(grid_position, Element::Air) {
-                    commands.spawn_ant(
-                        grid_position,
-                        AntColor(settings.ant_color),
-                        AntOrientation::new(Facing::random(&mut rng.reborrow()), Angle::Zero),
-                        AntInventory::default(),
-                        AntRole::Worker,
-                        AntName::random(&mut rng.reborrow()),
-                        Initiative::new(&mut rng.reborrow()),
-                        zone,
-                    );
+                if !self.grid_elements.is(grid_position, Element::Air) {
+                    return false;
                 }
+
+                self.commands.spawn_ant(
+                    grid_position,
+                    AntColor(self.settings.ant_color),
+                    AntOrientation::new(Facing::random(&mut self.rng.reborrow()), Angle::Zero),
+                    AntInventory::default(),
+                    AntRole::Worker,
+                    AntName::random(&mut self.rng.reborrow()),
+                    Initiative::new(&mut self.rng.reborrow()),
+                    zone,
+                );
+
+                true
             }
             ExternalSimulationEvent::KillAnt(grid_position, _zone) => {
-                if let Some((entity, _, _, _)) = ants_query
+                let ant = self
+                    .ant_position_map
+                    .get(&grid_position)
                     .iter()
-                    .find(|(_, &position, _, _)| position == grid_position)
-                {
-                    commands.entity(entity).insert(Dead).remove::<Initiative>();
+                    .find_map(|&entity| match self.ants_query.get(entity) {
+                        Ok((_, _, is_dead)) => Some((entity, is_dead)),
+                        _ => None,
+                    });
+
+                let Some((entity, is_dead)) = ant else {
+                    return false;
+                };
+
+                self.commands
+                    .entity(entity)
+                    .insert(Dead)
+                    .remove::<Initiative>();
+
+                if !is_dead {
+                    self.ant_died_event_writer
+                        .send(AntDiedEvent(entity, DeathCause::Killed));
                 }
+
+                !is_dead
             }
             ExternalSimulationEvent::DespawnWorkerAnt(grid_position, _zone) => {
-                if let Some((ant_entity, _, _, inventory)) =
-                    ants_query.iter().find(|(_, &position, &role, _)| {
-                        position == grid_position && role == AntRole::Worker
-                    })
-                {
-                    // TODO: This should happen automatically when an ant is despawned
-                    if let Some(element_entity) = &inventory.0 {
-                        commands.entity(*element_entity).despawn();
-                    }
-
-                    commands.entity(ant_entity).despawn_recursive();
+                let worker_ant =
+                    self.ant_position_map
+                        .get(&grid_position)
+                        .iter()
+                        .find_map(|&entity| match self.ants_query.get(entity) {
+                            Ok((&AntRole::Worker, inventory, _)) => Some((entity, inventory.0)),
+                            _ => None,
+                        });
+
+                let Some((ant_entity, inventory_item_entity)) = worker_ant else {
+                    return false;
+                };
+
+                // TODO: This should happen automatically when an ant is despawned
+                if let Some(element_entity) = inventory_item_entity {
+                    self.commands.entity(element_entity).despawn();
                 }
+
+                self.commands.entity(ant_entity).despawn_recursive();
+
+                true
             }
         }
     }
+
+    fn spawn_element(&mut self, position: Position, element: Element) -> bool {
+        self.grid_elements.is(position, Element::Air) && self.grid_elements.set(position, element)
+    }
+}
+
+/// Process user input events at the start of the FixedUpdate simulation loop.
+/// Need to process them manually because they'd be cleared at the end of the next Update
+/// which might occur before the next time FixedUpdate runs.
+/// Events are journaled as they're processed so the story can be replayed, and chronicled so the player can look back on them.
+pub fn process_external_event<Z: Zone + Copy>(
+    mut external_simulation_events: ResMut<Events<ExternalSimulationEvent<Z>>>,
+    mut sandbox_editor: SandboxEditor<Z>,
+    story_time: Res<StoryTime>,
+    mut journal: Option<ResMut<Journal>>,
+    mut chronicle_writer: ChronicleWriter,
+) where
+    JournaledEvent: From<ExternalSimulationEvent<Z>>,
+{
+    for event in external_simulation_events.drain() {
+        if let Some(journal) = journal.as_mut() {
+            journal.record(story_time.elapsed_ticks(), event);
+        }
+
+        // Edits which change nothing aren't worth chronicling.
+        if sandbox_editor.apply(event) {
+            chronicle_writer.record(ChronicleEvent::SandboxEdit {
+                edit: event.into(),
+                count: 1,
+            });
+        }
+    }
 }
@@ -0,0 +1,276 @@
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+
+use crate::{
+    app_state::AppState,
+    common::{LoadProgress, SimulationLoadProgress},
+    journal::{Journal, JournalReplay},
+    save::load_save_data,
+    SimulationPlugin,
+};
+
+/// How many frames setup is allowed to take before assuming the app is stuck outside of AppState::TellStory.
+const MAX_SETUP_UPDATES: usize = 100;
+
+/// Runs the simulation without a window, rendering, or UI.
+/// Intended for CI and experiments where there is no display server and nobody to click through the main menu.
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        // Use a shared, common source of randomness so that the simulation is deterministic.
+        app.init_resource::<GlobalRng>();
+
+        app.add_plugins((MinimalPlugins, RngPlugin::default(), SimulationPlugin));
+
+        // Ticks are only run by `tick`, rather than as real time passes, so that the same seed and the same number of ticks
+        // always tell the same story regardless of how long setup took.
+        app.world.resource_mut::<Time<Virtual>>().pause();
+
+        app.add_systems(
+            Update,
+            check_load_progress.run_if(in_state(AppState::Loading)),
+        );
+
+        // Nobody is around to choose a save slot so always begin a new story which is never saved.
+        app.add_systems(OnEnter(AppState::MainMenu), create_new_story);
+    }
+}
+
+// Mirrors rendering's `check_load_progress`, but there are no assets to wait on.
+fn check_load_progress(
+    mut next_app_state: ResMut<NextState<AppState>>,
+    simulation_load_progress: Res<SimulationLoadProgress>,
+) {
+    if simulation_load_progress.save_file == LoadProgress::Failure {
+        next_app_state.set(AppState::MainMenu);
+    } else if simulation_load_progress.save_file == LoadProgress::Success {
+        next_app_state.set(AppState::FinishSetup);
+    }
+}
+
+fn create_new_story(mut next_app_state: ResMut<NextState<AppState>>) {
+    next_app_state.set(AppState::CreateNewStory);
+}
+
+/// Finish building the app and update it until setup completes and the story is being told.
+pub fn begin_story(app: &mut App) {
+    app.finish();
+    app.cleanup();
+
+    for _ in 0..MAX_SETUP_UPDATES {
+        app.update();
+
+        if *app.world.resource::<State<AppState>>() == AppState::TellStory {
+            return;
+        }
+    }
+
+    panic!(
+        "Failed to reach AppState::TellStory within {} updates",
+        MAX_SETUP_UPDATES
+    );
+}
+
+/// Finish building the app and load a save into it without telling its story, e.g. to inspect it.
+/// `data` is the compressed contents of a save file or an exported `.symbiants` file.
+pub fn load_story(app: &mut App, data: &[u8]) -> Result<(), bevy_save::Error> {
+    app.finish();
+    app.cleanup();
+    // Register persisted types so the save can be deserialized.
+    app.world.run_schedule(Startup);
+
+    load_save_data(&mut app.world, data)
+}
+
+/// Begin a new story from a journal's settings, and scenario, and replay its events as their ticks come around.
+/// Tick the story up to the journal's story time to reproduce it.
+pub fn replay_story(app: &mut App, journal: &Journal) {
+    app.insert_resource(*journal.settings());
+
+    if let Some(scenario) = journal.scenario() {
+        app.insert_resource(scenario.clone());
+    }
+
+    app.insert_resource(JournalReplay::new(journal));
+
+    begin_story(app);
+}
+
+/// Run a single simulation tick regardless of how much real-world time has passed.
+/// Returns false once the story is over.
+pub fn tick(app: &mut App) -> bool {
+    app.world.run_schedule(FixedUpdate);
+    // Apply any state changes queued by the tick, e.g. AppState::EndStory when the queen dies.
+    app.world.run_schedule(StateTransition);
+
+    *app.world.resource::<State<AppState>>() == AppState::TellStory
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::{
+            ant::{hunger::Hunger, AntName, AntRole},
+            grid::ElementGrid,
+            position::Position,
+        },
+        external_event::ExternalSimulationEvent,
+        nest_simulation::nest::AtNest,
+        save::create_save_data,
+        settings::Settings,
+        story_time::StoryTime,
+    };
+
+    const SEED: u64 = 42;
+
+    fn seeded_story_app(seed: u64) -> App {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        app.insert_resource(Settings { seed, ..default() });
+        begin_story(&mut app);
+        app
+    }
+
+    fn tick_until(app: &mut App, elapsed_ticks: isize) {
+        while app.world.resource::<StoryTime>().elapsed_ticks() < elapsed_ticks {
+            assert!(tick(app), "Story ended before {} ticks", elapsed_ticks);
+        }
+    }
+
+    /// Everything the simulation has decided, in an order which doesn't depend on entity ids.
+    fn describe_world(world: &mut World) -> Vec<String> {
+        let mut description = world
+            .query::<(&ElementGrid, Has<AtNest>)>()
+            .iter(world)
+            .flat_map(|(element_grid, is_at_nest)| {
+                element_grid.iter().map(move |(position, element)| {
+                    format!("{:?} {:?} at nest: {}", element, position, is_at_nest)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        description.extend(
+            world
+                .query::<(&AntName, &AntRole, &Hunger, &Position, Has<AtNest>)>()
+                .iter(world)
+                .map(|(name, role, hunger, position, is_at_nest)| {
+                    format!(
+                        "{:?} {:?} {} {:?} at nest: {}",
+                        name,
+                        role,
+                        hunger.value(),
+                        position,
+                        is_at_nest
+                    )
+                }),
+        );
+
+        description.sort();
+        description
+    }
+
+    #[test]
+    fn same_seed_tells_same_story() {
+        let mut app = seeded_story_app(SEED);
+        let mut other_app = seeded_story_app(SEED);
+
+        tick_until(&mut app, 500);
+        tick_until(&mut other_app, 500);
+
+        assert_eq!(
+            describe_world(&mut app.world),
+            describe_world(&mut other_app.world)
+        );
+    }
+
+    #[test]
+    fn different_seed_tells_different_story() {
+        let mut app = seeded_story_app(SEED);
+        let mut other_app = seeded_story_app(SEED + 1);
+
+        tick_until(&mut app, 500);
+        tick_until(&mut other_app, 500);
+
+        assert_ne!(
+            describe_world(&mut app.world),
+            describe_world(&mut other_app.world)
+        );
+    }
+
+    #[test]
+    fn loaded_story_continues_same_story() {
+        let mut app = seeded_story_app(SEED);
+        tick_until(&mut app, 250);
+
+        let data = create_save_data(&mut app.world).unwrap();
+
+        let mut loaded_app = App::new();
+        loaded_app.add_plugins(HeadlessPlugin);
+        load_story(&mut loaded_app, &data).unwrap();
+        // Skip loading from a save slot because the story has already been loaded.
+        loaded_app
+            .world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::FinishSetup);
+        begin_story(&mut loaded_app);
+
+        tick_until(&mut app, 500);
+        tick_until(&mut loaded_app, 500);
+
+        assert_eq!(
+            describe_world(&mut app.world),
+            describe_world(&mut loaded_app.world)
+        );
+    }
+
+    #[test]
+    fn replayed_story_matches_journaled_story() {
+        let mut app = seeded_story_app(SEED);
+
+        let events = [
+            (
+                50,
+                ExternalSimulationEvent::SpawnFood(Position::new(10, 20), AtNest),
+            ),
+            (
+                50,
+                ExternalSimulationEvent::SpawnSand(Position::new(11, 20), AtNest),
+            ),
+            (
+                120,
+                ExternalSimulationEvent::SpawnWorkerAnt(Position::new(30, 30), AtNest),
+            ),
+            (
+                200,
+                ExternalSimulationEvent::DespawnElement(Position::new(40, 100), AtNest),
+            ),
+        ];
+
+        for (elapsed_ticks, event) in events {
+            tick_until(&mut app, elapsed_ticks);
+            app.world.send_event(event);
+        }
+
+        tick_until(&mut app, 500);
+
+        let journal = app.world.resource::<Journal>().clone();
+        assert_eq!(journal.entries().len(), events.len());
+
+        let mut replayed_app = App::new();
+        replayed_app.add_plugins(HeadlessPlugin);
+        replay_story(&mut replayed_app, &journal);
+        tick_until(&mut replayed_app, 500);
+
+        assert!(replayed_app.world.resource::<JournalReplay>().is_finished());
+        assert_eq!(
+            replayed_app.world.resource::<Journal>().entries(),
+            journal.entries()
+        );
+        assert_eq!(
+            describe_world(&mut app.world),
+            describe_world(&mut replayed_app.world)
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{
+    catch_up::PendingCatchUp, crater_simulation::crater::AtCrater,
+    external_event::ExternalSimulationEvent, nest_simulation::nest::AtNest, scenario::Scenario,
+    settings::Settings, story_time::StoryTime,
+};
+use bevy::prelude::*;
+
+/// An external event, from either zone, as it was drained by the simulation.
+/// Also records long absences, in ticks, because they're summarized rather than simulated tick by tick.
+#[derive(Reflect, PartialEq, Copy, Clone, Debug)]
+pub enum JournaledEvent {
+    Nest(ExternalSimulationEvent<AtNest>),
+    Crater(ExternalSimulationEvent<AtCrater>),
+    Absence(isize),
+}
+
+impl From<ExternalSimulationEvent<AtNest>> for JournaledEvent {
+    fn from(event: ExternalSimulationEvent<AtNest>) -> Self {
+        JournaledEvent::Nest(event)
+    }
+}
+
+impl From<ExternalSimulationEvent<AtCrater>> for JournaledEvent {
+    fn from(event: ExternalSimulationEvent<AtCrater>) -> Self {
+        JournaledEvent::Crater(event)
+    }
+}
+
+#[derive(Reflect, PartialEq, Copy, Clone, Debug)]
+pub struct JournalEntry {
+    pub tick: isize,
+    pub event: JournaledEvent,
+}
+
+/// Everything needed to tell a story again: how it began and every input it has received since.
+/// The seed determines every random choice so replaying the entries at their ticks reproduces the story exactly.
+/// Stories saved before journaling existed have no journal and can't be replayed.
+#[derive(Resource, Reflect, Clone, Debug)]
+#[reflect(Resource)]
+pub struct Journal {
+    settings: Settings,
+    entries: Vec<JournalEntry>,
+    scenario: Option<Scenario>,
+}
+
+impl Default for Journal {
+    fn default() -> Self {
+        Journal::new(Settings::default(), None)
+    }
+}
+
+impl Journal {
+    pub fn new(settings: Settings, scenario: Option<Scenario>) -> Self {
+        Self {
+            settings,
+            scenario,
+            entries: vec![],
+        }
+    }
+
+    /// The settings the story began with, which include its seed.
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// The scenario the story began from, if it didn't begin from an empty nest.
+    pub fn scenario(&self) -> Option<&Scenario> {
+        self.scenario.as_ref()
+    }
+
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    pub fn record(&mut self, tick: isize, event: impl Into<JournaledEvent>) {
+        self.entries.push(JournalEntry {
+            tick,
+            event: event.into(),
+        });
+    }
+}
+
+/// A journal being replayed into the current story. Not persisted - replays are driven by whoever inserted it.
+#[derive(Resource, Debug)]
+pub struct JournalReplay {
+    entries: Vec<JournalEntry>,
+    next_index: usize,
+}
+
+impl JournalReplay {
+    pub fn new(journal: &Journal) -> Self {
+        Self {
+            entries: journal.entries.clone(),
+            next_index: 0,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_index >= self.entries.len()
+    }
+}
+
+pub fn register_journal(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Journal>();
+    app_type_registry.write().register::<JournalEntry>();
+    app_type_registry.write().register::<Vec<JournalEntry>>();
+    app_type_registry.write().register::<JournaledEvent>();
+    app_type_registry
+        .write()
+        .register::<ExternalSimulationEvent<AtNest>>();
+    app_type_registry
+        .write()
+        .register::<ExternalSimulationEvent<AtCrater>>();
+}
+
+pub fn initialize_journal(
+    settings: Res<Settings>,
+    scenario: Option<Res<Scenario>>,
+    mut commands: Commands,
+) {
+    let scenario = scenario.map(|scenario| scenario.clone());
+
+    commands.insert_resource(Journal::new(*settings, scenario));
+}
+
+pub fn remove_journal_resources(mut commands: Commands) {
+    commands.remove_resource::<Journal>();
+    commands.remove_resource::<JournalReplay>();
+}
+
+/// Send journaled events, due this tick, so they're processed exactly as they were when first received.
+pub fn replay_journal(
+    mut journal_replay: ResMut<JournalReplay>,
+    story_time: Res<StoryTime>,
+    mut nest_events: EventWriter<ExternalSimulationEvent<AtNest>>,
+    mut crater_events: EventWriter<ExternalSimulationEvent<AtCrater>>,
+    mut commands: Commands,
+) {
+    while let Some(entry) = journal_replay.entries.get(journal_replay.next_index) {
+        if entry.tick > story_time.elapsed_ticks() {
+            break;
+        }
+
+        match entry.event {
+            JournaledEvent::Nest(event) => nest_events.send(event),
+            JournaledEvent::Crater(event) => crater_events.send(event),
+            JournaledEvent::Absence(ticks) => commands.insert_resource(PendingCatchUp(ticks)),
+        }
+
+        journal_replay.next_index += 1;
+    }
+}
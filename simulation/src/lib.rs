@@ -1,11 +1,21 @@
 pub mod app_state;
+pub mod away_report;
+pub mod catch_up;
+pub mod chronicle;
+pub mod colony_stats;
 pub mod common;
 pub mod crater_simulation;
 pub mod external_event;
+pub mod headless;
+pub mod journal;
 pub mod nest_simulation;
 pub mod save;
+pub mod scenario;
 pub mod settings;
+pub mod story_mode;
 pub mod story_time;
+#[cfg(test)]
+mod test_support;
 
 use self::{app_state::AppState, common::despawn_model, story_time::StoryPlaybackState};
 use bevy::prelude::*;
@@ -1,4 +1,5 @@
 use crate::{
+    chronicle::{ChronicleEvent, ChronicleWriter},
     common::{
         ant::{
             commands::AntCommandsExt, Angle, AntColor, AntInventory, AntName, AntOrientation,
@@ -49,8 +50,22 @@ impl Birthing {
     pub fn reset(&mut self) {
         self.value = 0.0;
     }
+
+    /// Equivalent to calling `tick` repeatedly, and giving birth as soon as ready, but without waiting on initiative.
+    /// Returns how many births occurred.
+    pub fn tick_by(&mut self, ticks: isize) -> isize {
+        let value = self.value + self.rate * ticks as f32;
+        let births = (value / self.max).floor();
+        self.value = value - births * self.max;
+
+        births as isize
+    }
 }
 
+/// Sent for each worker ant born, carrying the ant which gave birth.
+#[derive(Event, PartialEq, Copy, Clone, Debug)]
+pub struct AntGaveBirthEvent(pub Entity);
+
 pub fn register_birthing(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<Birthing>();
 }
@@ -58,18 +73,22 @@ pub fn register_birthing(app_type_registry: ResMut<AppTypeRegistry>) {
 pub fn ants_birthing(
     mut ants_birthing_query: Query<
         (
+            Entity,
             &mut Birthing,
             &Position,
             &AntColor,
             &AntOrientation,
+            &AntName,
             &mut Initiative,
         ),
         With<AtNest>,
     >,
     mut commands: Commands,
     mut rng: ResMut<GlobalRng>,
+    mut ant_gave_birth_event_writer: EventWriter<AntGaveBirthEvent>,
+    mut chronicle_writer: ChronicleWriter,
 ) {
-    for (mut birthing, position, color, orientation, mut initiative) in
+    for (ant_entity, mut birthing, position, color, orientation, name, mut initiative) in
         ants_birthing_query.iter_mut()
     {
         birthing.tick();
@@ -86,19 +105,39 @@ pub fn ants_birthing(
             // This isn't going to cause the application to panic, but isn't visually appealing, either.
             // Could introduce a custom command and prevent spawning if the tile is occupied and/or find nearest open tile
             // but since ants can get covered by sand already (when it falls on them) its low priority.
-            // Spawn worker ant (TODO: egg instead)
-            commands.spawn_ant(
-                orientation.get_behind_position(position),
-                AntColor(color.0),
-                AntOrientation::new(Facing::random(&mut rng.reborrow()), Angle::Zero),
-                AntInventory::default(),
-                AntRole::Worker,
-                AntName::random(&mut rng.reborrow()),
-                Initiative::new(&mut rng.reborrow()),
-                AtNest,
-            );
+            let child_name = give_birth(position, color, orientation, &mut rng, &mut commands);
+            ant_gave_birth_event_writer.send(AntGaveBirthEvent(ant_entity));
+            chronicle_writer.record(ChronicleEvent::Birth {
+                mother: name.0.clone(),
+                child: child_name.0,
+            });
 
             birthing.reset();
         }
     }
 }
+
+/// Spawn a worker ant (TODO: egg instead) behind the ant giving birth. Returns the name of the newborn ant.
+pub fn give_birth(
+    position: &Position,
+    color: &AntColor,
+    orientation: &AntOrientation,
+    rng: &mut ResMut<GlobalRng>,
+    commands: &mut Commands,
+) -> AntName {
+    let child_orientation = AntOrientation::new(Facing::random(&mut rng.reborrow()), Angle::Zero);
+    let child_name = AntName::random(&mut rng.reborrow());
+
+    commands.spawn_ant(
+        orientation.get_behind_position(position),
+        AntColor(color.0),
+        child_orientation,
+        AntInventory::default(),
+        AntRole::Worker,
+        child_name.clone(),
+        Initiative::new(&mut rng.reborrow()),
+        AtNest,
+    );
+
+    child_name
+}
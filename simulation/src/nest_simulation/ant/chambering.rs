@@ -2,7 +2,6 @@ use super::birthing::Birthing;
 use crate::{
     common::{
         ant::{commands::AntCommandsExt, AntInventory, AntOrientation, Initiative},
-        element::Element,
         grid::{Grid, GridElements},
         pheromone::{commands::PheromoneCommandsExt, Pheromone, PheromoneMap, PheromoneStrength},
         position::Position,
@@ -162,13 +161,12 @@ fn try_dig(
     }
 
     // Check if hitting a solid element and, if so, consider digging through it.
-    let element_entity = grid_elements.entity(*dig_position);
-    let element = grid_elements.element(*element_entity);
-    if *element == Element::Air {
+    let element = grid_elements.element(*dig_position);
+    if !element.is_diggable() {
         return false;
     }
 
-    commands.dig(*ant_entity, *dig_position, *element_entity, AtNest);
+    commands.dig(*ant_entity, *dig_position, element, AtNest);
 
     true
 }
@@ -91,14 +91,13 @@ fn try_dig(
     }
 
     // Check if hitting a solid element and, if so, consider digging through it.
-    let element_entity = grid_elements.entity(dig_position);
-    let element = grid_elements.element(*element_entity);
-    if *element == Element::Air {
+    let element = grid_elements.element(dig_position);
+    if !element.is_diggable() {
         return false;
     }
 
     // NOTE: can remove this in the future when adding more elements
-    if *element != Element::Sand && *element != Element::Food {
+    if !matches!(element, Element::Sand | Element::Food | Element::Waste) {
         return false;
     }
 
@@ -110,7 +109,7 @@ fn try_dig(
         });
 
         if adjacent_queen.is_some() {
-            commands.dig(ant_entity, dig_position, *element_entity, AtNest);
+            commands.dig(ant_entity, dig_position, element, AtNest);
 
             return true;
         }
@@ -118,21 +117,27 @@ fn try_dig(
 
     let mut dig = false;
 
-    if *element == Element::Food && *ant_role == AntRole::Worker {
+    if element == Element::Food && *ant_role == AntRole::Worker {
         // When above ground, workers prioritize picking up food. Queen needs to focus on nest construction.
         if nest.is_aboveground(&dig_position) {
             dig = rng.f32() < settings.probabilities.above_surface_food_dig;
         } else {
             dig = rng.f32() < settings.probabilities.below_surface_food_dig;
         }
-    } else if *element == Element::Sand && nest.is_underground(&dig_position) {
+    } else if element == Element::Sand && nest.is_underground(&dig_position) {
         // When underground, prioritize clearing out sand and allow for digging tunnels through dirt. Leave food underground.
         // It's OK for queen to pick up sand because sometimes it'll get in the way of nest building.
-        dig = *element == Element::Sand && nest.is_underground(&dig_position);
+        dig = element == Element::Sand && nest.is_underground(&dig_position);
+    } else if element == Element::Waste
+        && *ant_role == AntRole::Worker
+        && nest.is_underground(&dig_position)
+    {
+        // Workers keep waste out of the nest so it doesn't bury the food which is still good.
+        dig = true;
     }
 
     if dig {
-        commands.dig(ant_entity, dig_position, *element_entity, AtNest);
+        commands.dig(ant_entity, dig_position, element, AtNest);
 
         return true;
     }
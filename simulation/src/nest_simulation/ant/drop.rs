@@ -48,15 +48,13 @@ pub fn ants_drop(
 
         // Use ahead position for random inventory drop.
         if rng.f32() < settings.probabilities.random_drop {
-            let target_element_entity = grid_elements.entity(ahead_position);
-            commands.drop(ant_entity, ahead_position, *target_element_entity, AtNest);
+            let target_element = grid_elements.element(ahead_position);
+            commands.drop(ant_entity, ahead_position, target_element, AtNest);
             continue;
         }
 
         // Check if hitting a solid element and, if so, consider digging through it.
-        let entity = grid_elements.entity(ahead_position);
-        let element = grid_elements.element(*entity);
-        if *element != Element::Air {
+        if !grid_elements.is(ahead_position, Element::Air) {
             continue;
         }
 
@@ -68,8 +66,8 @@ pub fn ants_drop(
         // There is an air gap directly ahead of the ant. Consider dropping inventory.
         let inventory_item_element = elements_query.get(inventory.0.unwrap()).unwrap();
 
-        // Prioritize dropping sand above ground and food below ground.
-        let drop_sand = *inventory_item_element == Element::Sand
+        // Prioritize dropping sand, and waste, above ground and food below ground.
+        let drop_sand = matches!(inventory_item_element, Element::Sand | Element::Waste)
             && nest.is_aboveground(&ahead_position)
             && rng.f32() < settings.probabilities.above_surface_sand_drop;
 
@@ -103,8 +101,7 @@ pub fn ants_drop(
 
         if drop_sand || drop_food {
             // Drop inventory in front of ant
-            let target_element_entity = grid_elements.entity(ahead_position);
-            commands.drop(ant_entity, ahead_position, *target_element_entity, AtNest);
+            commands.drop(ant_entity, ahead_position, Element::Air, AtNest);
             continue;
         }
     }
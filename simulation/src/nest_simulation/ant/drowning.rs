@@ -0,0 +1,133 @@
+use crate::{
+    common::{
+        ant::{death::DeathCause, AntDiedEvent, Dead, Initiative},
+        element::Element,
+        grid::GridElements,
+        position::Position,
+    },
+    nest_simulation::nest::AtNest,
+    settings::Settings,
+    story_time::DEFAULT_TICKS_PER_SECOND,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How many ticks an ant has been holding its breath underwater.
+#[derive(Component, Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Reflect, Default)]
+#[reflect(Component)]
+pub struct Submerged(pub isize);
+
+pub fn register_drowning(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Submerged>();
+}
+
+/// Ants in the nest which are still alive.
+type LivingNestAntFilter = (With<AtNest>, Without<Dead>);
+
+/// Ants hold their breath while water fills their tile and catch it again as soon as they're out of the water.
+/// Ants which stay underwater for longer than `max_submerged_time` drown.
+pub fn ants_drown(
+    mut ants_query: Query<(Entity, &Position, Option<&mut Submerged>), LivingNestAntFilter>,
+    grid_elements: GridElements<AtNest>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+    mut ant_died_event_writer: EventWriter<AntDiedEvent>,
+) {
+    let max_submerged_ticks = settings.max_submerged_time * DEFAULT_TICKS_PER_SECOND;
+
+    for (ant_entity, position, submerged) in ants_query.iter_mut() {
+        if !grid_elements.is(*position, Element::Water) {
+            if submerged.is_some() {
+                commands.entity(ant_entity).remove::<Submerged>();
+            }
+
+            continue;
+        }
+
+        let submerged_ticks = submerged.as_ref().map_or(0, |submerged| submerged.0) + 1;
+
+        if submerged_ticks >= max_submerged_ticks {
+            commands
+                .entity(ant_entity)
+                .insert(Dead)
+                .remove::<(Initiative, Submerged)>();
+
+            ant_died_event_writer.send(AntDiedEvent(ant_entity, DeathCause::Drowned));
+        } else if let Some(mut submerged) = submerged {
+            submerged.0 = submerged_ticks;
+        } else {
+            commands
+                .entity(ant_entity)
+                .insert(Submerged(submerged_ticks));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::grid::ElementGrid,
+        test_support::{run_ticks, NestFixture},
+    };
+
+    fn setup(map: &str) -> App {
+        let mut app = NestFixture::new(map).build();
+        app.add_event::<AntDiedEvent>();
+        app.add_systems(Update, ants_drown);
+        app.world.resource_mut::<Settings>().max_submerged_time = 1;
+
+        app
+    }
+
+    fn set_element(app: &mut App, position: Position, element: Element) {
+        app.world
+            .query::<&mut ElementGrid>()
+            .single_mut(&mut app.world)
+            .set(position, element);
+    }
+
+    fn count_dead(app: &mut App) -> usize {
+        app.world
+            .query_filtered::<(), With<Dead>>()
+            .iter(&app.world)
+            .count()
+    }
+
+    #[test]
+    fn submerged_ant_drowns() {
+        let mut app = setup(
+            "
+            >
+            D
+            ",
+        );
+        set_element(&mut app, Position::ZERO, Element::Water);
+
+        run_ticks(&mut app, DEFAULT_TICKS_PER_SECOND as usize - 1);
+        assert_eq!(count_dead(&mut app), 0);
+
+        run_ticks(&mut app, 1);
+        assert_eq!(count_dead(&mut app), 1);
+    }
+
+    #[test]
+    fn ant_catches_its_breath_out_of_water() {
+        let mut app = setup(
+            "
+            >
+            D
+            ",
+        );
+        set_element(&mut app, Position::ZERO, Element::Water);
+        run_ticks(&mut app, DEFAULT_TICKS_PER_SECOND as usize - 1);
+
+        set_element(&mut app, Position::ZERO, Element::Air);
+        run_ticks(&mut app, 1);
+
+        // Holding its breath again starts over rather than picking up where it left off.
+        set_element(&mut app, Position::ZERO, Element::Water);
+        run_ticks(&mut app, DEFAULT_TICKS_PER_SECOND as usize - 1);
+        assert_eq!(count_dead(&mut app), 0);
+    }
+}
@@ -2,6 +2,7 @@ pub mod birthing;
 pub mod chambering;
 pub mod dig;
 pub mod drop;
+pub mod drowning;
 pub mod nest_expansion;
 pub mod nesting;
 pub mod sleep;
@@ -1,6 +1,9 @@
 use crate::{
     common::{
-        ant::{commands::AntCommandsExt, AntInventory, AntOrientation, AntRole, Initiative},
+        ant::{
+            commands::AntCommandsExt, position_map::AntPositionMap, AntInventory, AntOrientation,
+            AntRole, Initiative,
+        },
         element::Element,
         grid::GridElements,
         pheromone::{commands::PheromoneCommandsExt, Pheromone, PheromoneStrength},
@@ -28,6 +31,7 @@ pub fn ants_nest_expansion(
         ),
         With<AtNest>,
     >,
+    ant_position_map: Res<AntPositionMap<AtNest>>,
     grid_elements: GridElements<AtNest>,
     settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
@@ -36,11 +40,6 @@ pub fn ants_nest_expansion(
 ) {
     let nest = nest_query.single();
 
-    let ant_entity_positions = ants_query
-        .iter()
-        .map(|(_, _, _, _, position, entity)| (*position, entity))
-        .collect::<Vec<_>>();
-
     for (ant_role, ant_orientation, inventory, initiative, ant_position, ant_entity) in
         ants_query.iter()
     {
@@ -56,10 +55,11 @@ pub fn ants_nest_expansion(
             continue;
         }
 
-        let is_crowded = ant_entity_positions
-            .iter()
-            .filter(|(other_ant_position, other_ant_entity)| {
-                *other_ant_entity != ant_entity && ant_position.distance(other_ant_position) <= 2
+        let is_crowded = ant_position_map
+            .get_within_radius(ant_position, 2)
+            .into_iter()
+            .filter(|&other_ant_entity| {
+                other_ant_entity != ant_entity && ants_query.contains(other_ant_entity)
             })
             .count()
             >= 2;
@@ -80,8 +80,7 @@ pub fn ants_nest_expansion(
                 continue;
             }
 
-            let dig_target_entity = *grid_elements.entity(dirt_position);
-            commands.dig(ant_entity, dirt_position, dig_target_entity, AtNest);
+            commands.dig(ant_entity, dirt_position, Element::Dirt, AtNest);
             commands.spawn_pheromone(
                 dirt_position,
                 Pheromone::Tunnel,
@@ -1,8 +1,10 @@
 use super::walk::get_turned_orientation;
 use crate::{
+    chronicle::{ChronicleEvent, ChronicleWriter},
     common::{
         ant::{
-            commands::AntCommandsExt, AntInventory, AntOrientation, AntRole, Facing, Initiative,
+            commands::AntCommandsExt, AntInventory, AntName, AntOrientation, AntRole, Facing,
+            Initiative,
         },
         element::Element,
         grid::GridElements,
@@ -110,6 +112,7 @@ pub fn ants_nesting_action(
             &AntInventory,
             &mut Initiative,
             &Position,
+            &AntName,
             Entity,
         ),
         With<AtNest>,
@@ -119,10 +122,11 @@ pub fn ants_nesting_action(
     settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
+    mut chronicle_writer: ChronicleWriter,
 ) {
     let nest = nest_query.single();
 
-    for (mut nesting, orientation, inventory, mut initiative, position, ant_entity) in
+    for (mut nesting, orientation, inventory, mut initiative, position, name, ant_entity) in
         ants_query.iter_mut()
     {
         if !initiative.can_act() {
@@ -162,6 +166,9 @@ pub fn ants_nesting_action(
                 &mut commands,
                 &settings,
             );
+            chronicle_writer.record(ChronicleEvent::NestFounded {
+                queen: name.0.clone(),
+            });
             continue;
         }
     }
@@ -174,9 +181,7 @@ pub fn ants_nesting_action(
 ///     3) The ant must want to dig a nest (based on chance).
 ///     4) The ant must be aboveground.
 ///     5) The ant must not be too close to the edge of the world.
-///     6) The ant must be standing on a diggable element.
-/// TODO:
-///     * Instead of arbitrarily checking if ant is near edge of the map, place immovable rocks which dissuade ant from digging.
+///     6) The ant must be standing on a diggable element, i.e. not rock, which lies near the edges of the world.
 fn can_start_nesting(
     nesting: &Nesting,
     rng: &mut ResMut<GlobalRng>,
@@ -195,21 +200,17 @@ fn can_start_nesting(
         return false;
     }
 
-    // If x position is within 20% of world edge then don't dig there
-    let offset = settings.nest_width / 5;
-    let is_too_near_world_edge =
-        ant_position.x < offset || ant_position.x > settings.nest_width - offset;
-
-    let has_valid_dig_site = nest.is_aboveground(&ant_position) && !is_too_near_world_edge;
+    // Rock keeps ants from nesting near the edges of the world. Nests without it, e.g. scenarios and stories saved
+    // before there was rock, keep ants away from within 20% of the world's edge instead.
+    let is_too_near_world_edge = !settings.terrain.has_edge_rock() && {
+        let offset = settings.nest_width / 5;
+        ant_position.x < offset || ant_position.x > settings.nest_width - offset
+    };
 
     let dig_position = ant_orientation.get_below_position(ant_position);
-    let dig_target_entity = grid_elements.entity(dig_position);
-
-    let is_element_diggable = grid_elements
-        .get_element(*dig_target_entity)
-        .map_or(false, |element| *element != Element::Air);
+    let is_element_diggable = grid_elements.element(dig_position).is_diggable();
 
-    has_valid_dig_site && is_element_diggable
+    nest.is_aboveground(ant_position) && !is_too_near_world_edge && is_element_diggable
 }
 
 /// Start digging a nest by digging its entrance underneath the ant's current position
@@ -227,8 +228,8 @@ fn start_digging_nest(
 ) {
     // TODO: consider just marking tile with pheromone rather than digging immediately
     let dig_position = ant_orientation.get_below_position(ant_position);
-    let dig_target_entity = grid_elements.entity(dig_position);
-    commands.dig(ant_entity, dig_position, *dig_target_entity, AtNest);
+    let dig_target_element = grid_elements.element(dig_position);
+    commands.dig(ant_entity, dig_position, dig_target_element, AtNest);
 
     *nesting = Nesting::Started(dig_position);
     commands.spawn_pheromone(
@@ -244,9 +245,9 @@ fn start_digging_nest(
 ///     1) The ant must be underground.
 ///     2) The ant must be horizontal - newborn ants shouldn't fall.
 ///     3) The ant must be in a spacious chamber - surrounded by air to its left/right/above.
-///     4) The ant must be standing on a sturdy floor - dirt underneath it and behind it.
+///     4) The ant must be standing on a sturdy floor - dirt or rock underneath it and behind it.
 /// TODO:
-///     * The sturdy floor check looks for Dirt, but Sand/Food/Rock is sturdy.
+///     * The sturdy floor check looks for Dirt and Rock, but Sand/Food is sturdy.
 fn can_finish_nesting(
     ant_position: &Position,
     ant_orientation: &AntOrientation,
@@ -282,8 +283,11 @@ fn can_finish_nesting(
     let below_position = ant_orientation.get_below_position(ant_position);
     let behind_below_position = ant_orientation.get_behind_position(&below_position);
 
-    let is_chamber_floor_sturdy =
-        grid_elements.is_all(&[below_position, behind_below_position], Element::Dirt);
+    let is_chamber_floor_sturdy = [below_position, behind_below_position]
+        .iter()
+        .all(|&position| {
+            grid_elements.is(position, Element::Dirt) || grid_elements.is(position, Element::Rock)
+        });
 
     if !is_chamber_floor_sturdy {
         return false;
@@ -312,8 +316,8 @@ fn finish_digging_nest(
 
     if ant_inventory.0 != None {
         let drop_position = ant_orientation.get_ahead_position(ant_position);
-        let drop_target_entity = grid_elements.entity(drop_position);
-        commands.drop(ant_entity, drop_position, *drop_target_entity, AtNest);
+        let drop_target_element = grid_elements.element(drop_position);
+        commands.drop(ant_entity, drop_position, drop_target_element, AtNest);
     } else {
         // TODO: This seems wrong. Everywhere else initiative is hidden behind custom action commands.
         // Ensure that ant doesn't try to move or act after settling down
@@ -2,8 +2,9 @@ use bevy::prelude::*;
 use bevy_turborand::{DelegatedRng, GlobalRng};
 
 use crate::{
+    chronicle::{ChronicleEvent, ChronicleWriter},
     common::{
-        ant::{AntInventory, AntOrientation, AntRole, Initiative},
+        ant::{AntInventory, AntName, AntOrientation, AntRole, Initiative},
         grid::Grid,
         position::Position,
     },
@@ -27,6 +28,7 @@ pub fn ants_travel_to_crater(
             &AntOrientation,
             &AntInventory,
             &AntRole,
+            &AntName,
         ),
         With<AtNest>,
     >,
@@ -34,10 +36,11 @@ pub fn ants_travel_to_crater(
     mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
     settings: Res<Settings>,
+    mut chronicle_writer: ChronicleWriter,
 ) {
     let (grid, nest) = nest_query.single();
 
-    for (ant_entity, mut initiative, position, orientation, inventory, role) in
+    for (ant_entity, mut initiative, position, orientation, inventory, role, name) in
         ants_query.iter_mut()
     {
         if !initiative.can_move() {
@@ -86,6 +89,12 @@ pub fn ants_travel_to_crater(
                 (settings.crater_height / 2) + 1,
             ));
 
+        if !chronicle_writer.has_visited_crater() {
+            chronicle_writer.record(ChronicleEvent::FirstCraterTrip {
+                name: name.0.clone(),
+            });
+        }
+
         initiative.consume();
     }
 }
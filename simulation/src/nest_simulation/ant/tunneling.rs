@@ -48,22 +48,10 @@ pub fn ants_tunnel_pheromone_move(
 
         // If there's solid material in front of ant then consider turning onto it if there's tunnel to follow upward.
         let ahead_position = orientation.get_ahead_position(&ant_position);
-        let has_air_ahead = grid_elements
-            .get_entity(ahead_position)
-            .map_or(false, |entity| {
-                grid_elements
-                    .get_element(*entity)
-                    .map_or(false, |element| *element == Element::Air)
-            });
+        let has_air_ahead = grid_elements.is(ahead_position, Element::Air);
 
         let above_position = orientation.get_above_position(&ant_position);
-        let has_air_above = grid_elements
-            .get_entity(above_position)
-            .map_or(false, |entity| {
-                grid_elements
-                    .get_element(*entity)
-                    .map_or(false, |element| *element == Element::Air)
-            });
+        let has_air_above = grid_elements.is(above_position, Element::Air);
 
         if !has_air_ahead && has_air_above {
             *orientation = get_turned_orientation(
@@ -87,10 +75,8 @@ pub fn ants_tunnel_pheromone_move(
         let foot_orientation = orientation.rotate_forward();
         let foot_position = foot_orientation.get_ahead_position(&ahead_position);
 
-        if let Some(foot_entity) = grid_elements.get_entity(foot_position) {
-            let foot_element = grid_elements.element(*foot_entity);
-
-            if *foot_element == Element::Air {
+        if let Some(foot_element) = grid_elements.get_element(foot_position) {
+            if foot_element == Element::Air {
                 // If ant moves straight forward, it will be standing over air. Instead, turn into the air and remain standing on current block
                 *ant_position = foot_position;
                 *orientation = foot_orientation;
@@ -145,16 +131,14 @@ pub fn ants_tunnel_pheromone_act(
         }
 
         // Check if hitting a solid element and, if so, consider digging through it.
-        let entity = grid_elements.entity(ahead_position);
-        let element = grid_elements.element(*entity);
+        let element = grid_elements.element(ahead_position);
 
-        if *element == Element::Air {
+        if !element.is_diggable() {
             continue;
         }
 
         let dig_position = orientation.get_ahead_position(position);
-        let dig_target_entity = *grid_elements.entity(dig_position);
-        commands.dig(ant_entity, dig_position, dig_target_entity, AtNest);
+        commands.dig(ant_entity, dig_position, element, AtNest);
 
         // Reduce PheromoneStrength by 1 because not digging at ant_position, but ant_position + 1.
         // If this didn't occur then either the ant would need to apply strength-1 to itself when stepping onto a tile, or
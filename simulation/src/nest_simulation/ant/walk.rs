@@ -1,7 +1,6 @@
 use crate::{
     common::{
         ant::{AntOrientation, Initiative},
-        element::Element,
         grid::GridElements,
         position::Position,
     },
@@ -31,8 +30,10 @@ pub fn ants_stabilize_footing_movement(
         }
 
         let below_position = orientation.get_below_position(&position);
-        let has_air_below = grid_elements.is(below_position, Element::Air);
-        if !has_air_below {
+        let is_passable_below = grid_elements
+            .get_element(below_position)
+            .is_some_and(|element| element.is_passable());
+        if !is_passable_below {
             continue;
         }
 
@@ -60,18 +61,14 @@ pub fn ants_walk(
 
         // An ant might be attempting to walk forward into a solid block. If so, they'll turn and walk up the block.
         let ahead_position = orientation.get_ahead_position(&position);
-        let has_air_ahead = grid_elements
-            .get_entity(ahead_position)
-            .map_or(false, |entity| {
-                grid_elements
-                    .get_element(*entity)
-                    .map_or(false, |element| *element == Element::Air)
-            });
+        let is_passable_ahead = grid_elements
+            .get_element(ahead_position)
+            .is_some_and(|element| element.is_passable());
 
         // An ant might turn randomly. This is to prevent ants from getting stuck in loops and add visual variety.
         let is_turning_randomly = rng.chance(settings.probabilities.random_turn.into());
 
-        if !has_air_ahead || is_turning_randomly {
+        if !is_passable_ahead || is_turning_randomly {
             *orientation =
                 get_turned_orientation(&orientation, &position, &nest, &mut rng, &grid_elements);
 
@@ -83,10 +80,8 @@ pub fn ants_walk(
         let foot_orientation = orientation.rotate_forward();
         let foot_position = foot_orientation.get_ahead_position(&ahead_position);
 
-        if let Some(foot_entity) = grid_elements.get_entity(foot_position) {
-            let foot_element = grid_elements.element(*foot_entity);
-
-            if *foot_element == Element::Air {
+        if let Some(foot_element) = grid_elements.get_element(foot_position) {
+            if foot_element.is_passable() {
                 // If ant moves straight forward, it will be standing over air. Instead, turn into the air and remain standing on current block
                 *position = foot_position;
                 *orientation = foot_orientation;
@@ -150,19 +145,18 @@ fn is_valid_location(
     nest: &Nest,
     grid_elemenets: &GridElements<AtNest>,
 ) -> bool {
-    // Need air at the ants' body for it to be a legal ant zone.
-    let Some(entity) = grid_elemenets.get_entity(position) else {
+    // Need air, or water, at the ants' body for it to be a legal ant zone.
+    let Some(element) = grid_elemenets.get_element(position) else {
         return false;
     };
 
-    let element = grid_elemenets.element(*entity);
-    if *element != Element::Air {
+    if !element.is_passable() {
         return false;
     }
 
     // Get the zone beneath the ants' feet and check for air
     let below_position = orientation.get_below_position(&position);
-    let Some(entity) = grid_elemenets.get_entity(below_position) else {
+    let Some(element) = grid_elemenets.get_element(below_position) else {
         // SPECIAL CASE: if underground then an out-of-bounds zone is considered dirt to walk on
         if nest.is_underground(&below_position) {
             return true;
@@ -171,8 +165,7 @@ fn is_valid_location(
         return false;
     };
 
-    let element = grid_elemenets.element(*entity);
-    if *element == Element::Air {
+    if element.is_passable() {
         return false;
     }
 
@@ -2,49 +2,59 @@ use super::nest::AtNest;
 use crate::{
     common::{
         ant::{AntOrientation, Dead, Initiative},
-        element::{commands::ElementCommandsExt, Air, Element},
-        grid::{Grid, GridElements, GridElementsMut},
+        element::Element,
+        grid::{ElementGrid, Grid, GridElements},
         position::Position,
     },
     nest_simulation::nest::Nest,
     settings::Settings,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND},
 };
-use bevy::{prelude::*, utils::HashSet};
+use bevy::prelude::*;
 use bevy_turborand::{DelegatedRng, GlobalRng};
-use std::marker::PhantomData;
-
-// Sand becomes unstable temporarily when falling or adjacent to falling sand
-#[derive(Component, Reflect, Default)]
-#[reflect(Component)]
-pub struct Unstable;
-
-// Don't infer Stable implicitly from lack of Unstable for performance.
-// It's important for a System to be able to apply Unstable to newly added Elements without
-// iterating all Element each run in an attempt to do so.
-#[derive(Component, Reflect, Default)]
-#[reflect(Component)]
-pub struct Stable;
-
-pub fn register_gravity(app_type_registry: ResMut<AppTypeRegistry>) {
-    app_type_registry.write().register::<Unstable>();
-    app_type_registry.write().register::<Stable>();
+
+/// How far water looks to either side for somewhere lower to flow to.
+const WATER_FLOW_DISTANCE: isize = 8;
+
+/// How often water resting on dirt is checked for soaking in. Checking every tick would mean visiting every tile of
+/// the nest every tick.
+const SOAK_INTERVAL: isize = DEFAULT_TICKS_PER_SECOND; // 1 second
+
+/// Sand, food and waste always settle. Dirt aboveground doesn't have "background" supporting dirt to keep it stable - so it falls, too.
+/// Water always settles, too, but flows rather than tumbles.
+fn can_fall(element: Element, position: Position, nest: &Nest) -> bool {
+    match element {
+        Element::Sand | Element::Food | Element::Water | Element::Waste => true,
+        Element::Dirt => nest.is_aboveground(&position),
+        Element::Air | Element::Rock => false,
+    }
 }
 
-// TODO: How to do an exact match when running a test?
-// TODO: Add tests for ant gravity
-// TODO: It would be nice to be able to assert an entire map using shorthand like element_grid
+/// Falling elements swap places with air. Anything heavier than water sinks through it, too.
+fn can_fall_into(element: Element, target_element: Element) -> bool {
+    target_element == Element::Air
+        || (target_element == Element::Water && element != Element::Water)
+}
 
 // Search for a valid position for an element to fall into by searching to the
 // bottom left/center/right of a given position. Prioritize falling straight down
 // and do not fall if surrounded by non-air
 fn get_element_fall_position(
     position: Position,
-    grid_elements: &GridElements<AtNest>,
+    element: Element,
+    element_grid: &ElementGrid,
+    settings: &Settings,
     rng: &mut Mut<GlobalRng>,
 ) -> Option<Position> {
+    let can_fall_to = |position: Position| {
+        element_grid
+            .get(position)
+            .is_some_and(|target_element| can_fall_into(element, target_element))
+    };
+
     // If there is air below then continue falling down.
     let below_position = position + Position::Y;
-    if grid_elements.is(below_position, Element::Air) {
+    if can_fall_to(below_position) {
         return Some(below_position);
     }
 
@@ -52,13 +62,15 @@ fn get_element_fall_position(
     // Look for a column of air two units tall to either side and consider going in one of those directions.
     let left_position = position + Position::NEG_X;
     let left_below_position = position + Position::new(-1, 1);
-    let mut go_left = grid_elements.is_all(&[left_position, left_below_position], Element::Air)
-        && rng.chance(0.66);
+    let mut go_left = can_fall_to(left_position)
+        && can_fall_to(left_below_position)
+        && rng.f32() < settings.probabilities.element_tip;
 
     let right_position = position + Position::X;
-    let right_below_position = position + Position::ONE;
-    let mut go_right = grid_elements.is_all(&[right_position, right_below_position], Element::Air)
-        && rng.chance(0.66);
+    let right_below_position = position + Position::new(1, 1);
+    let mut go_right = can_fall_to(right_position)
+        && can_fall_to(right_below_position)
+        && rng.f32() < settings.probabilities.element_tip;
 
     // Flip a coin and choose a direction randomly to resolve ambiguity in fall direction.
     if go_left && go_right {
@@ -75,47 +87,142 @@ fn get_element_fall_position(
     }
 }
 
+/// Water falls straight down when it can. Otherwise, it flows sideways toward the nearest place it can fall, within
+/// WATER_FLOW_DISTANCE, so that it spills off ledges and fills the low points of a pool rather than heaping up like sand.
+fn get_water_flow_position(
+    position: Position,
+    element_grid: &ElementGrid,
+    rng: &mut Mut<GlobalRng>,
+) -> Option<Position> {
+    let below_position = position + Position::Y;
+    if element_grid.is(below_position, Element::Air) {
+        return Some(below_position);
+    }
+
+    let get_fall_distance = |direction: isize| {
+        (1..=WATER_FLOW_DISTANCE)
+            .map(|distance| position + Position::new(direction * distance, 0))
+            .take_while(|&side_position| element_grid.is(side_position, Element::Air))
+            .position(|side_position| element_grid.is(side_position + Position::Y, Element::Air))
+    };
+
+    let direction = match (get_fall_distance(-1), get_fall_distance(1)) {
+        (Some(left_distance), Some(right_distance)) if left_distance == right_distance => {
+            if rng.bool() {
+                -1
+            } else {
+                1
+            }
+        }
+        (Some(left_distance), Some(right_distance)) => {
+            if left_distance < right_distance {
+                -1
+            } else {
+                1
+            }
+        }
+        (Some(_), None) => -1,
+        (None, Some(_)) => 1,
+        (None, None) => return None,
+    };
+
+    // Spill straight over a ledge rather than stepping out over the air beside it.
+    let side_position = position + Position::new(direction, 0);
+    if element_grid.is(side_position + Position::Y, Element::Air) {
+        Some(side_position + Position::Y)
+    } else {
+        Some(side_position)
+    }
+}
+
+/// Elements only fall where the grid has changed, i.e. its dirty chunks, because everywhere else has already settled.
+/// Falling changes the grid, which dirties the chunks around each fallen element, so elements keep falling until they
+/// come to rest. Elements which don't fall, even by chance, are settled until something changes around them.
 pub fn gravity_elements(
-    mut element_position_queries: ParamSet<(
-        Query<&Position, (With<Element>, With<Unstable>, With<AtNest>)>,
-        Query<&mut Position, (With<Element>, With<AtNest>)>,
-    )>,
+    mut element_grid_query: Query<&mut ElementGrid, With<AtNest>>,
+    nest_query: Query<&Nest>,
+    settings: Res<Settings>,
     mut rng: ResMut<GlobalRng>,
-    mut grid_elements_queries: ParamSet<(GridElements<AtNest>, GridElementsMut<AtNest>)>,
 ) {
-    let element_air_swaps: Vec<_> = element_position_queries
-        .p0()
-        .iter()
-        .filter_map(|&position| {
-            get_element_fall_position(position, &grid_elements_queries.p0(), &mut rng.reborrow())
-                .and_then(|air_position| {
-                    Some((
-                        *grid_elements_queries.p0().get_entity(position)?,
-                        *grid_elements_queries.p0().get_entity(air_position)?,
-                    ))
-                })
+    let Ok(mut element_grid) = element_grid_query.get_single_mut() else {
+        return;
+    };
+
+    let nest = nest_query.single();
+    let dirty_positions = element_grid.take_dirty_positions();
+
+    // Decide where everything falls before moving anything so elements don't fall more than once per tick.
+    let element_swaps: Vec<_> = dirty_positions
+        .into_iter()
+        .filter_map(|position| {
+            let element = element_grid
+                .get(position)
+                .filter(|&element| can_fall(element, position, nest))?;
+
+            let fall_position = if element == Element::Water {
+                get_water_flow_position(position, &element_grid, &mut rng.reborrow())
+            } else {
+                get_element_fall_position(
+                    position,
+                    element,
+                    &element_grid,
+                    &settings,
+                    &mut rng.reborrow(),
+                )
+            };
+
+            fall_position.map(|target_position| (position, target_position))
         })
         .collect();
 
-    // Swap element/air positions and update internal state to reflect the swap
-    for &(element_entity, air_entity) in element_air_swaps.iter() {
-        let mut element_position_query = element_position_queries.p1();
-
-        let Ok([mut air_position, mut element_position]) =
-            element_position_query.get_many_mut([air_entity, element_entity])
-        else {
+    for (element_position, target_position) in element_swaps {
+        let (Some(element), Some(target_element)) = (
+            element_grid.get(element_position),
+            element_grid.get(target_position),
+        ) else {
             continue;
         };
 
-        // Swap element positions internally.
-        (element_position.x, air_position.x) = (air_position.x, element_position.x);
-        (element_position.y, air_position.y) = (air_position.y, element_position.y);
+        // An element which fell earlier this tick might have taken this spot already.
+        if !can_fall_into(element, target_element) {
+            continue;
+        }
+
+        element_grid.set(target_position, element);
+        element_grid.set(element_position, target_element);
+    }
+}
+
+/// Water resting on dirt soaks into it, by chance. Soaking is checked once every SOAK_INTERVAL, rather than every tick,
+/// so settled water doesn't keep its part of the grid from settling, too. The chance of soaking in covers the whole
+/// interval, so water soaks away just as quickly as `water_soak` says it would if it were checked every tick.
+pub fn water_soaks(
+    mut element_grid_query: Query<&mut ElementGrid, With<AtNest>>,
+    story_time: Res<StoryTime>,
+    settings: Res<Settings>,
+    mut rng: ResMut<GlobalRng>,
+) {
+    if story_time.elapsed_ticks() % SOAK_INTERVAL != 0 {
+        return;
+    }
+
+    let Ok(mut element_grid) = element_grid_query.get_single_mut() else {
+        return;
+    };
+
+    let soak_chance = 1.0 - (1.0 - settings.probabilities.water_soak).powi(SOAK_INTERVAL as i32);
+    let soaking_positions = element_grid
+        .iter()
+        .filter(|&(position, element)| {
+            element == Element::Water && element_grid.is(position + Position::Y, Element::Dirt)
+        })
+        .map(|(position, _)| position)
+        .collect::<Vec<_>>();
 
-        // Update indices since they're indexed by position and track where elements are at.
-        grid_elements_queries
-            .p1()
-            .set(*element_position, element_entity);
-        grid_elements_queries.p1().set(*air_position, air_entity);
+    for position in soaking_positions {
+        if rng.f32() < soak_chance {
+            element_grid.set(position, Element::Air);
+        }
     }
 }
 
@@ -142,7 +249,10 @@ pub fn gravity_ants(
         // Figure out foot direction
         let below_position = orientation.get_below_position(&position);
 
-        let is_air_beneath_feet = grid_elements.is_all(&[below_position], Element::Air);
+        // Water doesn't hold ants up any more than air does.
+        let is_air_beneath_feet = grid_elements
+            .get_element(below_position)
+            .is_some_and(|element| element.is_passable());
 
         // SPECIAL CASE: out of bounds underground is considered dirt not air
         let is_out_of_bounds_beneath_feet =
@@ -162,7 +272,9 @@ pub fn gravity_ants(
             || is_dead
         {
             let below_position = *position + Position::Y;
-            let is_air_below = grid_elements.is_all(&[below_position], Element::Air);
+            let is_air_below = grid_elements
+                .get_element(below_position)
+                .is_some_and(|element| element.is_passable());
 
             if is_air_below {
                 position.y = below_position.y;
@@ -179,551 +291,651 @@ pub fn gravity_ants(
     }
 }
 
-// If an air gap appears on the grid (either through spawning or movement of air) then mark adjacent elements as unstable.
-pub fn gravity_mark_unstable(
-    air_query: Query<&Position, (With<Air>, Changed<Position>, With<AtNest>)>,
-    mut commands: Commands,
-    nest_query: Query<&Nest>,
-    grid_elements: GridElements<AtNest>,
-) {
-    let mut positions = HashSet::new();
+#[cfg(test)]
+mod ant_gravity_tests {
+    use super::*;
+    use crate::{
+        common::ant::{Angle, Facing},
+        test_support::{assert_nest, run_ticks, NestFixture},
+    };
 
-    for &position in air_query.iter() {
-        positions.insert(position + Position::new(-1, -1));
-        positions.insert(position + Position::new(0, -1));
-        positions.insert(position + Position::new(1, -1));
-    }
+    fn setup(map: &str) -> App {
+        let mut app = NestFixture::new(map).build();
+        app.add_systems(Update, gravity_ants);
 
-    let nest = nest_query.single();
+        // Remove chance from falling/slipping so only footing decides whether an ant falls.
+        let mut settings = app.world.resource_mut::<Settings>();
+        settings.probabilities.random_fall = 0.0;
+        settings.probabilities.random_slip = 0.0;
 
-    for &position in &positions {
-        // If the current position contains a sand or food element, mark it as unstable
-        if let Some(entity) = grid_elements.get_entity(position) {
-            let element = grid_elements.element(*entity);
-
-            // Special Case - dirt aboveground doesn't have "background" supporting dirt to keep it stable - so it falls.
-            if matches!(*element, Element::Sand | Element::Food)
-                || *element == Element::Dirt && nest.is_aboveground(&position)
-            {
-                commands.toggle_element_command(
-                    *entity,
-                    position,
-                    true,
-                    Unstable,
-                    PhantomData::<AtNest>,
-                );
+        app
+    }
 
-                commands.toggle_element_command(
-                    *entity,
-                    position,
-                    false,
-                    Stable,
-                    PhantomData::<AtNest>,
-                );
-            }
+    fn set_orientation(app: &mut App, orientation: AntOrientation) {
+        for mut ant_orientation in app
+            .world
+            .query::<&mut AntOrientation>()
+            .iter_mut(&mut app.world)
+        {
+            *ant_orientation = orientation;
         }
     }
-}
 
-/// Elements which were Unstable, but didn't move this frame, are marked Stable by removing their Unstable marker.
-/// FIXME: floating column of sand can result in sand being marked stable while in the air due to having sand directly beneath.
-pub fn gravity_mark_stable(
-    unstable_element_query: Query<
-        (Ref<Position>, Entity),
-        (With<Unstable>, With<Element>, With<AtNest>),
-    >,
-    mut commands: Commands,
-) {
-    for (position, entity) in unstable_element_query.iter() {
-        if !position.is_changed() {
-            commands.toggle_element_command(
-                entity,
-                *position,
-                false,
-                Unstable,
-                PhantomData::<AtNest>,
-            );
-            commands.toggle_element_command(entity, *position, true, Stable, PhantomData::<AtNest>);
-        }
+    #[test]
+    fn upright_ant_over_air_falls_down() {
+        let mut app = setup(
+            "
+            >
+            .
+            .
+            ",
+        );
+
+        run_ticks(&mut app, 2);
+
+        assert_nest(
+            &mut app.world,
+            "
+            .
+            .
+            >
+            ",
+        );
+    }
+
+    #[test]
+    fn upright_ant_over_water_sinks() {
+        let mut app = setup(
+            "
+            >
+            W
+            W
+            R
+            ",
+        );
+
+        run_ticks(&mut app, 2);
+
+        assert_nest(
+            &mut app.world,
+            "
+            .
+            W
+            >
+            R
+            ",
+        );
+    }
+
+    #[test]
+    fn upright_ant_over_dirt_stays_put() {
+        let mut app = setup(
+            "
+            >
+            D
+            ",
+        );
+
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            >
+            D
+            ",
+        );
+    }
+
+    #[test]
+    fn sideways_ant_standing_on_dirt_over_air_stays_put() {
+        let mut app = setup(
+            "
+            >D
+            .D
+            ",
+        );
+        // Facing up with feet on the dirt to its right.
+        set_orientation(&mut app, AntOrientation::new(Facing::Right, Angle::Ninety));
+
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            >D
+            .D
+            ",
+        );
+    }
+
+    #[test]
+    fn sideways_ant_standing_on_air_over_air_falls_down() {
+        let mut app = setup(
+            "
+            >.
+            ..
+            ",
+        );
+        set_orientation(&mut app, AntOrientation::new(Facing::Right, Angle::Ninety));
+
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            ..
+            >.
+            ",
+        );
+    }
+
+    #[test]
+    fn dead_ant_falls_down() {
+        let mut app = setup(
+            "
+            >D
+            .D
+            ",
+        );
+        set_orientation(&mut app, AntOrientation::new(Facing::Right, Angle::Ninety));
+        let ant_entity = app
+            .world
+            .query_filtered::<Entity, With<AntOrientation>>()
+            .single(&app.world);
+        app.world.entity_mut(ant_entity).insert(Dead);
+
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            .D
+            >D
+            ",
+        );
     }
 }
 
-/// All Elements are expected to be marked as Stable or Unstable.
-/// Don't rely on just Added<Element> without multiple Without<> filters because that'll require iterating all elements each frame.
-pub fn gravity_set_stability(
-    element_query: Query<
-        (Entity, &Element, &Position),
-        (Without<Unstable>, Without<Stable>, With<AtNest>),
-    >,
-    nest_query: Query<&Nest>,
-    mut commands: Commands,
-) {
-    let nest = nest_query.single();
+#[cfg(test)]
+mod sand_gravity_tests {
+    use super::*;
+    use crate::{
+        common::grid::CHUNK_SIZE,
+        test_support::{assert_nest, run_ticks, NestFixture},
+    };
 
-    // TODO: Consider for_each for perf
-    for (entity, element, position) in element_query.iter() {
-        match element {
-            Element::Air => {
-                commands.entity(entity).insert(Stable);
-            }
-            Element::Dirt => {
-                // Dirt that spawns below surface level is not unstable but dirt that is above is unstable.
-                if nest.is_underground(position) {
-                    commands.entity(entity).insert(Stable);
-                } else {
-                    commands.entity(entity).insert(Unstable);
-                }
-            }
-            // Any sand or food that has just appeared starts off unstable. They'll be marked Stable later.
-            Element::Sand => {
-                commands.entity(entity).insert(Unstable);
-            }
-            Element::Food => {
-                commands.entity(entity).insert(Unstable);
-            }
-        }
+    fn setup(fixture: NestFixture) -> App {
+        let mut app = fixture.build();
+
+        app.add_systems(Update, gravity_elements);
+
+        app
+    }
+
+    fn set_element_tip(app: &mut App, element_tip: f32) {
+        app.world
+            .resource_mut::<Settings>()
+            .probabilities
+            .element_tip = element_tip;
+    }
+
+    // Confirm that sand successfully falls downward through multiple tiles of air.
+    #[test]
+    fn did_sand_fall_down() {
+        let mut app = setup(NestFixture::new(
+            "
+            S
+            .
+            .
+            ",
+        ));
+
+        run_ticks(&mut app, 2);
+
+        assert_nest(
+            &mut app.world,
+            "
+            .
+            .
+            S
+            ",
+        );
+    }
+
+    // Confirm that sand ontop of non-air stays put
+    #[test]
+    fn did_sand_not_fall_down() {
+        let mut app = setup(NestFixture::new(
+            "
+            S
+            D
+            ",
+        ));
+
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            S
+            D
+            ",
+        );
+    }
+
+    // Confirm that sand falls properly to the left
+    #[test]
+    fn did_sand_fall_left() {
+        let mut app = setup(NestFixture::new(
+            "
+            .S
+            .D
+            ",
+        ));
+        set_element_tip(&mut app, 1.0);
+
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            ..
+            SD
+            ",
+        );
+    }
+
+    // Confirm that sand falls properly to the right
+    #[test]
+    fn did_sand_fall_right() {
+        let mut app = setup(NestFixture::new(
+            "
+            S.
+            D.
+            ",
+        ));
+        set_element_tip(&mut app, 1.0);
+
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            ..
+            DS
+            ",
+        );
+    }
+
+    // Confirm that sand which could fall either way, on a tie between l/r, falls to one side or the other by chance.
+    #[test]
+    fn did_sand_fall_either_way_by_chance() {
+        let fell_left = (0..16)
+            .map(|seed| {
+                let mut app = setup(
+                    NestFixture::new(
+                        "
+                        .S.
+                        .D.
+                        ",
+                    )
+                    .with_seed(seed),
+                );
+                set_element_tip(&mut app, 1.0);
+
+                run_ticks(&mut app, 1);
+
+                let element_grid = app.world.query::<&ElementGrid>().single(&app.world);
+                let left = element_grid.get(Position::new(0, 1)) == Some(Element::Sand);
+                let right = element_grid.get(Position::new(2, 1)) == Some(Element::Sand);
+                assert!(left != right, "Sand should fall to exactly one side");
+
+                left
+            })
+            .collect::<Vec<_>>();
+
+        assert!(fell_left.contains(&true));
+        assert!(fell_left.contains(&false));
+    }
+
+    // Confirm that sand which doesn't tip over a ledge while its chunk is dirty comes to rest there.
+    #[test]
+    fn did_sand_rest_on_ledge() {
+        let map = "
+            .S
+            .D
+            ";
+        let mut app = setup(NestFixture::new(map));
+        set_element_tip(&mut app, 0.0);
+
+        run_ticks(&mut app, 10);
+
+        assert_nest(&mut app.world, map);
+    }
+
+    // Confirm that sand does not fall to the left if blocked to its upper-left
+    #[test]
+    fn did_sand_not_fall_upper_left() {
+        let map = "
+            DS
+            .D
+            ";
+        let mut app = setup(NestFixture::new(map));
+
+        run_ticks(&mut app, 10);
+
+        assert_nest(&mut app.world, map);
+    }
+
+    // Confirm that sand does not fall to the left if blocked to its lower-left
+    #[test]
+    fn did_sand_not_fall_lower_left() {
+        let map = "
+            .S
+            DD
+            ";
+        let mut app = setup(NestFixture::new(map));
+
+        run_ticks(&mut app, 10);
+
+        assert_nest(&mut app.world, map);
+    }
+
+    // Confirm that sand does not fall to the right if blocked to its upper-right
+    #[test]
+    fn did_sand_not_fall_upper_right() {
+        let map = "
+            SD
+            D.
+            ";
+        let mut app = setup(NestFixture::new(map));
+
+        run_ticks(&mut app, 10);
+
+        assert_nest(&mut app.world, map);
+    }
+
+    // Confirm that sand does not fall to the right if blocked to its lower-right
+    #[test]
+    fn did_sand_not_fall_lower_right() {
+        let map = "
+            S.
+            DD
+            ";
+        let mut app = setup(NestFixture::new(map));
+
+        run_ticks(&mut app, 10);
+
+        assert_nest(&mut app.world, map);
+    }
+
+    // Confirm that dirt aboveground isn't held up by the surrounding earth and so falls like sand.
+    #[test]
+    fn did_aboveground_dirt_fall_down() {
+        let mut app = setup(
+            NestFixture::new(
+                "
+                D
+                .
+                D
+                ",
+            )
+            .with_surface_level(1),
+        );
+
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            .
+            D
+            D
+            ",
+        );
+    }
+
+    // Confirm that a pillar of floating sand falls all the way down rather than coming to rest in the air.
+    #[test]
+    fn did_floating_sand_column_fall_down() {
+        let mut app = setup(NestFixture::new(
+            "
+            S
+            S
+            S
+            .
+            .
+            ",
+        ));
+
+        run_ticks(&mut app, 10);
+
+        assert_nest(
+            &mut app.world,
+            "
+            .
+            .
+            S
+            S
+            S
+            ",
+        );
+    }
+
+    // Confirm that a column taller than a chunk keeps falling as it crosses from one chunk into the next.
+    #[test]
+    fn did_tall_sand_column_fall_across_chunks() {
+        let height = CHUNK_SIZE as usize * 2;
+        let map = "S\n".repeat(height / 2) + &".\n".repeat(height / 2);
+        let mut app = setup(NestFixture::new(&map));
+
+        run_ticks(&mut app, height);
+
+        assert_nest(
+            &mut app.world,
+            &(".\n".repeat(height / 2) + &"S\n".repeat(height / 2)),
+        );
+    }
+
+    // Confirm that food tumbles like sand.
+    #[test]
+    fn did_food_fall_down() {
+        let mut app = setup(NestFixture::new(
+            "
+            F
+            .
+            ",
+        ));
+
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            .
+            F
+            ",
+        );
+    }
+
+    // Confirm that rock stays put even aboveground where dirt would fall.
+    #[test]
+    fn did_aboveground_rock_not_fall_down() {
+        let mut app = setup(
+            NestFixture::new(
+                "
+                R
+                .
+                ",
+            )
+            .with_surface_level(1),
+        );
+
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            R
+            .
+            ",
+        );
     }
 }
 
-// #[cfg(test)]
-// pub mod ant_gravity_tests {
-//     use crate::save::WorldSaveState;
-
-//     use super::*;
-//     use bevy::{log::LogPlugin, utils::HashMap};
-//     use rand::{rngs::StdRng, SeedableRng};
-//     use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
-
-//     wasm_bindgen_test_configure!(run_in_browser);
-
-//     fn setup(element_grid: Vec<Vec<Element>>) -> App {
-//         let mut app = App::new();
-//         app.add_plugin(LogPlugin::default());
-//         app.add_systems(ant_gravity);
-
-//         let seed = 42069; // ayy lmao
-//         let world_rng = Rng {
-//             rng: StdRng::seed_from_u64(seed),
-//         };
-
-//         app.insert_resource(world_rng);
-
-//         // TODO: probably reuse setup function between gravity and ant tests - maybe all tests
-//         let spawned_elements: HashMap<_, _> = element_grid
-//             .iter()
-//             .enumerate()
-//             .map(|(y, row)| {
-//                 row.iter()
-//                     .enumerate()
-//                     .map(|(x, element)| {
-//                         let position = Position::new(x as isize, y as isize);
-//                         let entity = app
-//                             .world
-//                             .spawn(ElementBundle::create(*element, position))
-//                             .id();
-
-//                         (position, entity)
-//                     })
-//                     .collect::<Vec<_>>()
-//             })
-//             .flatten()
-//             .collect();
-
-//         let height = element_grid.len() as isize;
-//         let width = element_grid.first().map_or(0, |row| row.len()) as isize;
-//         let nest = Nest::new(
-//             width,
-//             height,
-//             0.0,
-//             WorldSaveState::default(),
-//             Some(spawned_elements),
-//         );
-//         app.insert_resource(nest);
-//         app.insert_resource(Settings {
-//             nest_width: width,
-//             nest_height: height,
-//             ..default()
-//         });
-
-//         app
-//     }
-
-//     #[wasm_bindgen_test]
-//     fn upright_ant_over_air_falls_down() {
-//         // Arrange
-//         // let element_grid = vec![vec![Element::Air], vec![Element::Air]];
-//         // let mut app = setup(element_grid);
-//     }
-
-//     fn upright_ant_over_dirt_stays_put() {}
-
-//     fn sideways_left_ant_standing_dirt_over_air_stays_put() {}
-
-//     fn sideways_left_ant_standing_air_over_air_falls_down() {}
-
-//     fn sideways_right_ant_standing_dirt_over_air_stays_put() {}
-
-//     fn sideways_right_ant_standing_air_over_air_falls_down() {}
-
-//     // TODO: This is sus. A sideways ant is able to cling to dirt, but if it starts falling, it should probably keep falling
-//     // rather than exhibiting a super-ant ability to cling to dirt mid-fall.
-//     fn sideways_falling_ant_grabs_dirt() {}
-// }
-
-// TODO: confirm elements are despawned not just that grid is correct
-// #[cfg(test)]
-// pub mod sand_gravity_tests {
-//     use crate::{
-//         element::{ElementBundle, SandElementBundle},
-//         grid::WorldSaveState,
-//     };
-
-//     use super::*;
-//     use bevy::{log::LogPlugin, utils::HashMap};
-//     use rand::{rngs::StdRng, SeedableRng};
-//     use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
-
-//     wasm_bindgen_test_configure!(run_in_browser);
-
-//     // Create a new application to be used for testing the gravity system.
-//     // Map and flatten a grid of elements and spawn associated elements into the world.
-//     fn setup(element_grid: Vec<Vec<Element>>, seed: Option<u64>) -> App {
-//         let mut app = App::new();
-//         app.add_plugin(LogPlugin::default());
-//         app.add_systems(sand_gravity);
-
-//         let seed = seed.unwrap_or(42069); // ayy lmao
-//         let world_rng = Rng(StdRng::seed_from_u64(seed));
-
-//         app.insert_resource(world_rng);
-
-//         let spawned_elements: HashMap<_, _> = element_grid
-//             .iter()
-//             .enumerate()
-//             .map(|(y, row)| {
-//                 row.iter()
-//                     .enumerate()
-//                     .map(|(x, element)| {
-//                         let position = Position::new(x as isize, y as isize);
-
-//                         let entity = match element {
-//                             Element::Air => app.world.spawn(ElementBundle::new(Element::Air, position)).id(),
-//                             Element::Dirt => app.world.spawn(ElementBundle::new(Element::Dirt, position)).id(),
-//                             Element::Sand => app.world.spawn(ElementBundle::new(Element::Sand, position)).id(),
-//                         };
-
-//                         (position, entity)
-//                     })
-//                     .collect::<Vec<_>>()
-//             })
-//             .flatten()
-//             .collect();
-
-//         let height = element_grid.len() as isize;
-//         let width = element_grid.first().map_or(0, |row| row.len()) as isize;
-//         let nest = Nest::new(
-//             width,
-//             height,
-//             0,
-//             WorldSaveState::default(),
-//             Some(spawned_elements),
-//         );
-//         app.insert_resource(nest);
-//         app.insert_resource(Settings {
-//             nest_width: width,
-//             nest_height: height,
-//             ..default()
-//         });
-
-//         app
-//     }
-
-//     // Confirm that sand successfully falls downward through multiple tiles of air.
-//     #[wasm_bindgen_test]
-//     fn did_sand_fall_down() {
-//         // Arrange
-//         let element_grid = vec![vec![Element::Sand], vec![Element::Air], vec![Element::Air]];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::ZERO]),
-//             Some(&Element::Air)
-//         );
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::Y]),
-//             Some(&Element::Air)
-//         );
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::new(0, 2)]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand ontop of non-air stays put
-//     #[wasm_bindgen_test]
-//     fn did_sand_not_fall_down() {
-//         // Arrange
-//         let element_grid = vec![vec![Element::Sand], vec![Element::Dirt]];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::ZERO]),
-//             Some(&Element::Sand)
-//         );
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::Y]),
-//             Some(&Element::Dirt)
-//         );
-//     }
-
-//     // Confirm that sand falls properly to the left
-//     #[wasm_bindgen_test]
-//     fn did_sand_fall_left() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Air, Element::Sand],
-//             vec![Element::Air, Element::Dirt],
-//         ];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::X]),
-//             Some(&Element::Air)
-//         );
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::Y]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand falls properly to the right
-//     #[wasm_bindgen_test]
-//     fn did_sand_fall_right() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Sand, Element::Air],
-//             vec![Element::Dirt, Element::Air],
-//         ];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::ZERO]),
-//             Some(&Element::Air)
-//         );
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::ONE]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand falls to the left on a tie between l/r when given an appropriate random seed
-//     #[wasm_bindgen_test]
-//     fn did_sand_fall_left_by_chance() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Air, Element::Sand, Element::Air],
-//             vec![Element::Air, Element::Dirt, Element::Air],
-//         ];
-//         let mut app = setup(element_grid, Some(3));
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::Y]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand falls to the right on a tie between l/r when given an appropriate random seed
-//     #[wasm_bindgen_test]
-//     fn did_sand_fall_right_by_chance() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Air, Element::Sand, Element::Air],
-//             vec![Element::Air, Element::Dirt, Element::Air],
-//         ];
-//         let mut app = setup(element_grid, Some(1));
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::new(2, 1)]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand does not fall to the left if blocked to its upper-left
-//     #[wasm_bindgen_test]
-//     fn did_sand_not_fall_upper_left() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Dirt, Element::Sand],
-//             vec![Element::Air, Element::Dirt],
-//         ];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::X]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand does not fall to the left if blocked to its lower-left
-//     #[wasm_bindgen_test]
-//     fn did_sand_not_fall_lower_left() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Air, Element::Sand],
-//             vec![Element::Dirt, Element::Dirt],
-//         ];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world.get::<Element>(nest.elements[&Position::X]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand does not fall to the right if blocked to its upper-right
-//     #[wasm_bindgen_test]
-//     fn did_sand_not_fall_upper_right() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Sand, Element::Dirt],
-//             vec![Element::Dirt, Element::Air],
-//         ];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::ZERO]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that sand does not fall to the right if blocked to its lower-right
-//     #[wasm_bindgen_test]
-//     fn did_sand_not_fall_lower_right() {
-//         // Arrange
-//         let element_grid = vec![
-//             vec![Element::Sand, Element::Air],
-//             vec![Element::Dirt, Element::Dirt],
-//         ];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::ZERO]),
-//             Some(&Element::Sand)
-//         );
-//     }
-
-//     // Confirm that a pillar of sand will compact the bottom into dirt
-//     #[wasm_bindgen_test]
-//     fn did_sand_column_compact() {
-//         // Arrange
-//         let element_grid = vec![vec![Element::Sand]; 16];
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::new(0, 15)]),
-//             Some(&Element::Dirt)
-//         );
-//     }
-
-//     // Confirm that a pillar of floating sand falls downward instead of compacting into dirt
-//     #[wasm_bindgen_test]
-//     fn did_floating_sand_column_not_compact() {
-//         // Arrange
-//         let mut element_grid = vec![vec![Element::Sand]; 16];
-//         element_grid.push(vec![Element::Air]);
-//         let mut app = setup(element_grid, None);
-
-//         // Act
-//         app.update();
-
-//         // Assert
-//         let Some(nest) = app.world.get_resource::<Nest>() else { panic!() };
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::new(0, 15)]),
-//             Some(&Element::Air)
-//         );
-
-//         assert_eq!(
-//             app.world
-//                 .get::<Element>(nest.elements[&Position::new(0, 16)]),
-//             Some(&Element::Sand)
-//         );
-//     }
-// }
+#[cfg(test)]
+mod water_gravity_tests {
+    use super::*;
+    use crate::test_support::{assert_nest, run_ticks, NestFixture};
+
+    fn setup(map: &str) -> App {
+        let mut app = NestFixture::new(map).build();
+        app.insert_resource(StoryTime::default());
+        app.add_systems(Update, (water_soaks, gravity_elements).chain());
+
+        // Keep water from soaking away unless a test wants it to.
+        app.world
+            .resource_mut::<Settings>()
+            .probabilities
+            .water_soak = 0.0;
+
+        app
+    }
+
+    // Confirm that water flows sideways, rather than heaping up, until it spills off a ledge.
+    #[test]
+    fn did_water_flow_off_ledge() {
+        let mut app = setup(
+            "
+            W...
+            RRR.
+            RRR.
+            ",
+        );
+
+        run_ticks(&mut app, 10);
+
+        assert_nest(
+            &mut app.world,
+            "
+            ....
+            RRR.
+            RRRW
+            ",
+        );
+    }
+
+    // Confirm that water levels out to fill the bottom of a chamber.
+    #[test]
+    fn did_water_pool() {
+        let mut app = setup(
+            "
+            R..W..R
+            R..W..R
+            R.....R
+            RRRRRRR
+            ",
+        );
+
+        run_ticks(&mut app, 20);
+
+        assert_nest(
+            &mut app.world,
+            "
+            R.....R
+            R.....R
+            R.WW..R
+            RRRRRRR
+            ",
+        );
+    }
+
+    // Confirm that water resting on a flat floor stays put rather than wandering back and forth.
+    #[test]
+    fn did_water_settle() {
+        let mut app = setup(
+            "
+            .W.
+            RRR
+            ",
+        );
+
+        run_ticks(&mut app, 10);
+
+        assert_nest(
+            &mut app.world,
+            "
+            .W.
+            RRR
+            ",
+        );
+    }
+
+    // Confirm that water waiting to soak into dirt settles rather than keeping the grid around it dirty.
+    #[test]
+    fn did_water_settle_on_dirt() {
+        let map = "
+            .W.
+            DDD
+            ";
+        let mut app = setup(map);
+
+        run_ticks(&mut app, 10);
+
+        assert_nest(&mut app.world, map);
+        let mut element_grid = app
+            .world
+            .query::<&mut ElementGrid>()
+            .single_mut(&mut app.world);
+        assert!(element_grid.take_dirty_positions().is_empty());
+    }
+
+    // Confirm that sand sinks through water.
+    #[test]
+    fn did_sand_sink_through_water() {
+        let mut app = setup(
+            "
+            S
+            W
+            R
+            ",
+        );
+
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            W
+            S
+            R
+            ",
+        );
+    }
+
+    // Confirm that water resting on dirt soaks into it while water resting on rock doesn't.
+    #[test]
+    fn did_water_soak_into_dirt() {
+        let mut app = setup(
+            "
+            WW
+            DR
+            ",
+        );
+        app.world
+            .resource_mut::<Settings>()
+            .probabilities
+            .water_soak = 1.0;
+
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            .W
+            DR
+            ",
+        );
+    }
+}
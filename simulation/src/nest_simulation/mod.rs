@@ -1,23 +1,34 @@
 pub mod ant;
 pub mod gravity;
 pub mod nest;
+pub mod spoilage;
 
 use crate::common::{
-    ant::Ant, element::Element, grid::ElementEntityPositionCache, pheromone::{
+    ant::{
+        position_map::{
+            initialize_ant_position_resources, remove_ant_position_resources,
+            update_ant_position_map,
+        },
+        Ant,
+    },
+    element::Element,
+    grid::ElementGrid,
+    pheromone::{
         initialize_pheromone_resources, pheromone_duration_tick, remove_pheromone_resources,
         Pheromone,
-    }
+    },
 };
 
 use self::{
     ant::{
-        birthing::{ants_birthing, register_birthing},
+        birthing::{ants_birthing, register_birthing, AntGaveBirthEvent},
         chambering::{
             ants_add_chamber_pheromone, ants_chamber_pheromone_act, ants_fade_chamber_pheromone,
             ants_remove_chamber_pheromone,
         },
         dig::ants_dig,
         drop::ants_drop,
+        drowning::{ants_drown, register_drowning},
         nest_expansion::ants_nest_expansion,
         nesting::{
             ants_nesting_action, ants_nesting_movement, ants_nesting_start, register_nesting,
@@ -31,17 +42,15 @@ use self::{
         },
         walk::{ants_stabilize_footing_movement, ants_walk},
     },
-    gravity::{
-        gravity_ants, gravity_elements, gravity_mark_stable, gravity_mark_unstable,
-        gravity_set_stability, register_gravity,
-    },
+    gravity::{gravity_ants, gravity_elements, water_soaks},
     nest::{
         insert_nest_grid, register_nest, spawn_nest, spawn_nest_ants, spawn_nest_elements, AtNest,
         Nest,
     },
+    spoilage::food_spoils,
 };
 use super::{
-    despawn_model, settings::initialize_settings_resources, AppState, CleanupSet, FinishSetupSet,
+    despawn_model, settings::seed_global_rng, AppState, CleanupSet, FinishSetupSet,
     SimulationTickSet, StoryPlaybackState,
 };
 use bevy::prelude::*;
@@ -50,28 +59,35 @@ pub struct NestSimulationPlugin;
 
 impl Plugin for NestSimulationPlugin {
     fn build(&self, app: &mut App) {
+        app.add_event::<AntGaveBirthEvent>();
+
         app.add_systems(
             Startup,
             (
                 register_nesting,
                 register_birthing,
-                register_gravity,
+                register_drowning,
                 register_ant,
                 register_nest,
             ),
         );
 
         app.add_systems(
-            OnExit(AppState::MainMenu),
+            OnEnter(AppState::CreateNewStory),
             (
                 // Call `apply_deferred` to ensure Settings (via `initialize_settings_resources`) is available for use.
                 apply_deferred,
                 spawn_nest,
                 apply_deferred,
-                (spawn_nest_elements, spawn_nest_ants),
+                // Ants are spawned after elements, rather than alongside them, so they spawn on the ground and
+                // GlobalRng is always used in the same order.
+                spawn_nest_elements,
+                apply_deferred,
+                spawn_nest_ants,
             )
                 .chain()
-                .after(initialize_settings_resources),
+                // Spawn after seeding GlobalRng so that a story's initial world is determined by its seed.
+                .after(seed_global_rng),
         );
 
         app.add_systems(
@@ -79,7 +95,10 @@ impl Plugin for NestSimulationPlugin {
             (
                 insert_nest_grid,
                 apply_deferred,
-                initialize_pheromone_resources::<AtNest>,
+                (
+                    initialize_pheromone_resources::<AtNest>,
+                    initialize_ant_position_resources::<AtNest>,
+                ),
             )
                 .chain()
                 .in_set(FinishSetupSet::SimulationFinishSetup),
@@ -90,16 +109,16 @@ impl Plugin for NestSimulationPlugin {
             FixedUpdate,
             (
                 // TODO: Consider whether gravity is special enough to warrant being placed in PreSimulationTick
+                // It's helpful to apply gravity first because position updates are applied instantly and are seen by subsequent systems.
+                // Thus, ant actions can take into consideration where an element is this frame rather than where it was last frame.
+                // Ants drown once gravity has settled where the water is this tick. Food spoils, and water soaks
+                // away, first so that the grid settles around them this tick, too.
                 (
-                    gravity_set_stability,
-                    apply_deferred,
-                    // It's helpful to apply gravity first because position updates are applied instantly and are seen by subsequent systems.
-                    // Thus, ant actions can take into consideration where an element is this frame rather than where it was last frame.
+                    food_spoils,
+                    water_soaks,
                     gravity_elements,
                     gravity_ants,
-                    // Gravity side-effects can run whenever with little difference.
-                    gravity_mark_stable,
-                    gravity_mark_unstable,
+                    ants_drown,
                     apply_deferred,
                 )
                     .chain(),
@@ -117,7 +136,12 @@ impl Plugin for NestSimulationPlugin {
                         apply_deferred,
                     )
                         .chain(),
-                    (ants_nest_expansion, apply_deferred).chain(),
+                    (
+                        update_ant_position_map::<AtNest>,
+                        ants_nest_expansion,
+                        apply_deferred,
+                    )
+                        .chain(),
                     (pheromone_duration_tick::<AtNest>, apply_deferred).chain(),
                     // Tunneling Pheromone:
                     (
@@ -176,11 +200,13 @@ impl Plugin for NestSimulationPlugin {
             OnEnter(AppState::Cleanup),
             (
                 despawn_model::<Ant, AtNest>,
+                // Elements which aren't in the grid are being carried by ants.
                 despawn_model::<Element, AtNest>,
-                despawn_model::<ElementEntityPositionCache, AtNest>,
+                despawn_model::<ElementGrid, AtNest>,
                 despawn_model::<Pheromone, AtNest>,
                 despawn_model::<Nest, AtNest>,
                 remove_pheromone_resources::<AtNest>,
+                remove_ant_position_resources::<AtNest>,
             )
                 .in_set(CleanupSet::SimulationCleanup),
         );
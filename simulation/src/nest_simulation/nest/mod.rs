@@ -1,14 +1,18 @@
+pub mod terrain;
+
+use self::terrain::{generate_nest_elements, generate_surface_level};
 use crate::{
     common::{
         ant::{
             digestion::Digestion, hunger::Hunger, Angle, AntBundle, AntColor, AntInventory,
             AntName, AntOrientation, AntRole, Facing, Initiative,
         },
-        element::{Element, ElementBundle},
-        grid::{ElementEntityPositionCache, Grid},
+        element::Element,
+        grid::{ElementGrid, Grid},
         position::Position,
         Zone,
     },
+    scenario::Scenario,
     settings::Settings,
 };
 use bevy::prelude::*;
@@ -50,53 +54,83 @@ pub fn register_nest(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<AtNest>();
 }
 
-pub fn spawn_nest(settings: Res<Settings>, mut commands: Commands) {
-    let surface_level = (settings.nest_height as f32
-        - (settings.nest_height as f32 * settings.initial_dirt_percent))
-        as isize;
+pub fn spawn_nest(
+    settings: Res<Settings>,
+    scenario: Option<Res<Scenario>>,
+    mut rng: ResMut<GlobalRng>,
+    mut commands: Commands,
+) {
+    let surface_level = match scenario {
+        Some(scenario) => scenario.surface_level,
+        None => generate_surface_level(&settings, &mut rng),
+    };
 
     commands.spawn((Nest::new(surface_level), AtNest));
 }
 
 // TODO: despawn_nest_elements?
 
-/// Creates a new grid of Elements. The grid is densley populated.
-/// Note the intentional omission of calling `commands.spawn_element`. This is because
-/// `spawn_element` writes to the grid cache, which is not yet initialized. The grid cache will
-/// be updated after this function is called. This keeps cache initialization parity between
-/// creating a new world and loading an existing world.
+/// Creates a new grid of Elements. The grid is densley populated: air above the surface and generated terrain below,
+/// unless a scenario lays out the nest, and its pheromones, instead.
 pub fn spawn_nest_elements(
     nest_query: Query<&Nest>,
     settings: Res<Settings>,
+    scenario: Option<Res<Scenario>>,
+    mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
 ) {
-    let nest = nest_query.single();
+    if let Some(scenario) = scenario {
+        let element_grid = scenario
+            .nest
+            .to_element_grid(scenario.get_default_nest_element());
 
-    for y in 0..settings.nest_height {
-        for x in 0..settings.nest_width {
-            let position = Position::new(x, y);
+        commands.spawn((element_grid, AtNest));
+        scenario
+            .nest
+            .spawn_pheromones(AtNest, &settings, &mut commands);
 
-            if y <= nest.surface_level {
-                commands.spawn(ElementBundle::new(Element::Air, position, AtNest));
-            } else {
-                commands.spawn(ElementBundle::new(Element::Dirt, position, AtNest));
-            }
-        }
+        return;
     }
+
+    let nest = nest_query.single();
+    let element_grid = generate_nest_elements(nest, &settings, &mut rng);
+
+    commands.spawn((element_grid, AtNest));
 }
 
 pub fn spawn_nest_ants(
     nest_query: Query<&Nest>,
+    element_grid_query: Query<&ElementGrid, With<AtNest>>,
     settings: Res<Settings>,
+    scenario: Option<Res<Scenario>>,
     mut rng: ResMut<GlobalRng>,
     mut commands: Commands,
 ) {
-    let nest = nest_query.single();
     let mut rng = rng.reborrow();
 
+    if let Some(scenario) = scenario {
+        scenario
+            .nest
+            .spawn_ants(AtNest, &settings, &mut rng, &mut commands);
+
+        return;
+    }
+
+    let nest = nest_query.single();
+    let element_grid = element_grid_query.single();
+
+    // Ants stand on the ground, which might be a hill above the surface level.
+    let get_ground_position = |x: isize| {
+        let y = (0..nest.surface_level)
+            .find(|&y| !element_grid.is(Position::new(x, y + 1), Element::Air))
+            .unwrap_or(nest.surface_level);
+
+        Position::new(x, y)
+    };
+
     let queen_ant_bundle = AntBundle::new(
         // Queen always spawns in the center. She'll fall from the sky in the future.
-        Position::new(settings.nest_width / 2, nest.surface_level),
+        get_ground_position(settings.nest_width / 2),
         AntColor(settings.ant_color),
         AntOrientation::new(Facing::random(&mut rng), Angle::Zero),
         AntInventory::default(),
@@ -112,9 +146,7 @@ pub fn spawn_nest_ants(
 
     let worker_ant_bundles = (0..settings.initial_ant_worker_count)
         .map(|_| {
-            // TODO: maybe method on nest now
-            let random_surface_position =
-                Position::new(rng.isize(0..settings.nest_width), nest.surface_level);
+            let random_surface_position = get_ground_position(rng.isize(0..settings.nest_width));
 
             AntBundle::new(
                 random_surface_position,
@@ -135,29 +167,14 @@ pub fn spawn_nest_ants(
 }
 
 /// Called after creating a new story, or loading an existing story from storage.
-/// Creates a cache that maps positions to element entities for quick lookup outside of ECS architecture.
-///
-/// This is used to speed up most logic because there's a consistent need throughout the application to know what elements are
-/// at or near a given position.
+/// Grid isn't persisted because it's derived from Settings.
 pub fn insert_nest_grid(
     nest_query: Query<Entity, With<Nest>>,
-    element_query: Query<(&mut Position, Entity), (With<Element>, With<AtNest>)>,
     settings: Res<Settings>,
     mut commands: Commands,
 ) {
-    let mut elements_cache = vec![
-        vec![Entity::PLACEHOLDER; settings.nest_width as usize];
-        settings.nest_height as usize
-    ];
-
-    for (position, entity) in element_query.iter() {
-        elements_cache[position.y as usize][position.x as usize] = entity;
-    }
-
     commands.entity(nest_query.single()).insert(Grid::new(
         settings.nest_width,
         settings.nest_height,
     ));
-
-    commands.spawn((ElementEntityPositionCache(elements_cache), AtNest));
 }
@@ -0,0 +1,205 @@
+use super::Nest;
+use crate::{
+    common::{
+        element::{deposit::Deposit, Element},
+        grid::ElementGrid,
+        position::Position,
+    },
+    settings::Settings,
+};
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+/// How deep the dirt begins, varied by up to `terrain.depth_variation` so that some nests are deeper than others.
+pub fn generate_surface_level(settings: &Settings, rng: &mut GlobalRng) -> isize {
+    let variation = (rng.f32() * 2.0 - 1.0) * settings.terrain.depth_variation;
+    let dirt_percent = (settings.initial_dirt_percent + variation).clamp(0.0, 1.0);
+
+    (settings.nest_height as f32 - (settings.nest_height as f32 * dirt_percent)) as isize
+}
+
+/// Dirt below an uneven surface, with pockets of sand and caches of food buried within it, and air above.
+/// The surface rises into hills above the nest's surface level, but never below it, so that everything underground
+/// is still below the surface level. Hills flatten out toward the edges of the nest where ants come and go, and the
+/// ground there is crusted with rock so that the queen nests away from the edges.
+pub fn generate_nest_elements(
+    nest: &Nest,
+    settings: &Settings,
+    rng: &mut GlobalRng,
+) -> ElementGrid {
+    let terrain = settings.terrain;
+    let hill_heights = generate_hill_heights(
+        settings.nest_width,
+        terrain.hill_width,
+        // Leave a row of air above the tallest hill for ants to walk along.
+        terrain.hill_height.min(nest.surface_level() - 1).max(0),
+        rng,
+    );
+
+    let food_caches = (0..terrain.food_cache_count)
+        .map(|_| generate_deposit(Element::Food, terrain.food_cache_size, nest, settings, rng))
+        .collect::<Vec<_>>();
+    let sand_pockets = (0..terrain.sand_pocket_count)
+        .map(|_| generate_deposit(Element::Sand, terrain.sand_pocket_size, nest, settings, rng))
+        .collect::<Vec<_>>();
+    let edge_rock_width = (settings.nest_width as f32 * terrain.edge_rock_width) as isize;
+
+    ElementGrid::from_fn(settings.nest_width, settings.nest_height, |position| {
+        let ground_level = nest.surface_level() - hill_heights[position.x as usize];
+        if position.y <= ground_level {
+            return Element::Air;
+        }
+
+        let is_near_edge =
+            position.x < edge_rock_width || position.x >= settings.nest_width - edge_rock_width;
+        if is_near_edge && position.y <= ground_level + terrain.edge_rock_depth {
+            return Element::Rock;
+        }
+
+        food_caches
+            .iter()
+            .chain(sand_pockets.iter())
+            .find(|deposit| deposit.contains(position))
+            .map_or(Element::Dirt, |deposit| deposit.element)
+    })
+}
+
+/// Value noise: a random height for every `hill_width` tiles, smoothly interpolated between.
+fn generate_hill_heights(
+    width: isize,
+    hill_width: isize,
+    hill_height: isize,
+    rng: &mut GlobalRng,
+) -> Vec<isize> {
+    let peaks = (0..=width / hill_width + 1)
+        .map(|_| rng.f32())
+        .collect::<Vec<_>>();
+
+    (0..width)
+        .map(|x| {
+            let index = (x / hill_width) as usize;
+            let t = (x % hill_width) as f32 / hill_width as f32;
+            let smooth_t = t * t * (3.0 - 2.0 * t);
+            let height = peaks[index] + (peaks[index + 1] - peaks[index]) * smooth_t;
+            let edge_distance = x.min(width - 1 - x) as f32 / hill_width as f32;
+
+            (height * edge_distance.min(1.0) * hill_height as f32).round() as isize
+        })
+        .collect()
+}
+
+/// Buried deep enough that the deposit is covered by at least a tile of dirt, even in a valley.
+fn generate_deposit(
+    element: Element,
+    radius: isize,
+    nest: &Nest,
+    settings: &Settings,
+    rng: &mut GlobalRng,
+) -> Deposit {
+    let top = (nest.surface_level() + 2 + radius).min(settings.nest_height - 1);
+
+    Deposit {
+        element,
+        center: Position::new(
+            rng.isize(0..settings.nest_width),
+            rng.isize(top..settings.nest_height),
+        ),
+        radius,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Terrain;
+    use bevy::prelude::default;
+
+    fn generate(settings: &Settings) -> ElementGrid {
+        let mut rng = GlobalRng::with_seed(settings.seed);
+        let nest = Nest::new(generate_surface_level(settings, &mut rng));
+
+        generate_nest_elements(&nest, settings, &mut rng)
+    }
+
+    fn count(element_grid: &ElementGrid, element: Element) -> usize {
+        element_grid
+            .iter()
+            .filter(|&(_, grid_element)| grid_element == element)
+            .count()
+    }
+
+    #[test]
+    fn same_seed_generates_same_nest() {
+        let settings = Settings {
+            seed: 7,
+            ..default()
+        };
+        let element_grid = generate(&settings);
+
+        assert_eq!(
+            generate(&settings).iter().collect::<Vec<_>>(),
+            element_grid.iter().collect::<Vec<_>>()
+        );
+        assert_ne!(
+            generate(&Settings {
+                seed: 8,
+                ..settings
+            })
+            .iter()
+            .collect::<Vec<_>>(),
+            element_grid.iter().collect::<Vec<_>>()
+        );
+        assert!(count(&element_grid, Element::Sand) > 0);
+        assert!(count(&element_grid, Element::Food) > 0);
+    }
+
+    #[test]
+    fn edges_are_crusted_with_rock() {
+        let settings = Settings {
+            terrain: Terrain {
+                edge_rock_width: 0.2,
+                edge_rock_depth: 3,
+                ..Terrain::flat()
+            },
+            ..default()
+        };
+        let mut rng = GlobalRng::with_seed(settings.seed);
+        let nest = Nest::new(generate_surface_level(&settings, &mut rng));
+        let element_grid = generate_nest_elements(&nest, &settings, &mut rng);
+        let below_surface = |x: isize, depth: isize| {
+            element_grid
+                .get(Position::new(x, nest.surface_level() + depth))
+                .unwrap()
+        };
+
+        for x in [0, settings.nest_width / 5 - 1, settings.nest_width - 1] {
+            assert_eq!(below_surface(x, 1), Element::Rock);
+            assert_eq!(below_surface(x, 3), Element::Rock);
+            assert_eq!(below_surface(x, 4), Element::Dirt);
+        }
+
+        assert_eq!(below_surface(settings.nest_width / 2, 1), Element::Dirt);
+    }
+
+    #[test]
+    fn flat_terrain_is_dirt_below_surface() {
+        let settings = Settings {
+            terrain: Terrain::flat(),
+            ..default()
+        };
+        let mut rng = GlobalRng::with_seed(settings.seed);
+        let nest = Nest::new(generate_surface_level(&settings, &mut rng));
+
+        assert_eq!(nest.surface_level(), settings.nest_height / 2);
+
+        let element_grid = generate_nest_elements(&nest, &settings, &mut rng);
+
+        assert!(element_grid.iter().all(|(position, element)| {
+            element
+                == if nest.is_underground(&position) {
+                    Element::Dirt
+                } else {
+                    Element::Air
+                }
+        }));
+    }
+}
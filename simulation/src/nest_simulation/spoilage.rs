@@ -0,0 +1,155 @@
+use super::nest::AtNest;
+use crate::{
+    common::{element::Element, grid::ElementGrid},
+    settings::Settings,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND},
+};
+use bevy::prelude::*;
+
+/// How often food is checked for spoilage. Checking every tick would mean visiting every tile of the nest every tick.
+const SPOILAGE_INTERVAL: isize = 60 * DEFAULT_TICKS_PER_SECOND; // 1 minute
+
+/// Food in the nest spoils into waste once it has been in place for `food_spoil_time`. Food being carried by an ant
+/// doesn't spoil.
+pub fn food_spoils(
+    mut element_grid_query: Query<&mut ElementGrid, With<AtNest>>,
+    story_time: Res<StoryTime>,
+    settings: Res<Settings>,
+) {
+    if story_time.elapsed_ticks() % SPOILAGE_INTERVAL != 0 {
+        return;
+    }
+
+    spoil_food(
+        &mut element_grid_query.single_mut(),
+        story_time.elapsed_ticks(),
+        &settings,
+    );
+}
+
+/// Spoil food which was placed at least `food_spoil_time` before `elapsed_ticks`.
+///
+/// Food is only noticed when it's checked, so food placed since the last check is aged from now. Food which moves,
+/// e.g. by falling or being picked up and dropped, is placed afresh. So, food lasts between `food_spoil_time` and
+/// `food_spoil_time` plus the time between checks.
+pub fn spoil_food(element_grid: &mut ElementGrid, elapsed_ticks: isize, settings: &Settings) {
+    if settings.food_spoil_time == 0 {
+        return;
+    }
+
+    let spoil_ticks = settings.food_spoil_time * DEFAULT_TICKS_PER_SECOND;
+    let food_positions = element_grid
+        .iter()
+        .filter(|&(_, element)| element == Element::Food)
+        .map(|(position, _)| position)
+        .collect::<Vec<_>>();
+
+    for position in food_positions {
+        match element_grid.placed_tick(position) {
+            Some(placed_tick) if elapsed_ticks - placed_tick >= spoil_ticks => {
+                element_grid.set(position, Element::Waste);
+            }
+            Some(_) => {}
+            None => element_grid.set_placed_tick(position, elapsed_ticks),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::position::Position,
+        test_support::{assert_nest, run_ticks, NestFixture},
+    };
+
+    fn setup(food_spoil_time: isize) -> App {
+        let mut app = NestFixture::new(
+            "
+            FFF
+            DDD
+            ",
+        )
+        .build();
+        app.insert_resource(StoryTime::default());
+        app.world.resource_mut::<Settings>().food_spoil_time = food_spoil_time;
+        app.add_systems(Update, food_spoils);
+
+        app
+    }
+
+    fn skip_to_next_check(app: &mut App) {
+        app.world
+            .resource_mut::<StoryTime>()
+            .skip_ticks(SPOILAGE_INTERVAL);
+    }
+
+    #[test]
+    fn food_spoils_into_waste_once_old_enough() {
+        // Food which lasts two minutes is noticed at the first check and spoils two checks later.
+        let mut app = setup(120);
+
+        run_ticks(&mut app, 1);
+        skip_to_next_check(&mut app);
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            FFF
+            DDD
+            ",
+        );
+
+        skip_to_next_check(&mut app);
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            XXX
+            DDD
+            ",
+        );
+    }
+
+    #[test]
+    fn replaced_food_ages_from_when_its_noticed() {
+        let mut app = setup(60);
+
+        run_ticks(&mut app, 1);
+
+        let mut element_grid = app
+            .world
+            .query::<&mut ElementGrid>()
+            .single_mut(&mut app.world);
+        element_grid.set(Position::new(1, 0), Element::Air);
+        element_grid.set(Position::new(1, 0), Element::Food);
+
+        skip_to_next_check(&mut app);
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            XFX
+            DDD
+            ",
+        );
+    }
+
+    #[test]
+    fn food_never_spoils_without_spoil_time() {
+        let mut app = setup(0);
+
+        run_ticks(&mut app, 1);
+
+        assert_nest(
+            &mut app.world,
+            "
+            FFF
+            DDD
+            ",
+        );
+    }
+}
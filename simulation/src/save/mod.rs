@@ -2,6 +2,23 @@
 mod save_os;
 #[cfg(target_arch = "wasm32")]
 mod save_web;
+mod slot;
+mod snapshot;
+mod transfer;
+mod version;
+
+pub use crate::save::slot::{
+    initialize_save_slot_resources, read_save_slots, ActiveSaveSlot, SaveSlot, SaveSlotId,
+    SaveSlots,
+};
+pub use crate::save::snapshot::{
+    close_save_slot, create_save_data, load_save_data, register_save, save, LastSaveTime,
+    LastSnapshotTime,
+};
+pub use crate::save::transfer::{
+    export_save_file, ExportSaveEvent, SaveTransferStatus, EXPORT_FILE_EXTENSION,
+};
+pub use crate::save::version::SAVE_VERSION;
 
 // Re-export the platform-specific implementation
 #[cfg(target_arch = "wasm32")]
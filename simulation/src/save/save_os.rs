@@ -1,18 +1,243 @@
-// TODO: Support saving on non-WASM targets.
-use bevy::prelude::*;
+use bevy::{app::AppExit, prelude::*};
+use bevy_save::{
+    Backend, DefaultDebugFormat, Error, Format, Pipeline, Snapshot, SnapshotBuilder,
+    WorldSaveableExt,
+};
+use serde::de::DeserializeSeed;
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
 
-pub fn save() {}
+use super::{
+    slot::{get_save_slot_key, ActiveSaveSlot, SaveSlots},
+    snapshot::{
+        apply_snapshot, build_snapshot, deserialize_save_snapshot, write_active_save_slot,
+        LastSaveTime, LastSnapshotTime, PersistentModelQueryFilter,
+    },
+    transfer::{get_import_status, import_save_data, SaveTransferStatus},
+};
+use crate::common::{LoadProgress, SimulationLoadProgress};
 
-pub fn bind_save_onbeforeunload() {}
+const SAVE_DIRECTORY_NAME: &str = "symbiants";
+const LOAD_ERROR: &str = "Failed to load world state from save file";
 
-pub fn unbind_save_onbeforeunload() {}
+/// Native equivalent of the web's beforeunload listener. When set, world state is written to disk as the app exits.
+static SAVE_ON_EXIT: AtomicBool = AtomicBool::new(false);
 
-pub fn delete_save_file() {}
+/// Saves live in the user's data directory, e.g. ~/.local/share/symbiants on Linux.
+/// Fall back to the working directory on platforms without a data directory.
+fn get_save_file_path(key: &str) -> PathBuf {
+    dirs::data_dir()
+        .map(|data_dir| data_dir.join(SAVE_DIRECTORY_NAME))
+        .unwrap_or_default()
+        .join(key)
+}
+
+pub(super) fn read_save_data(key: &str) -> Option<Vec<u8>> {
+    fs::read(get_save_file_path(key)).ok()
+}
+
+/// Keys of everything saved, e.g. to find saves which are missing from the index of save slots.
+pub(super) fn list_save_keys() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(get_save_file_path("")) else {
+        return vec![];
+    };
+
+    entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .collect()
+}
+
+pub(super) fn write_save_data(key: &str, data: &[u8]) -> bool {
+    let save_result = write_file_atomically(&get_save_file_path(key), data);
+
+    if save_result.is_err() {
+        error!("Failed to save {} to file: {:?}", key, save_result);
+    }
 
-pub fn load_save_file(_world: &mut World) -> bool {
-    false
+    save_result.is_ok()
 }
 
-pub fn initialize_save_resources() {}
+pub(super) fn delete_save_data(key: &str) {
+    let path = get_save_file_path(key);
+
+    if path.exists() {
+        if let Err(error) = fs::remove_file(&path) {
+            error!("Failed to delete save file {:?}: {:?}", path, error);
+        }
+    }
+}
+
+/// Write to a temporary file then rename it over the save file.
+/// Renaming is atomic so the save file is never left half-written if the app crashes mid-save.
+fn write_file_atomically(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, data)?;
+    fs::rename(&temp_path, path)
+}
+
+/// Exports are written somewhere easy to find, e.g. ~/Downloads, rather than alongside the saves.
+pub(super) fn write_export_file(file_name: &str, data: &[u8]) -> Result<String, String> {
+    let path = dirs::download_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_default()
+        .join(file_name);
+
+    write_file_atomically(&path, data).map_err(|error| error.to_string())?;
+
+    Ok(path.display().to_string())
+}
+
+/// Native builds have no file picker so the player provides the path of the file to import.
+#[derive(Event)]
+pub struct ImportSaveEvent(pub PathBuf);
+
+pub fn import_save_files(
+    mut import_save_events: EventReader<ImportSaveEvent>,
+    mut save_slots: ResMut<SaveSlots>,
+    mut save_transfer_status: ResMut<SaveTransferStatus>,
+) {
+    for ImportSaveEvent(path) in import_save_events.read() {
+        let file_name = path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let result = fs::read(path)
+            .map_err(|error| Error::custom(error.to_string()))
+            .and_then(|data| import_save_data(&mut save_slots, &file_name, &data));
+
+        save_transfer_status.0 = Some(get_import_status(&file_name, result));
+    }
+}
+
+pub fn bind_save_onbeforeunload() {
+    SAVE_ON_EXIT.store(true, Ordering::Relaxed);
+}
+
+pub fn unbind_save_onbeforeunload() {
+    SAVE_ON_EXIT.store(false, Ordering::Relaxed);
+}
+
+/// Unlike the browser, there's still an opportunity to run systems while the app is closing.
+/// So, take a fresh snapshot rather than relying on the periodic one which may be several seconds stale.
+pub fn save_on_app_exit(world: &mut World) {
+    if !SAVE_ON_EXIT.load(Ordering::Relaxed) || world.resource::<Events<AppExit>>().is_empty() {
+        return;
+    }
+
+    write_active_save_slot(world);
+}
 
-pub fn remove_save_resources() {}
\ No newline at end of file
+pub fn initialize_save_resources(mut commands: Commands) {
+    commands.init_resource::<CompressedFileBackend>();
+    commands.init_resource::<LastSnapshotTime>();
+    commands.init_resource::<LastSaveTime>();
+    commands.init_resource::<SaveTransferStatus>();
+}
+
+pub fn remove_save_resources(mut commands: Commands) {
+    commands.remove_resource::<CompressedFileBackend>();
+    commands.remove_resource::<LastSnapshotTime>();
+    commands.remove_resource::<LastSaveTime>();
+    commands.remove_resource::<SaveTransferStatus>();
+}
+
+pub fn load_save_file(world: &mut World) {
+    world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Loading;
+
+    let Some(save_slot_id) = world.resource::<ActiveSaveSlot>().0 else {
+        world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Failure;
+        return;
+    };
+
+    let path = get_save_file_path(&get_save_slot_key(save_slot_id));
+
+    if load_save_file_at(world, &path).is_ok() {
+        world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Success;
+    } else {
+        world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Failure;
+    }
+}
+
+pub(super) fn load_save_file_at(world: &mut World, path: &Path) -> Result<(), Error> {
+    // Loading can happen outside of AppState::Loading, e.g. in tests, so don't assume the backend was initialized.
+    world.init_resource::<CompressedFileBackend>();
+
+    let mut model_query = world.query_filtered::<Entity, PersistentModelQueryFilter>();
+    model_query.update_archetypes(world);
+
+    let readonly_model_query = model_query.as_readonly();
+
+    world.load(SaveLoadPipeline::new(path, readonly_model_query))
+}
+
+struct SaveLoadPipeline<'q> {
+    path: &'q Path,
+    readonly_model_query: &'q QueryState<Entity, PersistentModelQueryFilter>,
+}
+
+impl<'q> SaveLoadPipeline<'q> {
+    pub fn new(
+        path: &'q Path,
+        readonly_model_query: &'q QueryState<Entity, PersistentModelQueryFilter>,
+    ) -> Self {
+        Self {
+            path,
+            readonly_model_query,
+        }
+    }
+}
+
+impl<'q> Pipeline for SaveLoadPipeline<'q> {
+    type Backend = CompressedFileBackend;
+    type Format = DefaultDebugFormat;
+
+    type Key<'a> = &'a Path;
+
+    fn key(&self) -> Self::Key<'_> {
+        self.path
+    }
+
+    fn capture_seed(&self, builder: SnapshotBuilder) -> Snapshot {
+        build_snapshot(builder.world(), self.readonly_model_query)
+    }
+
+    fn apply_seed(&self, world: &mut World, snapshot: &Snapshot) -> Result<(), bevy_save::Error> {
+        apply_snapshot(world, snapshot)
+    }
+}
+
+#[derive(Default, Resource)]
+pub struct CompressedFileBackend;
+
+impl Backend<&Path> for CompressedFileBackend {
+    fn save<F: Format, T: Serialize>(&self, _key: &Path, _value: &T) -> Result<(), Error> {
+        Err(Error::custom(
+            "Not implemented - expected to save by writing snapshot manually for now",
+        ))
+    }
+
+    fn load<F: Format, S: for<'de> DeserializeSeed<'de, Value = T>, T>(
+        &self,
+        key: &Path,
+        seed: S,
+    ) -> Result<T, Error> {
+        // A missing save file is expected on first run so don't log it as an error.
+        let compressed_saved_state = fs::read(key).map_err(|e| {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                error!("{}: {:?}", LOAD_ERROR, e);
+            }
+            Error::custom(LOAD_ERROR)
+        })?;
+
+        deserialize_save_snapshot(&compressed_saved_state, seed)
+    }
+}
@@ -1,144 +1,166 @@
-use bevy::{ecs::query::WorldQuery, prelude::*};
+use bevy::prelude::*;
 use bevy_save::{
     Backend, DefaultDebugFormat, Error, Format, Pipeline, Snapshot, SnapshotBuilder,
-    SnapshotSerializer, WorldSaveableExt,
+    WorldSaveableExt,
 };
-use brotli::enc::BrotliEncoderInitParams;
 use gloo_storage::{LocalStorage, Storage};
 use serde::de::DeserializeSeed;
 use serde::Serialize;
-use std::{cell::RefCell, io::Read, io::Write, sync::Mutex};
+use std::cell::RefCell;
 use wasm_bindgen::{prelude::Closure, JsCast};
-use web_sys::BeforeUnloadEvent;
+use web_sys::{
+    BeforeUnloadEvent, Blob, BlobPropertyBag, FileReader, HtmlAnchorElement, HtmlInputElement, Url,
+};
 
-use crate::{
-    common::{
-        ant::Ant, element::Element, pheromone::Pheromone, LoadProgress, SimulationLoadProgress,
+use super::{
+    slot::{get_save_slot_key, ActiveSaveSlot, SaveSlots},
+    snapshot::{
+        apply_snapshot, build_snapshot, deserialize_save_snapshot, write_save_snapshot,
+        LastSaveTime, LastSnapshotTime, PersistentModelQueryFilter,
     },
-    crater_simulation::crater::Crater,
-    nest_simulation::nest::Nest,
-    settings::Settings,
-    story_time::{StoryRealWorldTime, StoryTime},
+    transfer::{get_import_status, import_save_data, SaveTransferStatus, EXPORT_FILE_EXTENSION},
 };
+use crate::common::{LoadProgress, SimulationLoadProgress};
 
-const LOCAL_STORAGE_KEY: &str = "world-save-state";
 const LOAD_ERROR: &str = "Failed to load world state from local storage";
-const DECOMPRESS_ERROR: &str = "Failed to decompress data";
-
-static SAVE_SNAPSHOT: Mutex<Option<Vec<u8>>> = Mutex::new(None);
-
-#[derive(WorldQuery)]
-struct PersistentModelQueryFilter {
-    _or: Or<(
-        With<Ant>,
-        With<Element>,
-        With<Crater>,
-        With<Nest>,
-        With<Pheromone>,
-    )>,
-}
-
-#[derive(Resource, Default)]
-pub struct LastSnapshotTime(f32);
-
-#[derive(Resource, Default)]
-pub struct LastSaveTime(f32);
-
-/// Provide an opportunity to write world state to disk.
-/// This system does not run every time because saving is costly, but it does run periodically, rather than simply JIT,
-/// to avoid losing too much state in the event of a crash.
-/// NOTE: intentionally don't run immediately on first run because it's expensive and nothing has changed.
-/// Let the full interval pass before creating anything rather than initializing on first run then waiting.
-pub fn save(world: &mut World) {
-    let current_time = world.resource::<Time<Real>>().elapsed_seconds();
-    let last_snapshot_time = world.resource::<LastSnapshotTime>();
-    let snapshot_interval = world.resource::<Settings>().snapshot_interval;
-    if current_time - last_snapshot_time.0 < snapshot_interval as f32 {
-        return;
-    }
-
-    if let Some(snapshot) = create_save_snapshot(world) {
-        *SAVE_SNAPSHOT.lock().unwrap() = Some(snapshot);
-        world.resource_mut::<LastSnapshotTime>().0 = current_time;
-    } else {
-        error!("Failed to create snapshot");
-    }
-
-    let save_interval = world.resource::<Settings>().save_interval;
-    let last_save_time = world.resource::<LastSaveTime>();
-    if current_time - last_save_time.0 < save_interval as f32 {
-        return;
-    }
 
-    if write_save_snapshot() {
-        world.resource_mut::<LastSaveTime>().0 = current_time;
-    }
+pub(super) fn read_save_data(key: &str) -> Option<Vec<u8>> {
+    LocalStorage::get::<Vec<u8>>(key).ok()
 }
 
-fn create_save_snapshot(world: &mut World) -> Option<Vec<u8>> {
-    let mut buffer: Vec<u8> = Vec::new();
-    let mut serde = rmp_serde::Serializer::new(&mut buffer);
+/// Keys of everything saved, e.g. to find saves which are missing from the index of save slots.
+pub(super) fn list_save_keys() -> Vec<String> {
+    let storage = LocalStorage::raw();
+    let length = storage.length().unwrap_or_default();
 
-    // Persistent entities must have an Id marker because Id is fit for uniquely identifying across sessions.
-    // NOTE: Technically this could also include InventoryItem, but Element matches it (just by chance for now though?)
-    let mut model_query = world.query_filtered::<Entity, PersistentModelQueryFilter>();
-
-    model_query.update_archetypes(world);
-    let readonly_model_query = model_query.as_readonly();
-    let snapshot = build_snapshot(world, readonly_model_query);
+    (0..length)
+        .filter_map(|index| storage.key(index).ok().flatten())
+        .collect()
+}
 
-    let registry: &AppTypeRegistry = world.resource::<AppTypeRegistry>();
-    let result = SnapshotSerializer::new(&snapshot, registry).serialize(&mut serde);
+pub(super) fn write_save_data(key: &str, data: &[u8]) -> bool {
+    let save_result = LocalStorage::set(key, data);
 
-    if result.is_ok() {
-        return Some(buffer);
-    } else {
-        error!("Failed to serialize snapshot: {:?}", result);
+    if save_result.is_err() {
+        error!("Failed to save {} to local storage: {:?}", key, save_result);
     }
 
-    None
+    save_result.is_ok()
+}
+
+pub(super) fn delete_save_data(key: &str) {
+    LocalStorage::delete(key);
 }
 
-fn write_save_snapshot() -> bool {
-    let save_snapshot = SAVE_SNAPSHOT.lock().unwrap();
+/// Browsers don't allow writing files directly so prompt the player to download the export instead.
+pub(super) fn write_export_file(file_name: &str, data: &[u8]) -> Result<String, String> {
+    let window = web_sys::window().ok_or("window not available")?;
+    let document = window.document().ok_or("document not available")?;
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(data));
+    let blob = Blob::new_with_u8_array_sequence_and_options(
+        &parts,
+        BlobPropertyBag::new().type_("application/octet-stream"),
+    )
+    .map_err(|error| format!("{:?}", error))?;
+    let url = Url::create_object_url_with_blob(&blob).map_err(|error| format!("{:?}", error))?;
+
+    let anchor = document
+        .create_element("a")
+        .map_err(|error| format!("{:?}", error))?
+        .dyn_into::<HtmlAnchorElement>()
+        .map_err(|error| format!("{:?}", error))?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    Url::revoke_object_url(&url).map_err(|error| format!("{:?}", error))?;
+
+    Ok(format!("your downloads as {}", file_name))
+}
 
-    let buffer = match save_snapshot.as_ref() {
-        Some(buffer) => buffer,
-        // SAVE_SNAPSHOT can be empty during the first few seconds of app load because snapshots are taken periodically.
-        None => return false,
-    };
+/// Ask the player to choose a file to import. Files are read asynchronously so the import completes on a later frame.
+pub fn open_import_file_picker() {
+    let window = web_sys::window().expect("window not available");
+    let document = window.document().expect("document not available");
+
+    let input = document
+        .create_element("input")
+        .expect("Failed to create input element")
+        .dyn_into::<HtmlInputElement>()
+        .expect("Failed to create input element");
+    input.set_type("file");
+    input.set_accept(&format!(".{}", EXPORT_FILE_EXTENSION));
+
+    let on_change = Closure::once_into_js(move |event: web_sys::Event| {
+        let Some(file) = event
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
+            .and_then(|input| input.files())
+            .and_then(|files| files.get(0))
+        else {
+            return;
+        };
+
+        let Ok(file_reader) = FileReader::new() else {
+            return;
+        };
+
+        let file_name = file.name();
+        let on_load = Closure::once_into_js(move |event: web_sys::ProgressEvent| {
+            let result = event
+                .target()
+                .and_then(|target| target.dyn_into::<FileReader>().ok())
+                .and_then(|file_reader| file_reader.result().ok());
+
+            if let Some(result) = result {
+                let data = js_sys::Uint8Array::new(&result).to_vec();
+                PENDING_IMPORTS.with(|pending_imports| {
+                    pending_imports.borrow_mut().push((file_name, data));
+                });
+            }
+        });
+
+        file_reader.set_onload(Some(on_load.unchecked_ref()));
+        if let Err(error) = file_reader.read_as_array_buffer(&file) {
+            error!("Failed to read import file: {:?}", error);
+        }
+    });
 
-    // Compress snapshot using Brotli. In testing, this reduces a 4mb save file to 0.5mb with compression quality: 1.
-    let mut params = BrotliEncoderInitParams();
-    params.quality = 1; // Max compression (0-11 range)
+    input.set_onchange(Some(on_change.unchecked_ref()));
+    input.click();
+}
 
-    let mut compressed_data = brotli::CompressorWriter::with_params(Vec::new(), 4096, &params);
-    compressed_data
-        .write_all(buffer)
-        .expect("Failed to write to compressor");
+pub fn import_save_files(
+    mut save_slots: ResMut<SaveSlots>,
+    mut save_transfer_status: ResMut<SaveTransferStatus>,
+) {
+    let pending_imports = PENDING_IMPORTS.with(|pending_imports| pending_imports.take());
 
-    let save_result = LocalStorage::set(LOCAL_STORAGE_KEY, compressed_data.into_inner());
+    for (file_name, data) in pending_imports {
+        let result = import_save_data(&mut save_slots, &file_name, &data);
 
-    if save_result.is_err() {
-        error!(
-            "Failed to save world state to local storage: {:?}",
-            save_result
-        );
+        save_transfer_status.0 = Some(get_import_status(&file_name, result));
     }
-
-    save_result.is_ok()
 }
 
 thread_local! {
+    /// Files which have been read by the file picker, but not yet imported, as (file name, data).
+    static PENDING_IMPORTS: RefCell<Vec<(String, Vec<u8>)>> = RefCell::new(vec![]);
     static ON_BEFORE_UNLOAD: RefCell<Option<Closure<dyn FnMut(BeforeUnloadEvent) -> bool>>> = RefCell::new(None);
 }
 
-pub fn bind_save_onbeforeunload() {
+pub fn bind_save_onbeforeunload(active_save_slot: Res<ActiveSaveSlot>) {
+    let Some(save_slot_id) = active_save_slot.0 else {
+        return;
+    };
+
+    let key = get_save_slot_key(save_slot_id);
     let window = web_sys::window().expect("window not available");
 
     ON_BEFORE_UNLOAD.with(|opt_closure| {
         let closure = Closure::wrap(Box::new(move |_| {
-            write_save_snapshot();
+            write_save_snapshot(&key);
             // Tell browser not to interrupt the unload
             false
         }) as Box<dyn FnMut(BeforeUnloadEvent) -> bool>);
@@ -166,32 +188,38 @@ pub fn unbind_save_onbeforeunload() {
     });
 }
 
-pub fn delete_save_file() {
-    LocalStorage::delete(LOCAL_STORAGE_KEY);
-}
-
 pub fn initialize_save_resources(mut commands: Commands) {
     commands.init_resource::<CompressedWebStorageBackend>();
     commands.init_resource::<LastSnapshotTime>();
     commands.init_resource::<LastSaveTime>();
+    commands.init_resource::<SaveTransferStatus>();
 }
 
 pub fn remove_save_resources(mut commands: Commands) {
     commands.remove_resource::<CompressedWebStorageBackend>();
     commands.remove_resource::<LastSnapshotTime>();
     commands.remove_resource::<LastSaveTime>();
+    commands.remove_resource::<SaveTransferStatus>();
 }
 
 pub fn load_save_file(world: &mut World) {
+    world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Loading;
+
+    let Some(save_slot_id) = world.resource::<ActiveSaveSlot>().0 else {
+        world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Failure;
+        return;
+    };
+
     let mut model_query = world.query_filtered::<Entity, PersistentModelQueryFilter>();
     model_query.update_archetypes(world);
 
     let readonly_model_query = model_query.as_readonly();
 
-    world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Loading;
-
     if world
-        .load(SaveLoadPipeline::new(readonly_model_query))
+        .load(SaveLoadPipeline::new(
+            get_save_slot_key(save_slot_id),
+            readonly_model_query,
+        ))
         .is_ok()
     {
         world.resource_mut::<SimulationLoadProgress>().save_file = LoadProgress::Success;
@@ -206,9 +234,12 @@ struct SaveLoadPipeline<'q> {
 }
 
 impl<'q> SaveLoadPipeline<'q> {
-    pub fn new(readonly_model_query: &'q QueryState<Entity, PersistentModelQueryFilter>) -> Self {
+    pub fn new(
+        key: String,
+        readonly_model_query: &'q QueryState<Entity, PersistentModelQueryFilter>,
+    ) -> Self {
         Self {
-            key: LOCAL_STORAGE_KEY.to_string(),
+            key,
             readonly_model_query,
         }
     }
@@ -229,7 +260,7 @@ impl<'q> Pipeline for SaveLoadPipeline<'q> {
     }
 
     fn apply_seed(&self, world: &mut World, snapshot: &Snapshot) -> Result<(), bevy_save::Error> {
-        snapshot.applier(world).apply()
+        apply_snapshot(world, snapshot)
     }
 }
 
@@ -254,32 +285,6 @@ impl<'a> Backend<&'a str> for CompressedWebStorageBackend {
             Error::custom(LOAD_ERROR)
         })?;
 
-        // Initialize the decompressor
-        let mut decompressor = brotli::Decompressor::new(&compressed_saved_state[..], 4096);
-        let mut decompressed_data = Vec::new();
-
-        // Attempt to decompress the data
-        decompressor
-            .read_to_end(&mut decompressed_data)
-            .map_err(|e| {
-                error!("{}: {:?}", DECOMPRESS_ERROR, e);
-                Error::custom(DECOMPRESS_ERROR)
-            })?;
-
-        // Deserialize the data
-        let mut deserializer = rmp_serde::Deserializer::new(&decompressed_data[..]);
-        seed.deserialize(&mut deserializer).map_err(Error::loading)
+        deserialize_save_snapshot(&compressed_saved_state, seed)
     }
 }
-
-fn build_snapshot(
-    world: &World,
-    readonly_model_query: &QueryState<Entity, PersistentModelQueryFilter>,
-) -> Snapshot {
-    Snapshot::builder(world)
-        .extract_entities(readonly_model_query.iter_manual(world))
-        .extract_resource::<Settings>()
-        .extract_resource::<StoryTime>()
-        .extract_resource::<StoryRealWorldTime>()
-        .build()
-}
@@ -0,0 +1,295 @@
+use bevy::prelude::*;
+use chrono::{DateTime, Local, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{delete_save_data, list_save_keys, read_save_data, write_save_data};
+
+/// Saves written before slots existed were stored under this key. They're adopted as the first slot.
+const LEGACY_SAVE_KEY: &str = "world-save-state";
+const SAVE_SLOTS_KEY: &str = "save-slots";
+
+pub type SaveSlotId = u32;
+
+pub(super) fn get_save_slot_key(id: SaveSlotId) -> String {
+    format!("{}-{}", LEGACY_SAVE_KEY, id)
+}
+
+/// The inverse of `get_save_slot_key`. None for keys which aren't a slot's, e.g. the legacy save or the index.
+fn get_save_slot_id(key: &str) -> Option<SaveSlotId> {
+    key.strip_prefix(LEGACY_SAVE_KEY)?
+        .strip_prefix('-')?
+        .parse()
+        .ok()
+}
+
+/// Ids of every slot which has been saved to, whether or not the index knows about it.
+fn get_saved_slot_ids() -> Vec<SaveSlotId> {
+    let mut ids = list_save_keys()
+        .iter()
+        .filter_map(|key| get_save_slot_id(key))
+        .collect::<Vec<_>>();
+    ids.sort_unstable();
+
+    ids
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SaveSlot {
+    id: SaveSlotId,
+    name: String,
+    // Timestamps are stored as milliseconds, like StoryRealWorldTime, rather than DateTime<Utc>.
+    created: i64,
+    last_played: i64,
+    ant_count: usize,
+}
+
+impl SaveSlot {
+    pub fn id(&self) -> SaveSlotId {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn created(&self) -> DateTime<Local> {
+        Local.from_utc_datetime(&timestamp_to_datetime(self.created).naive_utc())
+    }
+
+    pub fn last_played(&self) -> DateTime<Local> {
+        Local.from_utc_datetime(&timestamp_to_datetime(self.last_played).naive_utc())
+    }
+
+    pub fn ant_count(&self) -> usize {
+        self.ant_count
+    }
+}
+
+fn timestamp_to_datetime(timestamp: i64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(timestamp)
+        .single()
+        .unwrap_or_default()
+}
+
+/// An index of every saved colony. It's persisted separately from the colonies themselves
+/// so that they can be listed without decompressing each one.
+///
+/// The index is only read from, and written to, storage by apps which let the player choose a colony. Elsewhere,
+/// e.g. headless runs and tests, it's kept in memory so that they never touch the player's saves.
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct SaveSlots {
+    slots: Vec<SaveSlot>,
+    next_id: SaveSlotId,
+    #[serde(skip)]
+    is_stored: bool,
+}
+
+impl SaveSlots {
+    fn read() -> Self {
+        let mut save_slots = match read_save_data(SAVE_SLOTS_KEY) {
+            Some(data) => match rmp_serde::from_slice::<SaveSlots>(&data) {
+                Ok(save_slots) => {
+                    return SaveSlots {
+                        is_stored: true,
+                        ..save_slots
+                    }
+                }
+                Err(error) => {
+                    error!("Failed to read save slots, rebuilding them: {:?}", error);
+                    Self::rebuild()
+                }
+            },
+            None => SaveSlots {
+                is_stored: true,
+                ..default()
+            },
+        };
+
+        if let Some(data) = read_save_data(LEGACY_SAVE_KEY) {
+            let id = save_slots.create("Colony");
+
+            if write_save_data(&get_save_slot_key(id), &data) {
+                delete_save_data(LEGACY_SAVE_KEY);
+            }
+        }
+
+        save_slots
+    }
+
+    /// Recover an index of every saved slot when the index itself can't be read so that no colony is lost.
+    /// Slots' names and summaries are lost with the index so they're named after their ids until renamed.
+    fn rebuild() -> Self {
+        let now = Utc::now().timestamp_millis();
+        let ids = get_saved_slot_ids();
+
+        let save_slots = SaveSlots {
+            slots: ids
+                .iter()
+                .map(|&id| SaveSlot {
+                    id,
+                    name: format!("Colony {}", id + 1),
+                    created: now,
+                    last_played: now,
+                    ant_count: 0,
+                })
+                .collect(),
+            next_id: ids.last().map_or(0, |&id| id + 1),
+            is_stored: true,
+        };
+        save_slots.write();
+
+        save_slots
+    }
+
+    fn write(&self) {
+        if !self.is_stored {
+            return;
+        }
+
+        match rmp_serde::to_vec(self) {
+            Ok(data) => {
+                write_save_data(SAVE_SLOTS_KEY, &data);
+            }
+            Err(error) => error!("Failed to write save slots: {:?}", error),
+        }
+    }
+
+    /// Slots ordered from most to least recently played.
+    pub fn iter(&self) -> impl Iterator<Item = &SaveSlot> {
+        let mut slots = self.slots.iter().collect::<Vec<_>>();
+        slots.sort_by_key(|slot| std::cmp::Reverse(slot.last_played));
+        slots.into_iter()
+    }
+
+    pub fn get(&self, id: SaveSlotId) -> Option<&SaveSlot> {
+        self.slots.iter().find(|slot| slot.id == id)
+    }
+
+    pub fn most_recent(&self) -> Option<&SaveSlot> {
+        self.iter().next()
+    }
+
+    /// Create an empty slot. It's written to once the new story is first saved.
+    pub fn create(&mut self, name: &str) -> SaveSlotId {
+        // Never reuse the key of an existing save, even one the index has lost track of, lest it be overwritten.
+        let saved_slot_ids = if self.is_stored {
+            get_saved_slot_ids()
+        } else {
+            vec![]
+        };
+        while self.get(self.next_id).is_some() || saved_slot_ids.contains(&self.next_id) {
+            self.next_id += 1;
+        }
+
+        let id = self.next_id;
+        let now = Utc::now().timestamp_millis();
+
+        let name = if name.trim().is_empty() {
+            format!("Colony {}", id + 1)
+        } else {
+            name.trim().to_string()
+        };
+
+        self.slots.push(SaveSlot {
+            id,
+            name,
+            created: now,
+            last_played: now,
+            ant_count: 0,
+        });
+        self.next_id += 1;
+        self.write();
+
+        id
+    }
+
+    pub fn duplicate(&mut self, id: SaveSlotId) -> Option<SaveSlotId> {
+        let slot = self.get(id)?.clone();
+        let data = read_save_data(&get_save_slot_key(id))?;
+
+        let duplicate_id = self.import(&format!("{} (Copy)", slot.name), &data)?;
+
+        if let Some(duplicate_slot) = self.get_mut(duplicate_id) {
+            duplicate_slot.last_played = slot.last_played;
+            duplicate_slot.ant_count = slot.ant_count;
+        }
+        self.write();
+
+        Some(duplicate_id)
+    }
+
+    /// Create a slot which already has a saved story.
+    pub(super) fn import(&mut self, name: &str, data: &[u8]) -> Option<SaveSlotId> {
+        let id = self.create(name);
+
+        if !write_save_data(&get_save_slot_key(id), data) {
+            self.delete(id);
+            return None;
+        }
+
+        Some(id)
+    }
+
+    pub fn rename(&mut self, id: SaveSlotId, name: &str) {
+        if name.trim().is_empty() {
+            return;
+        }
+
+        if let Some(slot) = self.get_mut(id) {
+            slot.name = name.trim().to_string();
+            self.write();
+        }
+    }
+
+    pub fn delete(&mut self, id: SaveSlotId) {
+        delete_save_data(&get_save_slot_key(id));
+
+        self.slots.retain(|slot| slot.id != id);
+        self.write();
+    }
+
+    /// Keep the summary shown in the main menu in sync with the most recent save of the slot's story.
+    pub(super) fn record_save(&mut self, id: SaveSlotId, last_played: i64, ant_count: usize) {
+        if let Some(slot) = self.get_mut(id) {
+            slot.last_played = last_played;
+            slot.ant_count = ant_count;
+            self.write();
+        }
+    }
+
+    fn get_mut(&mut self, id: SaveSlotId) -> Option<&mut SaveSlot> {
+        self.slots.iter_mut().find(|slot| slot.id == id)
+    }
+}
+
+/// The slot the current story was loaded from and is saved to.
+/// Stories without a slot, e.g. headless runs, are never saved.
+#[derive(Resource, Default)]
+pub struct ActiveSaveSlot(pub Option<SaveSlotId>);
+
+// NOTE: These resources live for the lifetime of the app, rather than being removed during cleanup,
+// because the main menu needs them to choose which story to tell next.
+pub fn initialize_save_slot_resources(mut commands: Commands) {
+    commands.init_resource::<SaveSlots>();
+    commands.init_resource::<ActiveSaveSlot>();
+}
+
+/// Replace the in-memory index of save slots with the player's. Only apps which let the player choose a colony,
+/// i.e. not headless runs or tests, should read it because adopting a legacy save moves the player's files.
+pub fn read_save_slots(mut save_slots: ResMut<SaveSlots>) {
+    *save_slots = SaveSlots::read();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_slot_ids_are_found_in_their_keys() {
+        assert_eq!(get_save_slot_id(&get_save_slot_key(3)), Some(3));
+        assert_eq!(get_save_slot_id(LEGACY_SAVE_KEY), None);
+        assert_eq!(get_save_slot_id(SAVE_SLOTS_KEY), None);
+        // Saves are written to a temporary file before replacing the slot's.
+        assert_eq!(get_save_slot_id("world-save-state-3.tmp"), None);
+    }
+}
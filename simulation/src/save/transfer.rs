@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use bevy_save::Error;
+
+use super::{
+    slot::{ActiveSaveSlot, SaveSlotId, SaveSlots},
+    snapshot::{create_save_data, decompress_snapshot},
+    version::migrate_save,
+    write_export_file,
+};
+
+/// Exported stories are the same compressed snapshot as a save slot, just written somewhere the player can find it.
+pub const EXPORT_FILE_EXTENSION: &str = "symbiants";
+
+#[derive(Event, Default)]
+pub struct ExportSaveEvent;
+
+/// The outcome of the most recent export or import so the player knows where their file went.
+#[derive(Resource, Default)]
+pub struct SaveTransferStatus(pub Option<String>);
+
+/// Take a fresh snapshot, rather than exporting the last periodic one, so the export matches what the player sees.
+pub fn export_save_file(world: &mut World) {
+    let file_name = world
+        .resource::<ActiveSaveSlot>()
+        .0
+        .and_then(|save_slot_id| world.resource::<SaveSlots>().get(save_slot_id))
+        .map(|save_slot| save_slot.name().to_string())
+        .unwrap_or_default();
+    let file_name = get_export_file_name(&file_name);
+
+    let status = match create_save_data(world) {
+        Some(data) => match write_export_file(&file_name, &data) {
+            Ok(location) => format!("Exported to {}", location),
+            Err(error) => format!("Failed to export: {}", error),
+        },
+        None => "Failed to export: unable to snapshot the world".to_string(),
+    };
+
+    world.resource_mut::<SaveTransferStatus>().0 = Some(status);
+}
+
+/// Save slots can be named anything, but an export's file name mustn't be able to climb out of, or across, directories
+/// or trip up the file system, so anything other than letters, digits, spaces, dashes and underscores is replaced.
+fn get_export_file_name(slot_name: &str) -> String {
+    let name = slot_name
+        .trim()
+        .chars()
+        .map(|c| match c {
+            'A'..='Z' | 'a'..='z' | '0'..='9' | ' ' | '_' | '-' => c,
+            _ => '_',
+        })
+        .collect::<String>();
+
+    let name = if name.is_empty() { "Colony" } else { &name };
+
+    format!("{}.{}", name, EXPORT_FILE_EXTENSION)
+}
+
+/// Store an exported story as a new save slot. The story isn't loaded so it must be continued from the main menu.
+pub(super) fn import_save_data(
+    save_slots: &mut SaveSlots,
+    file_name: &str,
+    data: &[u8],
+) -> Result<SaveSlotId, Error> {
+    // Check the file is a save, from this or an older version, before adding it to the main menu.
+    migrate_save(&decompress_snapshot(data)?)?;
+
+    let name = file_name
+        .strip_suffix(&format!(".{}", EXPORT_FILE_EXTENSION))
+        .unwrap_or(file_name);
+
+    save_slots
+        .import(name, data)
+        .ok_or_else(|| Error::custom("Failed to write imported save"))
+}
+
+pub(super) fn get_import_status(file_name: &str, result: Result<SaveSlotId, Error>) -> String {
+    match result {
+        Ok(_) => format!("Imported {}. Continue it from the main menu.", file_name),
+        Err(error) => format!("Failed to import {}: {}", file_name, error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_file_name_keeps_plain_slot_names() {
+        assert_eq!(
+            get_export_file_name("My Colony_2-b"),
+            "My Colony_2-b.symbiants"
+        );
+    }
+
+    #[test]
+    fn export_file_name_replaces_unsafe_characters() {
+        assert_eq!(
+            get_export_file_name("../../etc/passwd"),
+            "______etc_passwd.symbiants"
+        );
+        assert_eq!(get_export_file_name("C:\\Ants?*"), "C__Ants__.symbiants");
+        assert_eq!(get_export_file_name("  "), "Colony.symbiants");
+    }
+}
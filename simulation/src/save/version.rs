@@ -0,0 +1,1306 @@
+use crate::common::grid::CHUNK_SIZE;
+use bevy::prelude::*;
+use bevy_save::Error;
+use rmpv::Value;
+
+/// Marks the start of a versioned save. Saves written before versioning existed have no header and are treated as version 0.
+pub(super) const SAVE_HEADER: &str = "symbiants-save";
+
+/// Bump this whenever the shape of a persisted component or resource changes and register a migration from the previous version.
+pub const SAVE_VERSION: u32 = 15;
+
+const MIGRATE_ERROR: &str = "Failed to migrate save";
+
+/// A single step which rewrites a snapshot from `from_version` into the shape expected by `from_version + 1`.
+struct Migration {
+    from_version: u32,
+    migrate: fn(&mut Value),
+}
+
+/// Must contain exactly one step per version, in order, so that a save from any version can be upgraded one step at a time.
+const MIGRATIONS: &[Migration] = &[
+    // Version 0 saves have no header, but their snapshot is otherwise identical to version 1.
+    Migration {
+        from_version: 0,
+        migrate: |_| {},
+    },
+    // Version 2 added `Settings::seed`. Older stories weren't seeded so any seed is as good as another.
+    Migration {
+        from_version: 1,
+        migrate: |snapshot| {
+            for_each_reflected_value(
+                snapshot,
+                "simulation::settings::Settings",
+                &mut |settings| {
+                    push_struct_field(settings, "seed", Value::from(0));
+                },
+            );
+        },
+    },
+    // Version 3 stores each zone's elements in an ElementGrid rather than as one entity per tile.
+    Migration {
+        from_version: 2,
+        migrate: migrate_element_entities_to_grids,
+    },
+    // Version 4 persists which chunks of each ElementGrid are dirty instead of tracking UnstableElements.
+    Migration {
+        from_version: 3,
+        migrate: migrate_unstable_elements_to_dirty_chunks,
+    },
+    // Version 5 added `Journal::scenario`. Older stories all began from an empty nest.
+    Migration {
+        from_version: 4,
+        migrate: |snapshot| {
+            for_each_reflected_value(snapshot, JOURNAL_TYPE_PATH, &mut |journal| {
+                push_struct_field(journal, "scenario", Value::Nil);
+            });
+        },
+    },
+    // Version 6 added `Settings::terrain`. Older stories began from a flat nest of dirt.
+    Migration {
+        from_version: 5,
+        migrate: |snapshot| for_each_settings(snapshot, &mut add_flat_terrain),
+    },
+    // Version 7 added rock along the edges of the nest to `Settings::terrain`. Older stories' nests had no rock.
+    Migration {
+        from_version: 6,
+        migrate: |snapshot| for_each_settings(snapshot, &mut add_no_edge_rock),
+    },
+    // Version 8 added `Probabilities::water_soak` and `Settings::max_submerged_time`. Older stories had no water, so
+    // the defaults change nothing.
+    Migration {
+        from_version: 7,
+        migrate: |snapshot| for_each_settings(snapshot, &mut add_default_water_settings),
+    },
+    // Version 9 added `Settings::crater_terrain`. Older stories began from an empty crater.
+    Migration {
+        from_version: 8,
+        migrate: |snapshot| for_each_settings(snapshot, &mut add_empty_crater_terrain),
+    },
+    // Version 10 added food sources to `Settings::crater_terrain`, and `Settings::food_source_growth_time`. Older
+    // stories' craters had no food sources.
+    Migration {
+        from_version: 9,
+        migrate: |snapshot| for_each_settings(snapshot, &mut add_no_food_sources),
+    },
+    // Version 11 added `Settings::food_spoil_time`. Older stories' food never spoiled.
+    Migration {
+        from_version: 10,
+        migrate: |snapshot| {
+            for_each_settings(snapshot, &mut |settings| {
+                push_struct_field(settings, "food_spoil_time", Value::from(0));
+            });
+        },
+    },
+    // Version 12 added `Probabilities::element_tip`. Older stories always gave elements the same chance to tip.
+    Migration {
+        from_version: 11,
+        migrate: |snapshot| for_each_settings(snapshot, &mut add_default_element_tip),
+    },
+    // Version 13 added `StoryMode::chapter_started_tick`. Older stories timed every chapter from the story's start.
+    Migration {
+        from_version: 12,
+        migrate: |snapshot| {
+            for_each_reflected_value(snapshot, STORY_MODE_TYPE_PATH, &mut |story_mode| {
+                push_struct_field(story_mode, "chapter_started_tick", Value::from(0));
+            });
+        },
+    },
+    // Version 14 added when each tile's element was placed to ElementGrid. Older stories' food is aged from when it's
+    // next checked for spoilage.
+    Migration {
+        from_version: 13,
+        migrate: |snapshot| {
+            for_each_reflected_value(snapshot, ELEMENT_GRID_TYPE_PATH, &mut |element_grid| {
+                match element_grid {
+                    Value::Array(fields) => fields.push(Value::Array(vec![])),
+                    _ => error!("Expected an ElementGrid to be written as an array"),
+                }
+            });
+        },
+    },
+    // Version 15 added `FoodSource::is_dormant`. Older stories' food sources withered away, rather than going
+    // dormant, so those left were all still growing.
+    Migration {
+        from_version: 14,
+        migrate: |snapshot| {
+            for_each_reflected_value(snapshot, FOOD_SOURCE_TYPE_PATH, &mut |food_source| {
+                push_struct_field(food_source, "is_dormant", Value::from(false));
+            });
+        },
+    },
+];
+
+const ELEMENT_TYPE_PATH: &str = "simulation::common::element::Element";
+const INVENTORY_ITEM_TYPE_PATH: &str = "simulation::common::ant::InventoryItem";
+const POSITION_TYPE_PATH: &str = "simulation::common::position::Position";
+const ELEMENT_GRID_TYPE_PATH: &str = "simulation::common::grid::ElementGrid";
+const SETTINGS_TYPE_PATH: &str = "simulation::settings::Settings";
+const JOURNAL_TYPE_PATH: &str = "simulation::journal::Journal";
+const STORY_MODE_TYPE_PATH: &str = "simulation::story_mode::StoryMode";
+const FOOD_SOURCE_TYPE_PATH: &str =
+    "simulation::crater_simulation::crater::food_source::FoodSource";
+/// Where `Settings::probabilities`, `Settings::terrain` and `Settings::crater_terrain` are written when Settings'
+/// fields aren't named.
+const SETTINGS_PROBABILITIES_INDEX: usize = 16;
+const SETTINGS_TERRAIN_INDEX: usize = 18;
+const SETTINGS_CRATER_TERRAIN_INDEX: usize = 20;
+const UNSTABLE_ELEMENTS_TYPE_PATH: &str = "simulation::nest_simulation::gravity::UnstableElements";
+const ZONE_TYPE_PATHS: [&str; 2] = [
+    "simulation::nest_simulation::nest::AtNest",
+    "simulation::crater_simulation::crater::AtCrater",
+];
+
+/// Components which only existed to describe element entities. Carried elements keep `Element` but lose these.
+const REMOVED_ELEMENT_TYPE_PATHS: [&str; 6] = [
+    "simulation::common::element::Air",
+    "simulation::common::element::Dirt",
+    "simulation::common::element::Sand",
+    "simulation::common::element::Food",
+    "simulation::nest_simulation::gravity::Stable",
+    "simulation::nest_simulation::gravity::Unstable",
+];
+
+/// A zone's type path, its marker and its element entities, in the order they were found, as (position, element).
+type ZoneElements<'a> = (&'a str, Value, Vec<((i64, i64), Value)>);
+
+/// Replace every element entity, i.e. an Element which isn't being carried, with one ElementGrid entity per zone.
+fn migrate_element_entities_to_grids(value: &mut Value) {
+    let children = match value {
+        Value::Map(entries) => entries
+            .iter_mut()
+            .map(|(_, value)| value)
+            .collect::<Vec<_>>(),
+        Value::Array(items) => items.iter_mut().collect::<Vec<_>>(),
+        _ => return,
+    };
+
+    let is_entity_list = children
+        .iter()
+        .any(|child| get_components(child).is_some_and(|components| is_element_entity(components)));
+
+    if !is_entity_list {
+        for child in children {
+            migrate_element_entities_to_grids(child);
+        }
+
+        return;
+    }
+
+    let mut zone_elements: Vec<ZoneElements> = vec![];
+    let mut removed_entity = None;
+
+    let mut take_element_entity = |entity: &Value| {
+        let Some(components) =
+            get_components(entity).filter(|components| is_element_entity(components))
+        else {
+            return false;
+        };
+
+        let zone = components.iter().find_map(|(key, value)| {
+            ZONE_TYPE_PATHS
+                .into_iter()
+                .find(|&type_path| key.as_str() == Some(type_path))
+                .map(|type_path| (type_path, value.clone()))
+        });
+        let position = get_component(components, POSITION_TYPE_PATH).and_then(get_position);
+        let element = get_component(components, ELEMENT_TYPE_PATH).cloned();
+
+        if let (Some((type_path, marker)), Some(position), Some(element)) =
+            (zone, position, element)
+        {
+            match zone_elements
+                .iter_mut()
+                .find(|(zone, ..)| *zone == type_path)
+            {
+                Some((.., elements)) => elements.push((position, element)),
+                None => zone_elements.push((type_path, marker, vec![(position, element)])),
+            }
+        } else {
+            error!("Discarding element entity without a zone or position");
+        }
+
+        removed_entity.get_or_insert_with(|| entity.clone());
+        true
+    };
+
+    match value {
+        Value::Map(entries) => entries.retain(|(_, entity)| !take_element_entity(entity)),
+        Value::Array(items) => items.retain(|entity| !take_element_entity(entity)),
+        _ => unreachable!(),
+    }
+
+    let Some(removed_entity) = removed_entity else {
+        return;
+    };
+
+    for (zone_type_path, marker, elements) in zone_elements {
+        let width = elements.iter().map(|&((x, _), _)| x + 1).max().unwrap_or(0);
+        let height = elements.iter().map(|&((_, y), _)| y + 1).max().unwrap_or(0);
+
+        let mut grid = vec![Value::from("Air"); (width * height) as usize];
+        for ((x, y), element) in elements {
+            if x >= 0 && y >= 0 {
+                grid[(y * width + x) as usize] = element;
+            }
+        }
+
+        let components = vec![
+            (
+                ELEMENT_GRID_TYPE_PATH.into(),
+                Value::Array(vec![width.into(), height.into(), Value::Array(grid)]),
+            ),
+            (zone_type_path.into(), marker),
+        ];
+
+        // Keep the shape of the entity which was removed so the grid deserializes like any other entity.
+        let mut entity = removed_entity.clone();
+        if let Some(entity_components) = get_components_mut(&mut entity) {
+            *entity_components = components;
+        }
+
+        match value {
+            Value::Map(entries) => {
+                let id = entries
+                    .iter()
+                    .filter_map(|(key, _)| key.as_u64())
+                    .max()
+                    .map_or(0, |id| id + 1);
+
+                entries.push((id.into(), entity));
+            }
+            Value::Array(items) => items.push(entity),
+            _ => unreachable!(),
+        }
+    }
+
+    for type_path in REMOVED_ELEMENT_TYPE_PATHS {
+        remove_reflected_value(value, type_path);
+    }
+}
+
+fn is_element_entity(components: &[(Value, Value)]) -> bool {
+    get_component(components, ELEMENT_TYPE_PATH).is_some()
+        && get_component(components, INVENTORY_ITEM_TYPE_PATH).is_none()
+}
+
+fn get_component<'a>(components: &'a [(Value, Value)], type_path: &str) -> Option<&'a Value> {
+    components
+        .iter()
+        .find(|(key, _)| key.as_str() == Some(type_path))
+        .map(|(_, value)| value)
+}
+
+/// An entity's components are a map keyed by type path. Depending on how the entity was written, that map is
+/// the entity itself, its only field, or its `components` field.
+fn get_components(entity: &Value) -> Option<&Vec<(Value, Value)>> {
+    match entity {
+        Value::Map(entries) => match get_component(entries, "components") {
+            Some(components) => components.as_map(),
+            None => Some(entries),
+        },
+        Value::Array(fields) => match fields.as_slice() {
+            [Value::Map(components)] => Some(components),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn get_components_mut(entity: &mut Value) -> Option<&mut Vec<(Value, Value)>> {
+    match entity {
+        Value::Map(entries) => {
+            let components_field = entries
+                .iter()
+                .position(|(key, _)| key.as_str() == Some("components"));
+
+            match components_field {
+                Some(index) => match &mut entries[index].1 {
+                    Value::Map(components) => Some(components),
+                    _ => None,
+                },
+                None => Some(entries),
+            }
+        }
+        Value::Array(fields) => match fields.as_mut_slice() {
+            [Value::Map(components)] => Some(components),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Positions are written as `[x, y]`, or `{x, y}` when their fields are named.
+fn get_position(position: &Value) -> Option<(i64, i64)> {
+    match position {
+        Value::Array(fields) => Some((fields.first()?.as_i64()?, fields.get(1)?.as_i64()?)),
+        Value::Map(fields) => Some((
+            get_component(fields, "x")?.as_i64()?,
+            get_component(fields, "y")?.as_i64()?,
+        )),
+        _ => None,
+    }
+}
+
+/// Mark every chunk of every ElementGrid as dirty so that gravity settles anything that was unstable when saved.
+fn migrate_unstable_elements_to_dirty_chunks(value: &mut Value) {
+    remove_reflected_value(value, UNSTABLE_ELEMENTS_TYPE_PATH);
+
+    for_each_reflected_value(value, ELEMENT_GRID_TYPE_PATH, &mut |element_grid| {
+        let Value::Array(fields) = element_grid else {
+            error!("Expected an ElementGrid to be written as an array");
+            return;
+        };
+
+        let get_chunk_count = |length: Option<&Value>| {
+            let length = length.and_then(Value::as_u64).unwrap_or(0);
+            length.div_ceil(CHUNK_SIZE as u64)
+        };
+        let chunk_count = get_chunk_count(fields.first()) * get_chunk_count(fields.get(1));
+
+        fields.push(Value::Array((0..chunk_count).map(Value::from).collect()));
+    });
+}
+
+/// Remove every component or resource whose type path is `type_path`, wherever it's nested.
+fn remove_reflected_value(value: &mut Value, type_path: &str) {
+    match value {
+        Value::Map(entries) => {
+            entries.retain(|(key, _)| key.as_str() != Some(type_path));
+
+            for (_, value) in entries.iter_mut() {
+                remove_reflected_value(value, type_path);
+            }
+        }
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                remove_reflected_value(item, type_path);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Call `f` with every serialized Settings. Stories keep their settings, and so do their journals so that replaying
+/// a story generates the same nest. Journals write their settings, as a plain struct, in their first field.
+fn for_each_settings(value: &mut Value, f: &mut impl FnMut(&mut Value)) {
+    for_each_reflected_value(value, SETTINGS_TYPE_PATH, f);
+
+    for_each_reflected_value(
+        value,
+        JOURNAL_TYPE_PATH,
+        &mut |journal| match get_struct_field_mut(journal, "settings", 0) {
+            Some(settings) => f(settings),
+            None => error!("Expected a journal with settings"),
+        },
+    );
+}
+
+fn add_flat_terrain(settings: &mut Value) {
+    let terrain = nested_struct(
+        settings,
+        vec![
+            ("depth_variation", Value::F32(0.0)),
+            ("hill_height", Value::from(0)),
+            ("hill_width", Value::from(1)),
+            ("sand_pocket_count", Value::from(0)),
+            ("sand_pocket_size", Value::from(0)),
+            ("food_cache_count", Value::from(0)),
+            ("food_cache_size", Value::from(0)),
+        ],
+    );
+
+    push_struct_field(settings, "terrain", terrain);
+}
+
+fn add_empty_crater_terrain(settings: &mut Value) {
+    let crater_terrain = nested_struct(
+        settings,
+        vec![
+            ("food_patch_count", Value::from(0)),
+            ("food_patch_size", Value::from(0)),
+            ("obstacle_count", Value::from(0)),
+            ("obstacle_size", Value::from(0)),
+            ("rim_width", Value::from(0)),
+        ],
+    );
+
+    push_struct_field(settings, "crater_terrain", crater_terrain);
+}
+
+fn add_no_food_sources(settings: &mut Value) {
+    match get_struct_field_mut(settings, "crater_terrain", SETTINGS_CRATER_TERRAIN_INDEX) {
+        Some(crater_terrain) => {
+            push_struct_field(crater_terrain, "food_source_count", Value::from(0));
+            push_struct_field(crater_terrain, "food_source_capacity", Value::from(0));
+        }
+        None => error!("Expected settings with crater terrain when adding food sources"),
+    }
+
+    push_struct_field(settings, "food_source_growth_time", Value::from(1_800));
+}
+
+/// Nested structs are written the same way, named or not, as the struct containing them.
+fn nested_struct(parent: &Value, fields: Vec<(&str, Value)>) -> Value {
+    match parent {
+        Value::Map(_) => Value::Map(
+            fields
+                .into_iter()
+                .map(|(name, field)| (name.into(), field))
+                .collect(),
+        ),
+        _ => Value::Array(fields.into_iter().map(|(_, field)| field).collect()),
+    }
+}
+
+/// Read the version of a decompressed save and upgrade its snapshot to the current version.
+/// Returns the snapshot, without its header, ready to be deserialized.
+pub(super) fn migrate_save(data: &[u8]) -> Result<Vec<u8>, Error> {
+    let value = rmpv::decode::read_value(&mut &data[..]).map_err(|e| {
+        error!("{}: {:?}", MIGRATE_ERROR, e);
+        Error::custom(MIGRATE_ERROR)
+    })?;
+
+    let (version, mut snapshot) = split_save_header(value);
+
+    if version > SAVE_VERSION {
+        error!(
+            "Save version {} is newer than supported version {}",
+            version, SAVE_VERSION
+        );
+        return Err(Error::custom(MIGRATE_ERROR));
+    }
+
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|migration| migration.from_version >= version)
+    {
+        (migration.migrate)(&mut snapshot);
+    }
+
+    let mut buffer = Vec::new();
+    rmpv::encode::write_value(&mut buffer, &snapshot).map_err(|e| {
+        error!("{}: {:?}", MIGRATE_ERROR, e);
+        Error::custom(MIGRATE_ERROR)
+    })?;
+
+    Ok(buffer)
+}
+
+/// Versioned saves are written as `[SAVE_HEADER, version, snapshot]`. Anything else is an unversioned snapshot.
+fn split_save_header(value: Value) -> (u32, Value) {
+    if let Value::Array(mut items) = value {
+        let version = match items.as_slice() {
+            [Value::String(header), Value::Integer(version), _]
+                if header.as_str() == Some(SAVE_HEADER) =>
+            {
+                version.as_u64().map(|version| version as u32)
+            }
+            _ => None,
+        };
+
+        return match version {
+            Some(version) => (version, items.remove(2)),
+            None => (0, Value::Array(items)),
+        };
+    }
+
+    (0, value)
+}
+
+/// Call `f` with the serialized value of every component or resource whose type path is `type_path`.
+/// Reflected values are keyed by type path, so this finds them regardless of where they're nested within the snapshot.
+fn for_each_reflected_value(value: &mut Value, type_path: &str, f: &mut impl FnMut(&mut Value)) {
+    match value {
+        Value::Map(entries) => {
+            for (key, value) in entries.iter_mut() {
+                if key.as_str() == Some(type_path) {
+                    f(value);
+                } else {
+                    for_each_reflected_value(value, type_path, f);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items.iter_mut() {
+                for_each_reflected_value(item, type_path, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn add_no_edge_rock(settings: &mut Value) {
+    match get_struct_field_mut(settings, "terrain", SETTINGS_TERRAIN_INDEX) {
+        Some(terrain) => {
+            push_struct_field(terrain, "edge_rock_width", Value::F32(0.0));
+            push_struct_field(terrain, "edge_rock_depth", Value::from(0));
+        }
+        None => error!("Expected settings with terrain when adding edge rock"),
+    }
+}
+
+fn add_default_water_settings(settings: &mut Value) {
+    match get_struct_field_mut(settings, "probabilities", SETTINGS_PROBABILITIES_INDEX) {
+        Some(probabilities) => push_struct_field(probabilities, "water_soak", Value::F32(0.002)),
+        None => error!("Expected settings with probabilities when adding water_soak"),
+    }
+
+    push_struct_field(settings, "max_submerged_time", Value::from(60));
+}
+
+fn add_default_element_tip(settings: &mut Value) {
+    match get_struct_field_mut(settings, "probabilities", SETTINGS_PROBABILITIES_INDEX) {
+        Some(probabilities) => push_struct_field(probabilities, "element_tip", Value::F32(0.66)),
+        None => error!("Expected settings with probabilities when adding element_tip"),
+    }
+}
+
+/// Find a field of a serialized struct by its name or, when its fields aren't named, by its index.
+fn get_struct_field_mut<'a>(
+    value: &'a mut Value,
+    name: &str,
+    index: usize,
+) -> Option<&'a mut Value> {
+    match value {
+        Value::Array(fields) => fields.get_mut(index),
+        Value::Map(fields) => fields
+            .iter_mut()
+            .find(|(field_name, _)| field_name.as_str() == Some(name))
+            .map(|(_, field)| field),
+        _ => None,
+    }
+}
+
+/// Add a field to the end of a serialized struct. MessagePack writes structs as arrays, in field order, unless their fields are named.
+fn push_struct_field(value: &mut Value, name: &str, field: Value) {
+    match value {
+        Value::Array(fields) => fields.push(field),
+        Value::Map(fields) => fields.push((name.into(), field)),
+        _ => error!("Expected a struct when adding field {}", name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::ant::Ant,
+        headless::{begin_story, tick, HeadlessPlugin},
+        save::{
+            load_save_file_at,
+            snapshot::{compress_snapshot, create_save_snapshot},
+        },
+        story_time::StoryTime,
+    };
+    use std::path::{Path, PathBuf};
+
+    fn encode(value: &Value) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        rmpv::encode::write_value(&mut buffer, value).unwrap();
+        buffer
+    }
+
+    fn versioned(version: u32, snapshot: Value) -> Value {
+        Value::Array(vec![SAVE_HEADER.into(), version.into(), snapshot])
+    }
+
+    fn temp_save_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("symbiants-test-{}", std::process::id()))
+            .join(name)
+    }
+
+    fn write_save_file_at(world: &mut World, path: &Path) -> bool {
+        let Some(snapshot) = create_save_snapshot(world) else {
+            return false;
+        };
+
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, compress_snapshot(&snapshot)).is_ok()
+    }
+
+    fn story_app(ticks: usize) -> App {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        begin_story(&mut app);
+
+        for _ in 0..ticks {
+            tick(&mut app);
+        }
+
+        app
+    }
+
+    /// An app which has registered its types, but has not yet loaded or created a story.
+    fn empty_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        app.finish();
+        app.cleanup();
+        app.world.run_schedule(Startup);
+        app
+    }
+
+    fn count_ants(world: &mut World) -> usize {
+        world.query_filtered::<(), With<Ant>>().iter(world).count()
+    }
+
+    fn assert_loaded_matches(mut app: App, path: &Path) {
+        let mut loaded_app = empty_app();
+        assert!(load_save_file_at(&mut loaded_app.world, path).is_ok());
+
+        assert_eq!(
+            loaded_app.world.resource::<StoryTime>().elapsed_ticks(),
+            app.world.resource::<StoryTime>().elapsed_ticks()
+        );
+        assert_eq!(
+            count_ants(&mut loaded_app.world),
+            count_ants(&mut app.world)
+        );
+    }
+
+    #[test]
+    fn unversioned_save_is_version_zero() {
+        let snapshot = Value::Array(vec![Value::Map(vec![]), Value::Map(vec![])]);
+
+        assert_eq!(split_save_header(snapshot.clone()), (0, snapshot));
+    }
+
+    #[test]
+    fn versioned_save_reads_header() {
+        let snapshot = Value::Map(vec![("key".into(), 1.into())]);
+
+        assert_eq!(
+            split_save_header(versioned(SAVE_VERSION, snapshot.clone())),
+            (SAVE_VERSION, snapshot)
+        );
+    }
+
+    #[test]
+    fn newer_save_is_rejected() {
+        let save = versioned(SAVE_VERSION + 1, Value::Map(vec![]));
+
+        assert!(migrate_save(&encode(&save)).is_err());
+    }
+
+    #[test]
+    fn current_save_is_unchanged() {
+        let snapshot = Value::Map(vec![("key".into(), 1.into())]);
+        let save = versioned(SAVE_VERSION, snapshot.clone());
+
+        assert_eq!(migrate_save(&encode(&save)).unwrap(), encode(&snapshot));
+    }
+
+    #[test]
+    fn migrations_cover_every_version() {
+        assert_eq!(MIGRATIONS.len(), SAVE_VERSION as usize);
+
+        for (version, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.from_version, version as u32);
+        }
+    }
+
+    #[test]
+    fn reflected_values_are_found_when_nested() {
+        let type_path = "simulation::common::ant::hunger::Hunger";
+        let mut snapshot = Value::Map(vec![(
+            "entities".into(),
+            Value::Map(vec![
+                (
+                    0.into(),
+                    Value::Map(vec![(type_path.into(), Value::Array(vec![1.into()]))]),
+                ),
+                (
+                    1.into(),
+                    Value::Map(vec![(type_path.into(), Value::Array(vec![2.into()]))]),
+                ),
+            ]),
+        )]);
+
+        let mut found = vec![];
+        for_each_reflected_value(&mut snapshot, type_path, &mut |value| {
+            found.push(value.clone());
+            *value = Value::Nil;
+        });
+
+        assert_eq!(
+            found,
+            vec![Value::Array(vec![1.into()]), Value::Array(vec![2.into()])]
+        );
+
+        for_each_reflected_value(&mut snapshot, type_path, &mut |value| {
+            assert_eq!(*value, Value::Nil);
+        });
+    }
+
+    #[test]
+    fn loads_current_save() {
+        let mut app = story_app(100);
+        let path = temp_save_path("current");
+
+        assert!(write_save_file_at(&mut app.world, &path));
+
+        assert_loaded_matches(app, &path);
+    }
+
+    /// Settings fields which migrations leave untouched, other than adding to probabilities. Fields aren't named, so
+    /// only how many there are matters.
+    fn untouched_settings(field_count: usize) -> Vec<Value> {
+        (0..field_count)
+            .map(|index| match index {
+                SETTINGS_PROBABILITIES_INDEX => Value::Array(vec![]),
+                _ => Value::from(index),
+            })
+            .collect()
+    }
+
+    /// Later migrations give old settings the default water settings and an empty crater without food sources.
+    fn with_later_settings(mut settings: Vec<Value>) -> Vec<Value> {
+        if let Value::Array(probabilities) = &mut settings[SETTINGS_PROBABILITIES_INDEX] {
+            probabilities.push(Value::F32(0.002));
+        }
+        settings.push(60.into());
+        settings.extend(without_food_sources(empty_crater_terrain()));
+
+        with_default_element_tip(settings)
+    }
+
+    /// The last migration gives old probabilities the default chance for elements to tip over a ledge.
+    fn with_default_element_tip(mut settings: Vec<Value>) -> Vec<Value> {
+        if let Value::Array(probabilities) = &mut settings[SETTINGS_PROBABILITIES_INDEX] {
+            probabilities.push(Value::F32(0.66));
+        }
+
+        settings
+    }
+
+    fn empty_crater_terrain() -> Vec<Value> {
+        vec![0.into(); 5]
+    }
+
+    /// Crater terrain once food sources are added, followed by the food source growth time and food which never
+    /// spoils.
+    fn without_food_sources(crater_terrain: Vec<Value>) -> Vec<Value> {
+        vec![
+            Value::Array([crater_terrain, vec![0.into(), 0.into()]].concat()),
+            1_800.into(),
+            0.into(),
+        ]
+    }
+
+    /// Later migrations give old settings flat terrain without any rock.
+    fn flat_terrain() -> Value {
+        Value::Array(vec![
+            Value::F32(0.0),
+            0.into(),
+            1.into(),
+            0.into(),
+            0.into(),
+            0.into(),
+            0.into(),
+            Value::F32(0.0),
+            0.into(),
+        ])
+    }
+
+    fn settings_snapshot(settings: Vec<Value>) -> Value {
+        Value::Map(vec![(
+            "resources".into(),
+            Value::Map(vec![
+                (SETTINGS_TYPE_PATH.into(), Value::Array(settings.clone())),
+                (
+                    JOURNAL_TYPE_PATH.into(),
+                    Value::Array(vec![Value::Array(settings), Value::Array(vec![])]),
+                ),
+            ]),
+        )])
+    }
+
+    #[test]
+    fn settings_gain_a_seed() {
+        let settings_type_path = "simulation::settings::Settings";
+        let snapshot = |settings: Vec<Value>| {
+            Value::Map(vec![(
+                "resources".into(),
+                Value::Map(vec![(settings_type_path.into(), Value::Array(settings))]),
+            )])
+        };
+        let settings = untouched_settings(SETTINGS_TERRAIN_INDEX - 1);
+
+        let save = versioned(1, snapshot(settings.clone()));
+
+        assert_eq!(
+            migrate_save(&encode(&save)).unwrap(),
+            encode(&snapshot(with_later_settings(
+                [settings, vec![0.into(), flat_terrain()]].concat()
+            )))
+        );
+    }
+
+    #[test]
+    fn journals_gain_a_scenario() {
+        let journal_type_path = "simulation::journal::Journal";
+        let snapshot = |journal: Vec<Value>| {
+            Value::Map(vec![(
+                "resources".into(),
+                Value::Map(vec![(journal_type_path.into(), Value::Array(journal))]),
+            )])
+        };
+        let settings = untouched_settings(SETTINGS_TERRAIN_INDEX);
+
+        let save = versioned(
+            4,
+            snapshot(vec![Value::Array(settings.clone()), Value::Array(vec![])]),
+        );
+
+        assert_eq!(
+            migrate_save(&encode(&save)).unwrap(),
+            encode(&snapshot(vec![
+                Value::Array(with_later_settings(
+                    [settings, vec![flat_terrain()]].concat()
+                )),
+                Value::Array(vec![]),
+                Value::Nil
+            ]))
+        );
+    }
+
+    #[test]
+    fn settings_gain_flat_terrain() {
+        let settings = untouched_settings(SETTINGS_TERRAIN_INDEX);
+
+        let save = versioned(5, settings_snapshot(settings.clone()));
+
+        assert_eq!(
+            migrate_save(&encode(&save)).unwrap(),
+            encode(&settings_snapshot(with_later_settings(
+                [settings, vec![flat_terrain()]].concat()
+            )))
+        );
+    }
+
+    #[test]
+    fn terrain_gains_no_edge_rock() {
+        let terrain = vec![Value::F32(0.1), 6.into(), 24.into(), 6.into(), 4.into()];
+        let settings = untouched_settings(SETTINGS_TERRAIN_INDEX);
+
+        let save = versioned(
+            6,
+            settings_snapshot([settings.clone(), vec![Value::Array(terrain.clone())]].concat()),
+        );
+
+        assert_eq!(
+            migrate_save(&encode(&save)).unwrap(),
+            encode(&settings_snapshot(with_later_settings(
+                [
+                    settings,
+                    vec![Value::Array(
+                        [terrain, vec![Value::F32(0.0), 0.into()]].concat()
+                    )]
+                ]
+                .concat()
+            )))
+        );
+    }
+
+    #[test]
+    fn settings_gain_water_settings() {
+        let settings = untouched_settings(SETTINGS_TERRAIN_INDEX + 1);
+
+        let save = versioned(7, settings_snapshot(settings.clone()));
+
+        let mut migrated_settings = settings;
+        migrated_settings[SETTINGS_PROBABILITIES_INDEX] =
+            Value::Array(vec![Value::F32(0.002), Value::F32(0.66)]);
+        migrated_settings.push(60.into());
+        migrated_settings.extend(without_food_sources(empty_crater_terrain()));
+
+        assert_eq!(
+            migrate_save(&encode(&save)).unwrap(),
+            encode(&settings_snapshot(migrated_settings))
+        );
+    }
+
+    #[test]
+    fn settings_gain_empty_crater_terrain() {
+        let settings = untouched_settings(SETTINGS_TERRAIN_INDEX + 2);
+
+        let save = versioned(8, settings_snapshot(settings.clone()));
+
+        assert_eq!(
+            migrate_save(&encode(&save)).unwrap(),
+            encode(&settings_snapshot(with_default_element_tip(
+                [settings, without_food_sources(empty_crater_terrain())].concat()
+            )))
+        );
+    }
+
+    #[test]
+    fn crater_terrain_gains_no_food_sources() {
+        let crater_terrain = vec![6.into(), 3.into(), 10.into(), 4.into(), 2.into()];
+        let settings = untouched_settings(SETTINGS_CRATER_TERRAIN_INDEX);
+
+        let save = versioned(
+            9,
+            settings_snapshot(
+                [settings.clone(), vec![Value::Array(crater_terrain.clone())]].concat(),
+            ),
+        );
+
+        assert_eq!(
+            migrate_save(&encode(&save)).unwrap(),
+            encode(&settings_snapshot(with_default_element_tip(
+                [settings, without_food_sources(crater_terrain)].concat()
+            )))
+        );
+    }
+
+    #[test]
+    fn settings_gain_unspoiled_food() {
+        let settings = untouched_settings(SETTINGS_CRATER_TERRAIN_INDEX + 2);
+
+        let save = versioned(10, settings_snapshot(settings.clone()));
+
+        assert_eq!(
+            migrate_save(&encode(&save)).unwrap(),
+            encode(&settings_snapshot(with_default_element_tip(
+                [settings, vec![0.into()]].concat()
+            )))
+        );
+    }
+
+    #[test]
+    fn probabilities_gain_default_element_tip() {
+        let settings = untouched_settings(SETTINGS_CRATER_TERRAIN_INDEX + 3);
+
+        let save = versioned(11, settings_snapshot(settings.clone()));
+
+        assert_eq!(
+            migrate_save(&encode(&save)).unwrap(),
+            encode(&settings_snapshot(with_default_element_tip(settings)))
+        );
+    }
+
+    #[test]
+    fn story_mode_gains_chapter_start() {
+        let snapshot = |story_mode: Vec<Value>| {
+            Value::Map(vec![(
+                "resources".into(),
+                Value::Map(vec![(
+                    STORY_MODE_TYPE_PATH.into(),
+                    Value::Array(story_mode),
+                )]),
+            )])
+        };
+        let story_mode = vec![
+            3.into(),
+            Value::Array(vec![false.into()]),
+            Value::Array(vec![]),
+        ];
+
+        let save = versioned(12, snapshot(story_mode.clone()));
+
+        assert_eq!(
+            migrate_save(&encode(&save)).unwrap(),
+            encode(&snapshot([story_mode, vec![0.into()]].concat()))
+        );
+    }
+
+    #[test]
+    fn element_grids_gain_placed_ticks() {
+        let snapshot = |element_grid: Vec<Value>| {
+            Value::Map(vec![(
+                "entities".into(),
+                Value::Map(vec![(
+                    0.into(),
+                    Value::Array(vec![Value::Map(vec![(
+                        ELEMENT_GRID_TYPE_PATH.into(),
+                        Value::Array(element_grid),
+                    )])]),
+                )]),
+            )])
+        };
+        let element_grid = vec![
+            1.into(),
+            1.into(),
+            Value::Array(vec!["Food".into()]),
+            Value::Array(vec![]),
+        ];
+
+        let save = versioned(13, snapshot(element_grid.clone()));
+
+        assert_eq!(
+            migrate_save(&encode(&save)).unwrap(),
+            encode(&snapshot(
+                [element_grid, vec![Value::Array(vec![])]].concat()
+            ))
+        );
+    }
+
+    #[test]
+    fn food_sources_gain_dormancy() {
+        let snapshot = |food_source: Vec<Value>| {
+            Value::Map(vec![(
+                "entities".into(),
+                Value::Map(vec![(
+                    0.into(),
+                    Value::Array(vec![Value::Map(vec![(
+                        FOOD_SOURCE_TYPE_PATH.into(),
+                        Value::Array(food_source),
+                    )])]),
+                )]),
+            )])
+        };
+        let food_source = vec!["Bush".into(), 100.into(), 0.into()];
+
+        let save = versioned(14, snapshot(food_source.clone()));
+
+        assert_eq!(
+            migrate_save(&encode(&save)).unwrap(),
+            encode(&snapshot([food_source, vec![false.into()]].concat()))
+        );
+    }
+
+    #[test]
+    fn element_entities_become_grids() {
+        let nest = "simulation::nest_simulation::nest::AtNest";
+        let entity = |components: Vec<(&str, Value)>| {
+            Value::Array(vec![Value::Map(
+                components
+                    .into_iter()
+                    .map(|(type_path, value)| (type_path.into(), value))
+                    .collect(),
+            )])
+        };
+        let element = |x: i64, y: i64, element: &str, marker: &str| {
+            entity(vec![
+                (ELEMENT_TYPE_PATH, element.into()),
+                (POSITION_TYPE_PATH, Value::Array(vec![x.into(), y.into()])),
+                (nest, Value::Array(vec![])),
+                (marker, Value::Array(vec![])),
+            ])
+        };
+        let snapshot = |entities: Vec<(u64, Value)>| {
+            Value::Array(vec![
+                Value::Map(vec![]),
+                Value::Map(
+                    entities
+                        .into_iter()
+                        .map(|(id, entity)| (id.into(), entity))
+                        .collect(),
+                ),
+            ])
+        };
+
+        let carried = entity(vec![
+            (ELEMENT_TYPE_PATH, "Sand".into()),
+            (INVENTORY_ITEM_TYPE_PATH, Value::Array(vec![])),
+            (nest, Value::Array(vec![])),
+            ("simulation::common::element::Sand", Value::Array(vec![])),
+        ]);
+
+        let save = versioned(
+            2,
+            snapshot(vec![
+                (
+                    0,
+                    element(1, 0, "Dirt", "simulation::common::element::Dirt"),
+                ),
+                (1, element(0, 0, "Air", "simulation::common::element::Air")),
+                (
+                    2,
+                    element(0, 1, "Food", "simulation::common::element::Food"),
+                ),
+                (
+                    3,
+                    element(
+                        1,
+                        1,
+                        "Sand",
+                        "simulation::nest_simulation::gravity::Unstable",
+                    ),
+                ),
+                (4, carried),
+            ]),
+        );
+
+        let expected = snapshot(vec![
+            (
+                4,
+                entity(vec![
+                    (ELEMENT_TYPE_PATH, "Sand".into()),
+                    (INVENTORY_ITEM_TYPE_PATH, Value::Array(vec![])),
+                    (nest, Value::Array(vec![])),
+                ]),
+            ),
+            (
+                5,
+                entity(vec![
+                    (
+                        ELEMENT_GRID_TYPE_PATH,
+                        Value::Array(vec![
+                            2.into(),
+                            2.into(),
+                            Value::Array(vec![
+                                "Air".into(),
+                                "Dirt".into(),
+                                "Food".into(),
+                                "Sand".into(),
+                            ]),
+                            Value::Array(vec![0.into()]),
+                            Value::Array(vec![]),
+                        ]),
+                    ),
+                    (nest, Value::Array(vec![])),
+                ]),
+            ),
+        ]);
+
+        assert_eq!(migrate_save(&encode(&save)).unwrap(), encode(&expected));
+    }
+
+    #[test]
+    fn unstable_elements_become_dirty_chunks() {
+        let snapshot = |components: Vec<(&str, Value)>| {
+            Value::Array(vec![
+                Value::Map(vec![]),
+                Value::Map(vec![(
+                    0.into(),
+                    Value::Array(vec![Value::Map(
+                        components
+                            .into_iter()
+                            .map(|(type_path, value)| (type_path.into(), value))
+                            .collect(),
+                    )]),
+                )]),
+            ])
+        };
+        let elements = Value::Array(vec!["Air".into(); 20 * 17]);
+
+        let save = versioned(
+            3,
+            snapshot(vec![
+                (
+                    ELEMENT_GRID_TYPE_PATH,
+                    Value::Array(vec![20.into(), 17.into(), elements.clone()]),
+                ),
+                (
+                    UNSTABLE_ELEMENTS_TYPE_PATH,
+                    Value::Array(vec![Value::Array(vec![Value::Array(vec![
+                        1.into(),
+                        2.into(),
+                    ])])]),
+                ),
+            ]),
+        );
+
+        let expected = snapshot(vec![(
+            ELEMENT_GRID_TYPE_PATH,
+            Value::Array(vec![
+                20.into(),
+                17.into(),
+                elements,
+                Value::Array(vec![0.into(), 1.into(), 2.into(), 3.into()]),
+                Value::Array(vec![]),
+            ]),
+        )]);
+
+        assert_eq!(migrate_save(&encode(&save)).unwrap(), encode(&expected));
+    }
+
+    /// Saves written before versioning existed are a compressed snapshot without a header.
+    #[test]
+    fn loads_unversioned_save() {
+        let mut app = story_app(100);
+        let path = temp_save_path("unversioned");
+
+        let data = create_save_snapshot(&mut app.world).unwrap();
+        let (_, mut snapshot) =
+            split_save_header(rmpv::decode::read_value(&mut &data[..]).unwrap());
+
+        // Version 0 predates the water, crater terrain, food source and spoilage settings, which are the last of
+        // Settings, and the water and tipping probabilities, which are the last of its probabilities.
+        for_each_settings(&mut snapshot, &mut |settings| {
+            if let Value::Array(fields) = settings {
+                fields.truncate(fields.len() - 4);
+
+                if let Some(Value::Array(probabilities)) =
+                    fields.get_mut(SETTINGS_PROBABILITIES_INDEX)
+                {
+                    probabilities.truncate(probabilities.len() - 2);
+                }
+            }
+        });
+
+        // Nor did it have `Settings::seed` or `Settings::terrain`, which were then the last fields.
+        for_each_reflected_value(&mut snapshot, SETTINGS_TYPE_PATH, &mut |settings| {
+            if let Value::Array(fields) = settings {
+                fields.truncate(fields.len() - 2);
+            }
+        });
+
+        // Nor did it persist the dirty chunks of an ElementGrid, or when its elements were placed, which are its last
+        // fields.
+        for_each_reflected_value(&mut snapshot, ELEMENT_GRID_TYPE_PATH, &mut |element_grid| {
+            if let Value::Array(fields) = element_grid {
+                fields.truncate(fields.len() - 2);
+            }
+        });
+
+        // Nor could food sources go dormant, which is their last field.
+        for_each_reflected_value(&mut snapshot, FOOD_SOURCE_TYPE_PATH, &mut |food_source| {
+            if let Value::Array(fields) = food_source {
+                fields.pop();
+            }
+        });
+
+        // Nor did journals know which scenario a story began from, or keep the terrain settings it began with.
+        for_each_reflected_value(&mut snapshot, JOURNAL_TYPE_PATH, &mut |journal| {
+            if let Value::Array(fields) = journal {
+                fields.pop();
+
+                if let Some(Value::Array(settings_fields)) = fields.first_mut() {
+                    settings_fields.pop();
+                }
+            }
+        });
+
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, compress_snapshot(&encode(&snapshot))).unwrap();
+
+        assert_loaded_matches(app, &path);
+    }
+
+    /// Saves kept from every version, named `v{version}.sav`, ensure old saves keep loading as the game changes.
+    /// Generate one with `cargo test -p simulation -- --ignored generate_fixture_save` after bumping SAVE_VERSION.
+    #[test]
+    fn loads_fixture_saves() {
+        let fixture_directory = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+        for version in 1..=SAVE_VERSION {
+            let path = fixture_directory.join(format!("v{}.sav", version));
+            assert!(path.is_file(), "Missing fixture {:?}", path);
+
+            let mut loaded_app = empty_app();
+
+            assert!(
+                load_save_file_at(&mut loaded_app.world, &path).is_ok(),
+                "Failed to load fixture {:?}",
+                path
+            );
+            assert!(count_ants(&mut loaded_app.world) > 0);
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn generate_fixture_save() {
+        let mut app = story_app(1_000);
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(format!("v{}.sav", SAVE_VERSION));
+
+        assert!(write_save_file_at(&mut app.world, &path));
+    }
+}
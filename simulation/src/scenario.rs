@@ -0,0 +1,501 @@
+use crate::{
+    common::{
+        ant::{
+            digestion::Digestion, hunger::Hunger, Angle, AntBundle, AntColor, AntInventory,
+            AntName, AntOrientation, AntRole, Facing, Initiative,
+        },
+        element::Element,
+        grid::ElementGrid,
+        pheromone::{Pheromone, PheromoneDuration, PheromoneStrength},
+        position::Position,
+        Zone,
+    },
+    nest_simulation::{
+        ant::{birthing::Birthing, nesting::Nested},
+        nest::Nest,
+    },
+    settings::{Settings, Terrain},
+};
+use bevy::prelude::*;
+use bevy_turborand::GlobalRng;
+use ron::extensions::Extensions;
+use serde::{Deserialize, Serialize};
+
+/// Scenarios which come with the game. Players can add their own to their scenarios directory.
+const BUILT_IN_SCENARIOS: [(&str, &str); 1] = [(
+    "established_colony.ron",
+    include_str!("../scenarios/established_colony.ron"),
+)];
+
+/// A custom starting world, written in RON, which a new story begins from instead of an empty nest.
+/// See `scenarios/established_colony.ron` for an example.
+///
+/// The scenario is journaled along with the story's settings so that replaying the story begins from the same world.
+#[derive(Resource, Reflect, Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Tiles below the surface level of the nest are underground.
+    pub surface_level: isize,
+    pub nest: ZoneScenario,
+    pub crater: ZoneScenario,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct ZoneScenario {
+    pub width: isize,
+    pub height: isize,
+    /// Rows of elements, top to bottom, one character per element: '.' air, '#' dirt, ':' sand, '*' food, '@' rock,
+    /// '~' water and '%' waste.
+    /// Tiles which are left out are dirt below the nest's surface and air everywhere else.
+    #[serde(default)]
+    pub layout: Vec<String>,
+    #[serde(default)]
+    pub pheromones: Vec<PheromoneScenario>,
+    #[serde(default)]
+    pub ants: Vec<AntScenario>,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct PheromoneScenario {
+    pub position: Position,
+    pub pheromone: Pheromone,
+    /// Limited to what the pheromone could have when laid by an ant, e.g. `tunnel_length` for Tunnel.
+    pub strength: isize,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct AntScenario {
+    pub role: AntRole,
+    /// Queens are named Queen, and workers are given a random name, unless named here.
+    #[serde(default)]
+    pub name: Option<String>,
+    pub position: Position,
+    /// Ants face a random direction unless told otherwise.
+    #[serde(default)]
+    pub facing: Option<Facing>,
+    #[serde(default)]
+    pub angle: Angle,
+    /// Percent, from 0 to 100.
+    #[serde(default)]
+    pub hunger: f32,
+    /// A nested queen has already founded her nest and gives birth rather than looking for a place to dig.
+    #[serde(default)]
+    pub nested: bool,
+}
+
+fn parse_element(symbol: char) -> Option<Element> {
+    match symbol {
+        '.' => Some(Element::Air),
+        '#' => Some(Element::Dirt),
+        ':' => Some(Element::Sand),
+        '*' => Some(Element::Food),
+        '@' => Some(Element::Rock),
+        '~' => Some(Element::Water),
+        '%' => Some(Element::Waste),
+        _ => None,
+    }
+}
+
+impl Scenario {
+    /// Parse, and validate, a scenario written in RON. Optional values, like an ant's name, don't need `Some(...)`.
+    pub fn from_ron(ron: &str) -> Result<Scenario, String> {
+        let scenario = ron::Options::default()
+            .with_default_extension(Extensions::IMPLICIT_SOME)
+            .from_str::<Scenario>(ron)
+            .map_err(|error| format!("Failed to parse scenario: {}", error))?;
+
+        scenario.validate()?;
+
+        Ok(scenario)
+    }
+
+    /// Describe every problem with the scenario, rather than just the first, so they can all be fixed at once.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut problems = vec![];
+
+        if !(0..self.nest.height).contains(&self.surface_level) {
+            problems.push(format!(
+                "surface_level must be within the nest, but is {}",
+                self.surface_level
+            ));
+        }
+
+        let nest_problems = self.nest.validate(self.get_default_nest_element());
+        let crater_problems = self.crater.validate(get_default_crater_element);
+
+        for (zone_name, zone_problems) in [("nest", nest_problems), ("crater", crater_problems)] {
+            for problem in zone_problems {
+                problems.push(format!("{}: {}", zone_name, problem));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems.join("\n"))
+        }
+    }
+
+    /// Dirt below the surface and air above it, like the nest of a story which doesn't begin from a scenario.
+    pub fn get_default_nest_element(&self) -> impl Fn(Position) -> Element {
+        let nest = Nest::new(self.surface_level);
+
+        move |position| {
+            if nest.is_underground(&position) {
+                Element::Dirt
+            } else {
+                Element::Air
+            }
+        }
+    }
+
+    /// Zone sizes come from Settings everywhere else so the scenario decides them. The scenario lays out the nest,
+    /// rather than it being generated from terrain, so its edges have no rock.
+    pub fn apply_to(&self, settings: &mut Settings) {
+        settings.nest_width = self.nest.width;
+        settings.nest_height = self.nest.height;
+        settings.crater_width = self.crater.width;
+        settings.crater_height = self.crater.height;
+        settings.terrain = Terrain::flat();
+    }
+}
+
+pub fn get_default_crater_element(_position: Position) -> Element {
+    Element::Air
+}
+
+impl ZoneScenario {
+    fn validate(&self, get_default_element: impl Fn(Position) -> Element) -> Vec<String> {
+        let mut problems = vec![];
+
+        if self.width <= 0 || self.height <= 0 {
+            problems.push(format!(
+                "width and height must be greater than 0, but are {} and {}",
+                self.width, self.height
+            ));
+        }
+
+        if self.layout.len() > self.height as usize {
+            problems.push(format!(
+                "layout has {} rows, but the zone is only {} tall",
+                self.layout.len(),
+                self.height
+            ));
+        }
+
+        for (y, row) in self.layout.iter().enumerate() {
+            if row.chars().count() > self.width as usize {
+                problems.push(format!(
+                    "layout row {} is longer than the zone is wide ({})",
+                    y, self.width
+                ));
+            }
+
+            if let Some(symbol) = row.chars().find(|&symbol| parse_element(symbol).is_none()) {
+                problems.push(format!(
+                    "layout row {} has an unknown element '{}'",
+                    y, symbol
+                ));
+            }
+        }
+
+        let is_within_bounds = |position: &Position| {
+            (0..self.width).contains(&position.x) && (0..self.height).contains(&position.y)
+        };
+
+        for pheromone in self.pheromones.iter() {
+            if !is_within_bounds(&pheromone.position) {
+                problems.push(format!(
+                    "{:?} pheromone at {:?} is outside of the zone",
+                    pheromone.pheromone, pheromone.position
+                ));
+            }
+
+            if pheromone.strength <= 0 {
+                problems.push(format!(
+                    "{:?} pheromone at {:?} must have a strength greater than 0",
+                    pheromone.pheromone, pheromone.position
+                ));
+            }
+        }
+
+        for ant in self.ants.iter() {
+            if !is_within_bounds(&ant.position) {
+                problems.push(format!(
+                    "{:?} at {:?} is outside of the zone",
+                    ant.role, ant.position
+                ));
+            } else if self.get_element(ant.position, &get_default_element) != Element::Air {
+                problems.push(format!(
+                    "{:?} at {:?} must stand in air, but is buried",
+                    ant.role, ant.position
+                ));
+            }
+
+            if !(0.0..=100.0).contains(&ant.hunger) {
+                problems.push(format!(
+                    "{:?} at {:?} must have hunger between 0 and 100, but has {}",
+                    ant.role, ant.position, ant.hunger
+                ));
+            }
+        }
+
+        problems
+    }
+
+    fn get_element(
+        &self,
+        position: Position,
+        get_default_element: &impl Fn(Position) -> Element,
+    ) -> Element {
+        self.layout
+            .get(position.y as usize)
+            .and_then(|row| row.chars().nth(position.x as usize))
+            .and_then(parse_element)
+            .unwrap_or_else(|| get_default_element(position))
+    }
+
+    /// Tiles which are left out of the layout are given by `get_default_element`.
+    pub fn to_element_grid(
+        &self,
+        get_default_element: impl Fn(Position) -> Element,
+    ) -> ElementGrid {
+        ElementGrid::from_fn(self.width, self.height, |position| {
+            self.get_element(position, &get_default_element)
+        })
+    }
+
+    pub fn spawn_pheromones<Z: Zone + Copy>(
+        &self,
+        zone: Z,
+        settings: &Settings,
+        commands: &mut Commands,
+    ) {
+        for pheromone in self.pheromones.iter() {
+            let max_strength = match pheromone.pheromone {
+                Pheromone::Tunnel => settings.tunnel_length,
+                Pheromone::Chamber => settings.chamber_size,
+                Pheromone::Food | Pheromone::Nest => 100,
+            };
+
+            commands.spawn((
+                pheromone.position,
+                pheromone.pheromone,
+                PheromoneDuration::default(),
+                PheromoneStrength::new(pheromone.strength.min(max_strength), max_strength),
+                zone,
+            ));
+        }
+    }
+
+    pub fn spawn_ants<Z: Zone + Copy>(
+        &self,
+        zone: Z,
+        settings: &Settings,
+        rng: &mut Mut<GlobalRng>,
+        commands: &mut Commands,
+    ) {
+        for ant in self.ants.iter() {
+            let facing = ant.facing.unwrap_or_else(|| Facing::random(rng));
+            let name = match (&ant.name, ant.role) {
+                (Some(name), _) => AntName(name.clone()),
+                (None, AntRole::Queen) => AntName(String::from("Queen")),
+                (None, AntRole::Worker) => AntName::random(rng),
+            };
+
+            let mut hunger = Hunger::new(settings.max_hunger_time);
+            hunger.set_value(ant.hunger);
+
+            let mut ant_entity_commands = commands.spawn(AntBundle::new(
+                ant.position,
+                AntColor(settings.ant_color),
+                AntOrientation::new(facing, ant.angle),
+                AntInventory::default(),
+                ant.role,
+                name,
+                Initiative::new(rng),
+                zone,
+                hunger,
+                Digestion::new(settings.max_digestion_time),
+            ));
+
+            if ant.nested {
+                ant_entity_commands.insert((Nested, Birthing::new(settings.max_birthing_time)));
+            }
+        }
+    }
+}
+
+/// Every scenario which can be chosen when beginning a new story, by file name, along with why it can't be chosen
+/// if it's invalid. Player's scenarios, which live in their scenarios directory, are only found on native.
+pub fn find_scenarios() -> Vec<(String, Result<Scenario, String>)> {
+    let mut scenarios = BUILT_IN_SCENARIOS
+        .iter()
+        .map(|&(file_name, ron)| (file_name.to_string(), Scenario::from_ron(ron)))
+        .collect::<Vec<_>>();
+
+    #[cfg(not(target_family = "wasm"))]
+    scenarios.extend(find_player_scenarios());
+
+    scenarios
+}
+
+/// Scenarios live in the user's data directory, alongside saves, e.g. ~/.local/share/symbiants/scenarios on Linux.
+#[cfg(not(target_family = "wasm"))]
+fn find_player_scenarios() -> Vec<(String, Result<Scenario, String>)> {
+    let scenarios_directory = dirs::data_dir()
+        .unwrap_or_default()
+        .join("symbiants")
+        .join("scenarios");
+
+    let Ok(entries) = std::fs::read_dir(scenarios_directory) else {
+        return vec![];
+    };
+
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "ron"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            let scenario = std::fs::read_to_string(&path)
+                .map_err(|error| format!("Failed to read scenario: {}", error))
+                .and_then(|ron| Scenario::from_ron(&ron));
+
+            (file_name.to_string(), scenario)
+        })
+        .collect()
+}
+
+pub fn register_scenario(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<Scenario>();
+    app_type_registry.write().register::<Option<Scenario>>();
+    app_type_registry.write().register::<ZoneScenario>();
+    app_type_registry.write().register::<PheromoneScenario>();
+    app_type_registry
+        .write()
+        .register::<Vec<PheromoneScenario>>();
+    app_type_registry.write().register::<AntScenario>();
+    app_type_registry.write().register::<Vec<AntScenario>>();
+    app_type_registry.write().register::<Option<Facing>>();
+    app_type_registry.write().register::<Option<String>>();
+}
+
+/// Runs after Settings are initialized, and before they're journaled, so the story is told at the scenario's size.
+pub fn apply_scenario_settings(scenario: Res<Scenario>, mut settings: ResMut<Settings>) {
+    scenario.apply_to(&mut settings);
+}
+
+/// A scenario only decides how a new story begins.
+pub fn remove_scenario_resources(mut commands: Commands) {
+    commands.remove_resource::<Scenario>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::ant::Ant,
+        headless::{begin_story, HeadlessPlugin},
+        journal::Journal,
+        nest_simulation::nest::AtNest,
+    };
+
+    fn established_colony() -> Scenario {
+        Scenario::from_ron(BUILT_IN_SCENARIOS[0].1).unwrap()
+    }
+
+    #[test]
+    fn built_in_scenarios_are_valid() {
+        for (file_name, ron) in BUILT_IN_SCENARIOS {
+            assert_eq!(Scenario::from_ron(ron).err(), None, "{}", file_name);
+        }
+    }
+
+    #[test]
+    fn every_problem_is_described() {
+        let error = Scenario::from_ron(
+            r#"(
+                name: "Broken",
+                surface_level: 2,
+                nest: (
+                    width: 4,
+                    height: 4,
+                    layout: ["....", "....", "..?", "..###"],
+                    ants: [(role: Queen, position: (x: 3, y: 3), name: "Buried")],
+                ),
+                crater: (width: 0, height: 4),
+            )"#,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error,
+            [
+                "nest: layout row 2 has an unknown element '?'",
+                "nest: layout row 3 is longer than the zone is wide (4)",
+                "nest: Queen at Position { x: 3, y: 3 } must stand in air, but is buried",
+                "crater: width and height must be greater than 0, but are 0 and 4",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn story_begins_from_scenario() {
+        let scenario = established_colony();
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        app.insert_resource(scenario.clone());
+        begin_story(&mut app);
+
+        let settings = *app.world.resource::<Settings>();
+        assert_eq!(
+            (settings.nest_width, settings.nest_height),
+            (scenario.nest.width, scenario.nest.height)
+        );
+        assert!(!settings.terrain.has_edge_rock());
+
+        let element_grid = app
+            .world
+            .query_filtered::<&ElementGrid, With<AtNest>>()
+            .single(&app.world);
+        assert!(element_grid.is(Position::new(48, 29), Element::Food));
+        assert!(element_grid.is(Position::new(40, 20), Element::Air));
+        assert!(element_grid.is(Position::new(0, 63), Element::Dirt));
+
+        let (queen_name, queen_hunger) = app
+            .world
+            .query_filtered::<(&AntName, &AntRole, &Hunger), With<Nested>>()
+            .iter(&app.world)
+            .map(|(name, role, hunger)| {
+                assert_eq!(*role, AntRole::Queen);
+                (name.0.clone(), hunger.value())
+            })
+            .next()
+            .unwrap();
+        assert_eq!(queen_name, "Queen");
+        assert!(queen_hunger >= 25.0);
+
+        assert_eq!(
+            app.world.query::<&Ant>().iter(&app.world).count(),
+            scenario.nest.ants.len()
+        );
+        assert_eq!(
+            app.world
+                .query::<&Pheromone>()
+                .iter(&app.world)
+                .collect::<Vec<_>>(),
+            vec![&Pheromone::Chamber]
+        );
+
+        // The scenario is kept so the story can be replayed from the same world.
+        assert_eq!(app.world.resource::<Journal>().scenario(), Some(&scenario));
+    }
+}
@@ -1,6 +1,13 @@
-use bevy::{prelude::*, reflect::Reflect};
+use bevy::{
+    prelude::*,
+    reflect::{GetPath, Reflect},
+};
+use bevy_turborand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
-#[derive(Clone, Copy, Reflect, Debug)]
+#[derive(Clone, Copy, Reflect, Serialize, Deserialize, Debug)]
+#[serde(default)]
 pub struct Probabilities {
     pub random_drop: f32,             // drop while wandering
     pub random_turn: f32,             // turn while wandering
@@ -16,10 +23,151 @@ pub struct Probabilities {
     pub below_surface_queen_nest_dig: f32,
     pub expand_nest: f32,
     pub sleep_emote: f32,
+    pub water_soak: f32,  // chance for water resting on dirt to soak into it
+    pub element_tip: f32, // chance for a settled element to tip over a ledge
 }
 
-#[derive(Resource, Copy, Clone, Reflect, Debug)]
+impl Default for Probabilities {
+    fn default() -> Probabilities {
+        Probabilities {
+            random_drop: 0.003,
+            random_turn: 0.005,
+            // Ants slip/fall due to gravity when upside down or vertical.
+            // These settings help prevent scenarios where ants dig themselves onto islands and become trapped.
+            // If these settings are set too high then it will become difficult to haul sand out of nest.
+            random_fall: 0.002,
+            random_slip: 0.001,
+            above_surface_sand_drop: 0.04,
+            above_surface_food_dig: 0.50,
+            below_surface_food_dig: 0.10,
+            below_surface_food_drop: 0.10,
+            below_surface_food_adjacent_food_drop: 0.50,
+            above_surface_queen_food_drop: 0.50,
+            above_surface_queen_nest_dig: 0.10,
+            below_surface_queen_nest_dig: 0.50,
+            // TODO: keep playing with this value. lower chance = more cramped nest, but less sand to manage.
+            expand_nest: 0.2,
+            sleep_emote: 0.001,
+            water_soak: 0.002,
+            element_tip: 0.66,
+        }
+    }
+}
+
+/// How the nest of a new sandbox story is generated. Generation is random, but seeded, so every story's nest is
+/// different yet the same seed always generates the same nest. Zeroes generate a flat nest of dirt.
+#[derive(Clone, Copy, Reflect, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct Terrain {
+    /// How much deeper, or shallower, than `initial_dirt_percent` the dirt may be, as a fraction of nest_height.
+    pub depth_variation: f32,
+    /// The tallest a hill may rise above the surface level, in tiles.
+    pub hill_height: isize,
+    /// The distance between one hill and the next, in tiles.
+    pub hill_width: isize,
+    pub sand_pocket_count: isize,
+    /// Radius, in tiles.
+    pub sand_pocket_size: isize,
+    pub food_cache_count: isize,
+    /// Radius, in tiles.
+    pub food_cache_size: isize,
+    /// How far the rock crust along either edge of the nest reaches inward, as a fraction of nest_width.
+    /// Queens won't nest on rock, so this keeps them from nesting too near the edges of the world.
+    pub edge_rock_width: f32,
+    /// How deep the rock crust goes below the surface, in tiles.
+    pub edge_rock_depth: isize,
+}
+
+impl Default for Terrain {
+    fn default() -> Terrain {
+        Terrain {
+            depth_variation: 0.1,
+            hill_height: 6,
+            hill_width: 24,
+            sand_pocket_count: 6,
+            sand_pocket_size: 4,
+            food_cache_count: 2,
+            food_cache_size: 2,
+            edge_rock_width: 0.2,
+            edge_rock_depth: 3,
+        }
+    }
+}
+
+impl Terrain {
+    /// The terrain of stories which were saved before terrain was generated.
+    pub fn flat() -> Terrain {
+        Terrain {
+            depth_variation: 0.0,
+            hill_height: 0,
+            hill_width: 1,
+            sand_pocket_count: 0,
+            sand_pocket_size: 0,
+            food_cache_count: 0,
+            food_cache_size: 0,
+            edge_rock_width: 0.0,
+            edge_rock_depth: 0,
+        }
+    }
+
+    pub fn has_edge_rock(&self) -> bool {
+        self.edge_rock_width > 0.0 && self.edge_rock_depth > 0
+    }
+}
+
+/// How the crater of a new sandbox story is generated. Like Terrain it's random, but seeded. Zeroes generate an
+/// empty crater of air.
+#[derive(Clone, Copy, Reflect, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct CraterTerrain {
+    pub food_patch_count: isize,
+    /// The largest radius of a patch, in tiles. Each patch is somewhere between a single tile and this large.
+    pub food_patch_size: isize,
+    /// Obstacles are either rock or dirt. Ants in the crater walk around them.
+    pub obstacle_count: isize,
+    /// The largest radius of an obstacle, in tiles.
+    pub obstacle_size: isize,
+    /// How thick the ring of rock around the crater is, in tiles. Beyond the rim is dirt.
+    pub rim_width: isize,
+    /// Bushes and seed drops which grow food around themselves during the day.
+    pub food_source_count: isize,
+    /// How much food each source grows before it's depleted.
+    pub food_source_capacity: isize,
+}
+
+impl Default for CraterTerrain {
+    fn default() -> CraterTerrain {
+        CraterTerrain {
+            food_patch_count: 6,
+            food_patch_size: 3,
+            obstacle_count: 10,
+            obstacle_size: 4,
+            rim_width: 2,
+            food_source_count: 4,
+            food_source_capacity: 100,
+        }
+    }
+}
+
+impl CraterTerrain {
+    /// The crater of stories which were saved before craters were generated.
+    pub fn empty() -> CraterTerrain {
+        CraterTerrain {
+            food_patch_count: 0,
+            food_patch_size: 0,
+            obstacle_count: 0,
+            obstacle_size: 0,
+            rim_width: 0,
+            food_source_count: 0,
+            food_source_capacity: 0,
+        }
+    }
+}
+
+/// Settings which aren't given, e.g. by a settings file, take their default values.
+#[derive(Resource, Copy, Clone, Reflect, Serialize, Deserialize, Debug)]
 #[reflect(Resource)]
+#[serde(default)]
 pub struct Settings {
     pub snapshot_interval: isize,
     pub save_interval: isize,
@@ -38,6 +186,16 @@ pub struct Settings {
     pub max_birthing_time: isize,
     pub is_breathwork_scheduled: bool,
     pub probabilities: Probabilities,
+    /// Seeds GlobalRng when the story begins so that the same seed, given the same input, always tells the same story.
+    pub seed: u64,
+    pub terrain: Terrain,
+    /// How long an ant can stay underwater before it drowns.
+    pub max_submerged_time: isize,
+    pub crater_terrain: CraterTerrain,
+    /// How long a food source takes to grow each piece of food. Food sources don't grow at night.
+    pub food_source_growth_time: isize,
+    /// How long food lasts in the nest before it spoils into waste. Zero keeps food fresh forever.
+    pub food_spoil_time: isize,
 }
 
 impl Default for Settings {
@@ -47,7 +205,7 @@ impl Default for Settings {
             save_interval: 60,
             // Saving data to local storage is slow, but generating the snapshot of the world is also slow.
             // Take snapshots aggressively because browser tab closes too quickly to JIT snapshot.
-            snapshot_interval: 5, // TODO: prefer 1 here but it's too slow, makes sim stutter
+            snapshot_interval: 1,
             nest_width: 144,
             // TODO: I want this to be able to go to 400 without lag and without breaking local storage
             nest_height: 144,
@@ -60,43 +218,418 @@ impl Default for Settings {
             tunnel_length: 12,
             emote_duration: 30,
             max_hunger_time: 86_400, // 1 day
-            // NOTE: digestion_time must be quicker than hunger_time or ants hunger will never decrease.
+            // NOTE: digestion_time must be quicker than hunger_time or ants hunger will never decrease. See `validate`.
             max_digestion_time: 3_600, // 1 hour
             max_birthing_time: 3_600,  // 1 hour
             is_breathwork_scheduled: false,
-            probabilities: Probabilities {
-                random_drop: 0.003,
-                random_turn: 0.005,
-                // Ants slip/fall due to gravity when upside down or vertical.
-                // These settings help prevent scenarios where ants dig themselves onto islands and become trapped.
-                // If these settings are set too high then it will become difficult to haul sand out of nest.
-                random_fall: 0.002,
-                random_slip: 0.001,
-                above_surface_sand_drop: 0.04,
-                above_surface_food_dig: 0.50,
-                below_surface_food_dig: 0.10,
-                below_surface_food_drop: 0.10,
-                below_surface_food_adjacent_food_drop: 0.50,
-                above_surface_queen_food_drop: 0.50,
-                above_surface_queen_nest_dig: 0.10,
-                below_surface_queen_nest_dig: 0.50,
-                // TODO: keep playing with this value. lower chance = more cramped nest, but less sand to manage.
-                expand_nest: 0.2,
-                sleep_emote: 0.001,
-            },
+            probabilities: Probabilities::default(),
+            // Fixed so that default settings always tell the same story. New stories are given a random seed instead,
+            // unless one is chosen, when their settings are initialized.
+            seed: 0,
+            terrain: Terrain::default(),
+            max_submerged_time: 60, // 1 minute
+            crater_terrain: CraterTerrain::default(),
+            food_source_growth_time: 1_800, // 30 minutes
+            food_spoil_time: 172_800,       // 2 days
+        }
+    }
+}
+
+impl Settings {
+    /// Seeded from entropy so every new story is different.
+    pub fn random_seed() -> u64 {
+        GlobalRng::new().u64(..)
+    }
+
+    /// Whether settings written in RON choose their own seed, rather than leaving it to default.
+    fn is_seed_chosen_in_ron(ron: &str) -> bool {
+        match ron::from_str::<ron::Value>(ron) {
+            Ok(ron::Value::Map(fields)) => fields
+                .keys()
+                .any(|name| *name == ron::Value::String("seed".to_string())),
+            _ => false,
+        }
+    }
+
+    /// Parse settings written in RON, e.g. `(nest_width: 200, probabilities: (random_drop: 0.01))`.
+    pub fn from_ron(ron: &str) -> Result<Settings, String> {
+        ron::from_str(ron).map_err(|error| format!("Failed to parse settings: {}", error))
+    }
+
+    /// Change a single setting, e.g. `nest_width` or `probabilities.random_drop`, by parsing `value` as its type.
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let field = self
+            .reflect_path_mut(name)
+            .map_err(|_| format!("Unknown setting: {}", name))?;
+        let invalid_value = || format!("Invalid value for {}: {}", name, value);
+
+        if let Some(field) = field.downcast_mut::<isize>() {
+            *field = value.parse().map_err(|_| invalid_value())?;
+        } else if let Some(field) = field.downcast_mut::<f32>() {
+            *field = value.parse().map_err(|_| invalid_value())?;
+        } else if let Some(field) = field.downcast_mut::<bool>() {
+            *field = value.parse().map_err(|_| invalid_value())?;
+        } else if let Some(field) = field.downcast_mut::<u64>() {
+            *field = value.parse().map_err(|_| invalid_value())?;
+        } else {
+            return Err(format!("{} can't be set on its own", name));
+        }
+
+        Ok(())
+    }
+
+    /// Describe every problem with these settings, rather than just the first, so they can all be fixed at once.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut problems = vec![];
+
+        let sizes = [
+            ("snapshot_interval", self.snapshot_interval),
+            ("save_interval", self.save_interval),
+            ("nest_width", self.nest_width),
+            ("nest_height", self.nest_height),
+            ("crater_width", self.crater_width),
+            ("crater_height", self.crater_height),
+            ("chamber_size", self.chamber_size),
+            ("tunnel_length", self.tunnel_length),
+            ("emote_duration", self.emote_duration),
+            ("max_hunger_time", self.max_hunger_time),
+            ("max_digestion_time", self.max_digestion_time),
+            ("max_birthing_time", self.max_birthing_time),
+            ("terrain.hill_width", self.terrain.hill_width),
+            ("max_submerged_time", self.max_submerged_time),
+            ("food_source_growth_time", self.food_source_growth_time),
+        ];
+
+        for (name, value) in sizes {
+            if value <= 0 {
+                problems.push(format!("{} must be greater than 0, but is {}", name, value));
+            }
+        }
+
+        let counts = [
+            ("initial_ant_worker_count", self.initial_ant_worker_count),
+            ("terrain.hill_height", self.terrain.hill_height),
+            ("terrain.sand_pocket_count", self.terrain.sand_pocket_count),
+            ("terrain.sand_pocket_size", self.terrain.sand_pocket_size),
+            ("terrain.food_cache_count", self.terrain.food_cache_count),
+            ("terrain.food_cache_size", self.terrain.food_cache_size),
+            ("terrain.edge_rock_depth", self.terrain.edge_rock_depth),
+            ("food_spoil_time", self.food_spoil_time),
+            (
+                "crater_terrain.food_patch_count",
+                self.crater_terrain.food_patch_count,
+            ),
+            (
+                "crater_terrain.food_patch_size",
+                self.crater_terrain.food_patch_size,
+            ),
+            (
+                "crater_terrain.obstacle_count",
+                self.crater_terrain.obstacle_count,
+            ),
+            (
+                "crater_terrain.obstacle_size",
+                self.crater_terrain.obstacle_size,
+            ),
+            ("crater_terrain.rim_width", self.crater_terrain.rim_width),
+            (
+                "crater_terrain.food_source_count",
+                self.crater_terrain.food_source_count,
+            ),
+            (
+                "crater_terrain.food_source_capacity",
+                self.crater_terrain.food_source_capacity,
+            ),
+        ];
+
+        for (name, value) in counts {
+            if value < 0 {
+                problems.push(format!("{} can't be negative, but is {}", name, value));
+            }
+        }
+
+        let mut fractions = vec![
+            (
+                "initial_dirt_percent".to_string(),
+                self.initial_dirt_percent,
+            ),
+            (
+                "terrain.depth_variation".to_string(),
+                self.terrain.depth_variation,
+            ),
+            (
+                "terrain.edge_rock_width".to_string(),
+                self.terrain.edge_rock_width,
+            ),
+        ];
+
+        for (index, field) in self.probabilities.iter_fields().enumerate() {
+            if let (Some(name), Some(&value)) = (
+                self.probabilities.name_at(index),
+                field.downcast_ref::<f32>(),
+            ) {
+                fractions.push((format!("probabilities.{}", name), value));
+            }
+        }
+
+        for (name, value) in fractions {
+            if !(0.0..=1.0).contains(&value) {
+                problems.push(format!(
+                    "{} must be between 0 and 1, but is {}",
+                    name, value
+                ));
+            }
+        }
+
+        if self.max_digestion_time >= self.max_hunger_time {
+            problems.push(format!(
+                "max_digestion_time ({}) must be less than max_hunger_time ({}) or ants' hunger will never decrease",
+                self.max_digestion_time, self.max_hunger_time
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems.join("\n"))
         }
     }
 }
 
+/// Where the settings of new stories come from on native: defaults, then a settings file, then individual settings
+/// given on the command line. Without a SettingsSource new stories use the defaults.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct SettingsSource {
+    pub path: Option<PathBuf>,
+    /// Names and values of settings to change after reading the settings file.
+    pub overrides: Vec<(String, String)>,
+}
+
+impl SettingsSource {
+    pub const USAGE: &'static str = "[--settings <file>] [--set <name>=<value>]...";
+
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Result<SettingsSource, String> {
+        let mut settings_source = SettingsSource::default();
+
+        while let Some(arg) = args.next() {
+            if !settings_source.parse_arg(&arg, &mut args)? {
+                return Err(format!("Unknown argument: {}", arg));
+            }
+        }
+
+        Ok(settings_source)
+    }
+
+    /// Consume `arg`, and its value from `args`, if it's `--settings` or `--set`. Returns whether `arg` was consumed
+    /// so that other command lines can accept these arguments alongside their own.
+    pub fn parse_arg(
+        &mut self,
+        arg: &str,
+        args: &mut impl Iterator<Item = String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "--settings" => {
+                let value = args.next().ok_or("Missing value for --settings")?;
+                self.path = Some(PathBuf::from(value));
+            }
+            "--set" => {
+                let value = args.next().ok_or("Missing value for --set")?;
+                let (name, value) = value
+                    .split_once('=')
+                    .ok_or_else(|| format!("Expected <name>=<value> for --set: {}", value))?;
+                self.overrides.push((name.to_string(), value.to_string()));
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// New stories are given a random seed unless the settings file, or a setting given on the command line, chooses one.
+    pub fn load(&self) -> Result<Settings, String> {
+        let mut is_seed_chosen = self.overrides.iter().any(|(name, _)| name == "seed");
+
+        let mut settings = match &self.path {
+            Some(path) => {
+                let ron = std::fs::read_to_string(path)
+                    .map_err(|error| format!("Failed to read {:?}: {}", path, error))?;
+                is_seed_chosen |= Settings::is_seed_chosen_in_ron(&ron);
+                Settings::from_ron(&ron).map_err(|error| format!("{:?}: {}", path, error))?
+            }
+            None => Settings::default(),
+        };
+
+        if !is_seed_chosen {
+            settings.seed = Settings::random_seed();
+        }
+
+        for (name, value) in &self.overrides {
+            settings.set(name, value)?;
+        }
+
+        settings
+            .validate()
+            .map_err(|problems| format!("Invalid settings:\n{}", problems))?;
+
+        Ok(settings)
+    }
+}
+
 pub fn register_settings(app_type_registry: ResMut<AppTypeRegistry>) {
     app_type_registry.write().register::<Settings>();
     app_type_registry.write().register::<Probabilities>();
+    app_type_registry.write().register::<Terrain>();
+    app_type_registry.write().register::<CraterTerrain>();
 }
 
-pub fn initialize_settings_resources(mut commands: Commands) {
-    commands.init_resource::<Settings>();
+/// Settings which were already chosen, e.g. by a test, are kept. Otherwise they're loaded from the SettingsSource, or
+/// are the defaults, and the new story is given a random seed.
+/// The command line checks its SettingsSource at startup, so an invalid source here means the file changed since then.
+pub fn initialize_settings_resources(
+    settings: Option<Res<Settings>>,
+    settings_source: Option<Res<SettingsSource>>,
+    mut commands: Commands,
+) {
+    if settings.is_some() {
+        return;
+    }
+
+    let settings = match settings_source {
+        Some(settings_source) => settings_source.load().unwrap_or_else(|error| {
+            error!("{}\nUsing default settings instead.", error);
+            Settings {
+                seed: Settings::random_seed(),
+                ..default()
+            }
+        }),
+        None => Settings {
+            seed: Settings::random_seed(),
+            ..default()
+        },
+    };
+
+    commands.insert_resource(settings);
+}
+
+/// New stories start from their seed. Loaded stories instead restore GlobalRng from their save so they continue
+/// exactly where they left off.
+pub fn seed_global_rng(settings: Res<Settings>, mut rng: ResMut<GlobalRng>) {
+    *rng = GlobalRng::with_seed(settings.seed);
 }
 
 pub fn remove_settings_resources(mut commands: Commands) {
     commands.remove_resource::<Settings>();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn default_settings_are_valid() {
+        assert_eq!(Settings::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn missing_settings_are_defaults() {
+        let settings =
+            Settings::from_ron("(nest_width: 200, probabilities: (random_drop: 0.01))").unwrap();
+
+        assert_eq!(settings.nest_width, 200);
+        assert_eq!(settings.nest_height, Settings::default().nest_height);
+        assert_eq!(settings.probabilities.random_drop, 0.01);
+        assert_eq!(
+            settings.probabilities.random_turn,
+            Probabilities::default().random_turn
+        );
+    }
+
+    #[test]
+    fn default_seed_is_fixed() {
+        assert_eq!(Settings::default().seed, 0);
+    }
+
+    /// The seed chosen for a new story by `initialize_settings_resources`.
+    fn initialize_seed(settings_source: Option<SettingsSource>) -> u64 {
+        let mut world = World::new();
+        if let Some(settings_source) = settings_source {
+            world.insert_resource(settings_source);
+        }
+
+        world.run_system_once(initialize_settings_resources);
+        world.resource::<Settings>().seed
+    }
+
+    #[test]
+    fn new_stories_are_randomly_seeded() {
+        let load_seed = || SettingsSource::default().load().unwrap().seed;
+
+        assert_ne!(load_seed(), load_seed());
+        assert_ne!(initialize_seed(None), initialize_seed(None));
+        assert_ne!(
+            initialize_seed(Some(SettingsSource::default())),
+            initialize_seed(Some(SettingsSource::default()))
+        );
+    }
+
+    #[test]
+    fn chosen_seeds_are_kept() {
+        let overridden = || SettingsSource {
+            overrides: vec![("seed".to_string(), "5".to_string())],
+            ..default()
+        };
+
+        assert_eq!(overridden().load().unwrap().seed, 5);
+        assert_eq!(initialize_seed(Some(overridden())), 5);
+
+        let path = std::env::temp_dir().join(format!("symbiants-seed-{}.ron", std::process::id()));
+        std::fs::write(&path, "(seed: 5)").unwrap();
+        let from_file = SettingsSource {
+            path: Some(path.clone()),
+            ..default()
+        };
+        let seed = from_file.load().map(|settings| settings.seed);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(seed, Ok(5));
+        assert!(!Settings::is_seed_chosen_in_ron("(nest_width: 200)"));
+    }
+
+    #[test]
+    fn settings_are_set_by_name() {
+        let mut settings = Settings::default();
+
+        settings.set("crater_width", "64").unwrap();
+        settings.set("probabilities.expand_nest", "0.5").unwrap();
+
+        assert_eq!(settings.crater_width, 64);
+        assert_eq!(settings.probabilities.expand_nest, 0.5);
+        assert!(settings.set("crater_depth", "64").is_err());
+        assert!(settings.set("crater_width", "wide").is_err());
+        assert!(settings.set("ant_color", "purple").is_err());
+    }
+
+    #[test]
+    fn every_problem_is_described() {
+        let settings = Settings {
+            nest_width: 0,
+            max_digestion_time: 86_400,
+            probabilities: Probabilities {
+                random_drop: 1.5,
+                ..default()
+            },
+            ..default()
+        };
+
+        assert_eq!(
+            settings.validate(),
+            Err([
+                "nest_width must be greater than 0, but is 0",
+                "probabilities.random_drop must be between 0 and 1, but is 1.5",
+                "max_digestion_time (86400) must be less than max_hunger_time (86400) or ants' hunger will never decrease",
+            ]
+            .join("\n"))
+        );
+    }
+}
@@ -0,0 +1,314 @@
+use crate::{
+    chronicle::Chronicle,
+    common::ant::{AntRole, Dead},
+    nest_simulation::ant::nesting::Nested,
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND, SECONDS_PER_DAY},
+};
+use bevy::prelude::*;
+
+const TICKS_PER_DAY: isize = SECONDS_PER_DAY * DEFAULT_TICKS_PER_SECOND;
+
+/// Something the colony must achieve before Story Mode moves on to its next chapter.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Goal {
+    /// The queen has finished nesting and settled in to give birth.
+    QueenNested,
+    /// At least this many workers are alive at once.
+    Workers(usize),
+    /// A worker has brought food home from the crater.
+    FoodFromCrater,
+    /// The colony has survived this many days since the chapter began.
+    SurviveDays(isize),
+}
+
+pub struct Chapter {
+    pub title: &'static str,
+    /// Told as the chapter begins.
+    pub introduction: &'static str,
+    /// Told once every goal has been met.
+    pub conclusion: &'static str,
+    pub goals: &'static [Goal],
+}
+
+pub const CHAPTERS: [Chapter; 4] = [
+    Chapter {
+        title: "A Place to Call Home",
+        introduction: "A young queen has landed on the surface, her wings shed and her colony yet to be. \
+            She needs somewhere safe, underground, to raise her brood.",
+        conclusion: "The queen has dug herself a chamber and sealed herself inside. Soon she'll lay her first eggs.",
+        goals: &[Goal::QueenNested],
+    },
+    Chapter {
+        title: "A Growing Family",
+        introduction: "The queen can't do everything herself. \
+            Workers will dig, forage and tend to the nest while she gives birth.",
+        conclusion: "The nest is bustling with workers now.",
+        goals: &[Goal::Workers(5)],
+    },
+    Chapter {
+        title: "Beyond the Nest",
+        introduction: "The nest won't feed itself. \
+            Somewhere out in the crater there is food for any worker brave enough to go looking for it.",
+        conclusion: "A worker has made it home with food from the crater. The colony knows where to look now.",
+        goals: &[Goal::FoodFromCrater],
+    },
+    Chapter {
+        title: "Enduring",
+        introduction: "A colony is only as strong as its will to endure. \
+            Keep the queen alive, and her colony fed, for a week.",
+        conclusion: "A week has passed and the colony endures. What happens next is up to you.",
+        goals: &[Goal::SurviveDays(7)],
+    },
+];
+
+/// A chapter's introduction or conclusion, by chapter index, waiting to be told to the player.
+#[derive(Reflect, PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Narration {
+    ChapterStarted(usize),
+    ChapterCompleted(usize),
+}
+
+/// Progress through Story Mode's chapters, which is saved along with the story.
+/// Only stories begun from Story Mode have progress. Sandbox stories have no chapters or goals.
+#[derive(Resource, Reflect, PartialEq, Clone, Debug)]
+#[reflect(Resource)]
+pub struct StoryMode {
+    chapter: usize,
+    /// Whether each of the current chapter's goals has been met. Goals stay met, e.g. even if workers die afterward.
+    met_goals: Vec<bool>,
+    /// Oldest first. Kept until read so that narration isn't missed by closing the story before it's told.
+    unread_narration: Vec<Narration>,
+    /// When the current chapter began, in elapsed ticks, so that goals which take time are timed from then.
+    chapter_started_tick: isize,
+}
+
+impl Default for StoryMode {
+    fn default() -> Self {
+        Self {
+            chapter: 0,
+            met_goals: vec![false; CHAPTERS[0].goals.len()],
+            unread_narration: vec![Narration::ChapterStarted(0)],
+            chapter_started_tick: 0,
+        }
+    }
+}
+
+impl StoryMode {
+    /// The chapter being played, if there are any chapters left.
+    pub fn chapter(&self) -> Option<&'static Chapter> {
+        CHAPTERS.get(self.chapter)
+    }
+
+    pub fn chapter_index(&self) -> usize {
+        self.chapter
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.chapter >= CHAPTERS.len()
+    }
+
+    /// Each of the current chapter's goals along with whether it has been met.
+    pub fn goals(&self) -> impl Iterator<Item = (Goal, bool)> + '_ {
+        self.chapter()
+            .map_or(&[][..], |chapter| chapter.goals)
+            .iter()
+            .copied()
+            .zip(self.met_goals.iter().copied())
+    }
+
+    pub fn unread_narration(&self) -> Option<Narration> {
+        self.unread_narration.first().copied()
+    }
+
+    pub fn read_narration(&mut self) {
+        if !self.unread_narration.is_empty() {
+            self.unread_narration.remove(0);
+        }
+    }
+
+    /// Check the current chapter's unmet goals and, once they're all met, begin the next chapter at `elapsed_ticks`.
+    /// Only one chapter is completed at a time so that each chapter's goals are achieved during that chapter.
+    fn meet_goals(&mut self, elapsed_ticks: isize, is_met: impl Fn(Goal) -> bool) {
+        let Some(chapter) = self.chapter() else {
+            return;
+        };
+
+        for (met_goal, &goal) in self.met_goals.iter_mut().zip(chapter.goals) {
+            *met_goal = *met_goal || is_met(goal);
+        }
+
+        if self.met_goals.iter().any(|&met_goal| !met_goal) {
+            return;
+        }
+
+        self.unread_narration
+            .push(Narration::ChapterCompleted(self.chapter));
+        self.chapter += 1;
+        self.chapter_started_tick = elapsed_ticks;
+
+        if let Some(chapter) = self.chapter() {
+            self.met_goals = vec![false; chapter.goals.len()];
+            self.unread_narration
+                .push(Narration::ChapterStarted(self.chapter));
+        } else {
+            self.met_goals.clear();
+        }
+    }
+}
+
+pub fn register_story_mode(app_type_registry: ResMut<AppTypeRegistry>) {
+    app_type_registry.write().register::<StoryMode>();
+    app_type_registry.write().register::<Narration>();
+    app_type_registry.write().register::<Vec<Narration>>();
+    app_type_registry.write().register::<Vec<bool>>();
+}
+
+pub fn remove_story_mode_resources(mut commands: Commands) {
+    commands.remove_resource::<StoryMode>();
+}
+
+pub fn check_story_mode_goals(
+    ants_query: Query<(&AntRole, Has<Nested>), Without<Dead>>,
+    chronicle: Res<Chronicle>,
+    story_time: Res<StoryTime>,
+    mut story_mode: ResMut<StoryMode>,
+) {
+    if story_mode.is_complete() {
+        return;
+    }
+
+    let is_queen_nested = ants_query
+        .iter()
+        .any(|(&role, is_nested)| role == AntRole::Queen && is_nested);
+    let workers = ants_query
+        .iter()
+        .filter(|(&role, _)| role == AntRole::Worker)
+        .count();
+
+    let elapsed_ticks = story_time.elapsed_ticks();
+    let chapter_ticks = elapsed_ticks - story_mode.chapter_started_tick;
+
+    story_mode.meet_goals(elapsed_ticks, |goal| match goal {
+        Goal::QueenNested => is_queen_nested,
+        Goal::Workers(count) => workers >= count,
+        Goal::FoodFromCrater => chronicle.has_brought_food_from_crater(),
+        Goal::SurviveDays(days) => chapter_ticks >= days * TICKS_PER_DAY,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app_state::AppState,
+        headless::{begin_story, load_story, tick, HeadlessPlugin},
+        nest_simulation::ant::birthing::Birthing,
+        save::create_save_data,
+        settings::Settings,
+    };
+
+    #[test]
+    fn chapters_are_completed_in_order() {
+        let mut story_mode = StoryMode::default();
+
+        // Every goal is met, but only the first chapter is completed, so the second chapter's goals are checked anew.
+        story_mode.meet_goals(0, |_| true);
+        story_mode.meet_goals(0, |goal| goal != Goal::Workers(5));
+
+        assert_eq!(story_mode.chapter_index(), 1);
+        assert_eq!(
+            story_mode.goals().collect::<Vec<_>>(),
+            vec![(Goal::Workers(5), false)]
+        );
+
+        let mut narration = vec![];
+        while let Some(unread_narration) = story_mode.unread_narration() {
+            narration.push(unread_narration);
+            story_mode.read_narration();
+        }
+
+        assert_eq!(
+            narration,
+            vec![
+                Narration::ChapterStarted(0),
+                Narration::ChapterCompleted(0),
+                Narration::ChapterStarted(1),
+            ]
+        );
+
+        for _ in 1..CHAPTERS.len() {
+            story_mode.meet_goals(0, |_| true);
+        }
+
+        assert!(story_mode.is_complete());
+        assert_eq!(story_mode.goals().count(), 0);
+    }
+
+    #[test]
+    fn survival_is_timed_from_the_start_of_its_chapter() {
+        let mut story_mode = StoryMode::default();
+        for _ in 1..CHAPTERS.len() {
+            story_mode.meet_goals(10 * TICKS_PER_DAY, |_| true);
+        }
+        assert_eq!(
+            story_mode.goals().collect::<Vec<_>>(),
+            vec![(Goal::SurviveDays(7), false)]
+        );
+
+        let mut app = App::new();
+        app.insert_resource(story_mode);
+        app.init_resource::<Chronicle>();
+        app.insert_resource(StoryTime::default());
+        app.add_systems(Update, check_story_mode_goals);
+
+        // A week into the story, but not yet a week into the chapter.
+        app.world
+            .resource_mut::<StoryTime>()
+            .skip_ticks(16 * TICKS_PER_DAY);
+        app.update();
+        assert!(!app.world.resource::<StoryMode>().is_complete());
+
+        app.world
+            .resource_mut::<StoryTime>()
+            .skip_ticks(TICKS_PER_DAY);
+        app.update();
+        assert!(app.world.resource::<StoryMode>().is_complete());
+    }
+
+    #[test]
+    fn progress_is_saved_with_story() {
+        let mut app = App::new();
+        app.add_plugins(HeadlessPlugin);
+        app.insert_resource(StoryMode::default());
+        begin_story(&mut app);
+
+        let queen_entity = app
+            .world
+            .query::<(Entity, &AntRole)>()
+            .iter(&app.world)
+            .find(|(_, &ant_role)| ant_role == AntRole::Queen)
+            .map(|(entity, _)| entity)
+            .unwrap();
+        let max_birthing_time = app.world.resource::<Settings>().max_birthing_time;
+        app.world
+            .entity_mut(queen_entity)
+            .insert((Nested, Birthing::new(max_birthing_time)));
+        tick(&mut app);
+
+        let story_mode = app.world.resource::<StoryMode>().clone();
+        assert_eq!(story_mode.chapter_index(), 1);
+
+        let data = create_save_data(&mut app.world).unwrap();
+
+        let mut loaded_app = App::new();
+        loaded_app.add_plugins(HeadlessPlugin);
+        load_story(&mut loaded_app, &data).unwrap();
+        loaded_app
+            .world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::FinishSetup);
+        begin_story(&mut loaded_app);
+
+        assert_eq!(*loaded_app.world.resource::<StoryMode>(), story_mode);
+    }
+}
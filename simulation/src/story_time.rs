@@ -1,3 +1,4 @@
+use crate::catch_up::PendingCatchUp;
 use bevy::prelude::*;
 use chrono::Datelike;
 use chrono::{DateTime, LocalResult, NaiveDate, TimeZone, Timelike, Utc};
@@ -88,6 +89,11 @@ impl StoryTime {
         self.elapsed_ticks
     }
 
+    /// Advance the clock without running ticks, e.g. after summarizing a long absence.
+    pub fn skip_ticks(&mut self, ticks: isize) {
+        self.elapsed_ticks += ticks;
+    }
+
     pub fn as_time_info(&self) -> TimeInfo {
         let start_time_offset = if self.is_real_time {
             self.real_time_offset
@@ -226,6 +232,7 @@ pub fn remove_story_time_resources(mut commands: Commands) {
     commands.remove_resource::<StoryTime>();
     commands.remove_resource::<FastForwardPendingTicks>();
     commands.remove_resource::<TicksPerSecond>();
+    commands.remove_resource::<PendingCatchUp>();
     // Can't remove this resource because it's owned by Bevy.
     // Just assume they'll get reset to default when calling `initialize`. It's not a big deal
     // as long as Time::<Fixed> is reset when the app is restarted.
@@ -234,15 +241,16 @@ pub fn remove_story_time_resources(mut commands: Commands) {
 
 /// On startup, determine how much real-world time has passed since the last time the app ran,
 /// record this value into SimulationTime, and anticipate further processing.
+/// Only the last day of a longer absence is fast-forwarded. The rest is summarized by `catch_up_absence`.
 /// Write to SimulationTime because, in another scenario where the app is paused not closed, SimulationTime
 /// will be used by Bevy internally to track how de-synced the FixedUpdate schedule is from real-world time.
 pub fn setup_story_time(
     mut story_real_world_time: ResMut<StoryRealWorldTime>,
     mut next_story_playback_state: ResMut<NextState<StoryPlaybackState>>,
-    mut story_elapsed_ticks: ResMut<StoryTime>,
     ticks_per_second: Res<TicksPerSecond>,
     mut fast_forward_pending_ticks: ResMut<FastForwardPendingTicks>,
     mut fixed_time: ResMut<Time<Fixed>>,
+    mut commands: Commands,
 ) {
     // Setup story_real_world_time here, rather than as a Default, so that delta_seconds doesn't grow while idling in main menu
     if story_real_world_time.0 == 0 {
@@ -257,11 +265,8 @@ pub fn setup_story_time(
         let seconds_past_max = delta_seconds - SECONDS_PER_DAY;
 
         if seconds_past_max > 0 {
-            // Increment elapsed ticks by the amount not being simulated to keep game clock synced with real-world clock
-            if story_elapsed_ticks.is_real_time {
-                let missed_ticks = seconds_past_max * ticks_per_second.0;
-                story_elapsed_ticks.elapsed_ticks += missed_ticks;
-            }
+            // Summarizing advances elapsed ticks, too, so the game clock stays synced with the real-world clock.
+            commands.insert_resource(PendingCatchUp(seconds_past_max * ticks_per_second.0));
 
             // Enforce a max of 24 hours because it's impossible to quickly simulate an arbitrary amount of time missed.
             delta_seconds = SECONDS_PER_DAY;
@@ -0,0 +1,231 @@
+//! Build small, seeded worlds from ASCII maps so tests can arrange and assert an entire map at a glance.
+//!
+//! Each row of a map is a row of the nest, top to bottom. Leading/trailing whitespace and blank lines are ignored
+//! so maps can be indented alongside the code using them.
+//!
+//! | Char | Tile                              |
+//! |------|-----------------------------------|
+//! | `.`  | Air                               |
+//! | `D`  | Dirt                              |
+//! | `S`  | Sand                              |
+//! | `F`  | Food                              |
+//! | `R`  | Rock                              |
+//! | `W`  | Water                             |
+//! | `X`  | Waste                             |
+//! | `>`  | Upright worker ant, facing right  |
+//! | `<`  | Upright worker ant, facing left   |
+
+use crate::{
+    common::{
+        ant::{
+            digestion::Digestion, hunger::Hunger, Angle, AntBundle, AntColor, AntInventory,
+            AntName, AntOrientation, AntRole, Facing, Initiative,
+        },
+        element::Element,
+        grid::{ElementGrid, Grid},
+        position::Position,
+    },
+    nest_simulation::nest::{AtNest, Nest},
+    settings::Settings,
+};
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+
+/// Tests which depend on chance should pass with this seed unless they choose their own.
+pub const TEST_SEED: u64 = 42069; // ayy lmao
+
+enum Tile {
+    Element(Element),
+    Ant(Facing),
+}
+
+fn parse_tile(cell: char) -> Tile {
+    match cell {
+        '.' => Tile::Element(Element::Air),
+        'D' => Tile::Element(Element::Dirt),
+        'S' => Tile::Element(Element::Sand),
+        'F' => Tile::Element(Element::Food),
+        'R' => Tile::Element(Element::Rock),
+        'W' => Tile::Element(Element::Water),
+        'X' => Tile::Element(Element::Waste),
+        '>' => Tile::Ant(Facing::Right),
+        '<' => Tile::Ant(Facing::Left),
+        _ => panic!("Unknown map character: {:?}", cell),
+    }
+}
+
+fn get_element_cell(element: &Element) -> char {
+    match element {
+        Element::Air => '.',
+        Element::Dirt => 'D',
+        Element::Sand => 'S',
+        Element::Food => 'F',
+        Element::Rock => 'R',
+        Element::Water => 'W',
+        Element::Waste => 'X',
+    }
+}
+
+fn parse_map(map: &str) -> Vec<Vec<char>> {
+    let rows = map
+        .lines()
+        .map(str::trim)
+        .filter(|row| !row.is_empty())
+        .map(|row| row.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let width = rows.first().map_or(0, |row| row.len());
+    assert!(
+        rows.iter().all(|row| row.len() == width),
+        "Every row of a map must be the same width"
+    );
+
+    rows
+}
+
+/// Describes a nest to build from an ASCII map.
+pub struct NestFixture<'a> {
+    map: &'a str,
+    surface_level: isize,
+    seed: u64,
+}
+
+impl<'a> NestFixture<'a> {
+    /// The whole map is underground unless told otherwise so that dirt is supported as it would be in the nest.
+    pub fn new(map: &'a str) -> Self {
+        Self {
+            map,
+            surface_level: -1,
+            seed: TEST_SEED,
+        }
+    }
+
+    /// Rows at, or above, `surface_level` are aboveground.
+    pub fn with_surface_level(mut self, surface_level: isize) -> Self {
+        self.surface_level = surface_level;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Build an app containing just the nest. It has no systems so add those being tested and call `run_ticks`.
+    pub fn build(self) -> App {
+        let rows = parse_map(self.map);
+        let height = rows.len() as isize;
+        let width = rows.first().map_or(0, |row| row.len()) as isize;
+
+        let mut app = App::new();
+
+        let settings = Settings {
+            nest_width: width,
+            nest_height: height,
+            seed: self.seed,
+            ..default()
+        };
+
+        app.insert_resource(settings);
+        app.insert_resource(GlobalRng::with_seed(self.seed));
+
+        let mut elements = Vec::with_capacity((width * height) as usize);
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                let position = Position::new(x as isize, y as isize);
+
+                let element = match parse_tile(*cell) {
+                    Tile::Element(element) => element,
+                    Tile::Ant(facing) => {
+                        spawn_ant(&mut app.world, &settings, position, facing);
+                        Element::Air
+                    }
+                };
+
+                elements.push(element);
+            }
+        }
+
+        app.world.spawn((
+            Nest::new(self.surface_level),
+            Grid::new(width, height),
+            AtNest,
+        ));
+        app.world
+            .spawn((ElementGrid::new(width, height, elements), AtNest));
+
+        app
+    }
+}
+
+fn spawn_ant(world: &mut World, settings: &Settings, position: Position, facing: Facing) {
+    let initiative = world.resource_scope(|_, mut rng: Mut<GlobalRng>| Initiative::new(&mut rng));
+
+    world.spawn(AntBundle::new(
+        position,
+        AntColor(settings.ant_color),
+        AntOrientation::new(facing, Angle::Zero),
+        AntInventory::default(),
+        AntRole::Worker,
+        AntName(format!("Ant at {:?}", position)),
+        initiative,
+        AtNest,
+        Hunger::new(settings.max_hunger_time),
+        Digestion::new(settings.max_digestion_time),
+    ));
+}
+
+/// Run the app's systems once per tick.
+pub fn run_ticks(app: &mut App, ticks: usize) {
+    for _ in 0..ticks {
+        app.update();
+    }
+}
+
+/// Render the nest back into an ASCII map. Ants are drawn over the air they're standing in.
+pub fn render_nest(world: &mut World) -> String {
+    let settings = *world.resource::<Settings>();
+    let mut rows = vec![vec!['?'; settings.nest_width as usize]; settings.nest_height as usize];
+
+    let element_grid = world
+        .query_filtered::<&ElementGrid, With<AtNest>>()
+        .single(world);
+
+    for (position, element) in element_grid.iter() {
+        rows[position.y as usize][position.x as usize] = get_element_cell(&element);
+    }
+
+    for (orientation, position) in world
+        .query_filtered::<(&AntOrientation, &Position), (With<AntRole>, With<AtNest>)>()
+        .iter(world)
+    {
+        rows[position.y as usize][position.x as usize] = match orientation.get_facing() {
+            Facing::Right => '>',
+            Facing::Left => '<',
+        };
+    }
+
+    rows.into_iter()
+        .map(|row| row.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Assert the whole nest matches `expected`. Panics with both maps, in full, when they differ.
+#[track_caller]
+pub fn assert_nest(world: &mut World, expected: &str) {
+    let expected = parse_map(expected)
+        .into_iter()
+        .map(|row| row.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let actual = render_nest(world);
+
+    assert!(
+        actual == expected,
+        "Nest doesn't match.\nExpected:\n{}\n\nActual:\n{}\n",
+        expected,
+        actual
+    );
+}
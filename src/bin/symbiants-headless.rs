@@ -0,0 +1,209 @@
+use bevy::prelude::*;
+use simulation::{
+    common::{
+        ant::{hunger::Hunger, Ant, AntRole, Dead},
+        element::Element,
+        grid::ElementGrid,
+    },
+    headless::{begin_story, load_story, replay_story, tick, HeadlessPlugin},
+    journal::Journal,
+    settings::{Settings, SettingsSource},
+    story_time::{StoryTime, DEFAULT_TICKS_PER_SECOND, SECONDS_PER_HOUR},
+};
+use std::{path::PathBuf, process::ExitCode};
+
+/// Simulate one in-game hour unless told otherwise.
+const DEFAULT_TICKS: usize = (SECONDS_PER_HOUR * DEFAULT_TICKS_PER_SECOND) as usize;
+
+const USAGE: &str =
+    "Usage: symbiants-headless [--ticks <count>] [--seed <number> | --replay <save file>]";
+
+struct Args {
+    ticks: Option<usize>,
+    seed: Option<u64>,
+    replay_path: Option<PathBuf>,
+    settings_source: SettingsSource,
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}\n{} {}", message, USAGE, SettingsSource::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin);
+
+    let ticks = match args.replay_path {
+        Some(replay_path) => {
+            let (journal, elapsed_ticks) = match read_journal(&replay_path) {
+                Ok(journal) => journal,
+                Err(message) => {
+                    eprintln!("{}", message);
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            println!(
+                "Replaying {} journaled events from {:?}",
+                journal.entries().len(),
+                replay_path
+            );
+            replay_story(&mut app, &journal);
+
+            // Catch up to the moment the save was taken unless asked to look further ahead.
+            args.ticks.unwrap_or(elapsed_ticks as usize)
+        }
+        None => {
+            let mut settings = match args.settings_source.load() {
+                Ok(settings) => settings,
+                Err(message) => {
+                    eprintln!("{}", message);
+                    return ExitCode::from(2);
+                }
+            };
+
+            if let Some(seed) = args.seed {
+                settings.seed = seed;
+            }
+
+            // Settings already present aren't overwritten when the story is created so they decide its seed.
+            app.insert_resource(settings);
+
+            begin_story(&mut app);
+
+            args.ticks.unwrap_or(DEFAULT_TICKS)
+        }
+    };
+
+    for _ in 0..ticks {
+        if !tick(&mut app) {
+            println!("The queen has died. Story over.");
+            break;
+        }
+    }
+
+    print_summary(&mut app.world);
+
+    ExitCode::SUCCESS
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut ticks = None;
+    let mut seed = None;
+    let mut replay_path = None;
+    let mut settings_source = SettingsSource::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ticks" => {
+                let value = args.next().ok_or("Missing value for --ticks")?;
+                ticks = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("Invalid value for --ticks: {}", value))?,
+                );
+            }
+            "--seed" => {
+                let value = args.next().ok_or("Missing value for --seed")?;
+                seed = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("Invalid value for --seed: {}", value))?,
+                );
+            }
+            "--replay" => {
+                let value = args.next().ok_or("Missing value for --replay")?;
+                replay_path = Some(PathBuf::from(value));
+            }
+            _ => {
+                if !settings_source.parse_arg(&arg, &mut args)? {
+                    return Err(format!("Unknown argument: {}", arg));
+                }
+            }
+        }
+    }
+
+    if seed.is_some() && replay_path.is_some() {
+        return Err(
+            "--seed can't be used with --replay because the journal has its own seed".into(),
+        );
+    }
+
+    if replay_path.is_some()
+        && (settings_source.path.is_some() || !settings_source.overrides.is_empty())
+    {
+        return Err(
+            "--settings and --set can't be used with --replay because the journal has its own settings".into(),
+        );
+    }
+
+    Ok(Args {
+        ticks,
+        seed,
+        replay_path,
+        settings_source,
+    })
+}
+
+/// Read the journal out of a save, along with how far its story had progressed when saved.
+fn read_journal(save_path: &PathBuf) -> Result<(Journal, isize), String> {
+    let data = std::fs::read(save_path)
+        .map_err(|error| format!("Failed to read {:?}: {}", save_path, error))?;
+
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin);
+    load_story(&mut app, &data)
+        .map_err(|error| format!("Failed to load {:?}: {}", save_path, error))?;
+
+    let journal = app.world.remove_resource::<Journal>().ok_or_else(|| {
+        format!(
+            "{:?} was saved before journaling and can't be replayed",
+            save_path
+        )
+    })?;
+
+    Ok((journal, app.world.resource::<StoryTime>().elapsed_ticks()))
+}
+
+fn print_summary(world: &mut World) {
+    let ant_count = world
+        .query_filtered::<(), (With<Ant>, Without<Dead>)>()
+        .iter(world)
+        .count();
+
+    let food_count = world
+        .query::<&ElementGrid>()
+        .iter(world)
+        .flat_map(|element_grid| element_grid.iter())
+        .filter(|&(_, element)| element == Element::Food)
+        .count();
+
+    let queen_hunger = world
+        .query_filtered::<(&AntRole, &Hunger), With<Ant>>()
+        .iter(world)
+        .find(|(ant_role, _)| **ant_role == AntRole::Queen)
+        .map(|(_, hunger)| hunger.value());
+
+    let story_time = world.resource::<StoryTime>();
+    let time_info = story_time.as_time_info();
+
+    println!("Seed: {}", world.resource::<Settings>().seed);
+    println!(
+        "Elapsed: {} ticks (day {}, {:02}:{:02})",
+        story_time.elapsed_ticks(),
+        time_info.days() + 1,
+        time_info.hours(),
+        time_info.minutes()
+    );
+    println!("Ants: {}", ant_count);
+    println!("Food: {}", food_count);
+
+    match queen_hunger {
+        Some(hunger) => println!("Queen hunger: {:.1}%", hunger),
+        None => println!("Queen hunger: n/a (no queen)"),
+    }
+}
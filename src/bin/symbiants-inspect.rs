@@ -0,0 +1,282 @@
+use bevy::{prelude::*, reflect::serde::TypedReflectSerializer};
+use serde::Serialize;
+use simulation::{
+    common::{
+        ant::{hunger::Hunger, AntName, AntRole, Dead},
+        element::Element,
+        grid::ElementGrid,
+        position::Position,
+    },
+    crater_simulation::crater::AtCrater,
+    headless::{load_story, HeadlessPlugin},
+    nest_simulation::nest::AtNest,
+    settings::Settings,
+    story_time::{StoryRealWorldTime, StoryTime},
+};
+use std::{path::PathBuf, process::ExitCode};
+
+const USAGE: &str =
+    "Usage: symbiants-inspect <save file> [--format <text|ron|json>] [--output <file>]";
+
+#[derive(PartialEq)]
+enum OutputFormat {
+    Text,
+    Ron,
+    Json,
+}
+
+struct Args {
+    save_path: PathBuf,
+    format: OutputFormat,
+    output_path: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct AntInspection {
+    name: String,
+    role: AntRole,
+    hunger: f32,
+    position: Position,
+    zone: &'static str,
+    is_dead: bool,
+}
+
+/// Everything worth knowing about a save. Grids are rows of ASCII, top to bottom, so they stay readable once converted.
+#[derive(Serialize)]
+struct SaveInspection<'a> {
+    settings: TypedReflectSerializer<'a>,
+    story_time: TypedReflectSerializer<'a>,
+    ants: Vec<AntInspection>,
+    nest: Vec<String>,
+    crater: Vec<String>,
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let data = match std::fs::read(&args.save_path) {
+        Ok(data) => data,
+        Err(error) => {
+            eprintln!("Failed to read {:?}: {}", args.save_path, error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut app = App::new();
+    app.add_plugins(HeadlessPlugin);
+
+    if let Err(error) = load_story(&mut app, &data) {
+        eprintln!("Failed to load {:?}: {}", args.save_path, error);
+        return ExitCode::FAILURE;
+    }
+
+    let output = match inspect(&mut app.world, &args.format) {
+        Ok(output) => output,
+        Err(error) => {
+            eprintln!("Failed to convert save: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match args.output_path {
+        Some(output_path) => {
+            if let Err(error) = std::fs::write(&output_path, output) {
+                eprintln!("Failed to write {:?}: {}", output_path, error);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", output),
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut save_path = None;
+    let mut format = OutputFormat::Text;
+    let mut output_path = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let value = args.next().ok_or("Missing value for --format")?;
+                format = match value.as_str() {
+                    "text" => OutputFormat::Text,
+                    "ron" => OutputFormat::Ron,
+                    "json" => OutputFormat::Json,
+                    _ => return Err(format!("Invalid value for --format: {}", value)),
+                };
+            }
+            "--output" => {
+                let value = args.next().ok_or("Missing value for --output")?;
+                output_path = Some(PathBuf::from(value));
+            }
+            _ if arg.starts_with("--") => return Err(format!("Unknown argument: {}", arg)),
+            _ if save_path.is_none() => save_path = Some(PathBuf::from(arg)),
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    Ok(Args {
+        save_path: save_path.ok_or("Missing save file")?,
+        format,
+        output_path,
+    })
+}
+
+fn inspect(world: &mut World, format: &OutputFormat) -> Result<String, String> {
+    let ants = get_ants(world);
+    let nest = render_grid::<AtNest>(world, |settings| {
+        (settings.nest_width, settings.nest_height)
+    });
+    let crater = render_grid::<AtCrater>(world, |settings| {
+        (settings.crater_width, settings.crater_height)
+    });
+
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let settings = world.resource::<Settings>();
+    let story_time = world.resource::<StoryTime>();
+
+    let inspection = SaveInspection {
+        settings: TypedReflectSerializer::new(settings, &registry),
+        story_time: TypedReflectSerializer::new(story_time, &registry),
+        ants,
+        nest,
+        crater,
+    };
+
+    match format {
+        OutputFormat::Ron => {
+            ron::ser::to_string_pretty(&inspection, default()).map_err(|error| error.to_string())
+        }
+        OutputFormat::Json => {
+            serde_json::to_string_pretty(&inspection).map_err(|error| error.to_string())
+        }
+        OutputFormat::Text => {
+            let time_info = story_time.as_time_info();
+            let mut output = String::new();
+
+            output += &format!(
+                "Story time: {} ticks (day {}, {:02}:{:02})\n",
+                story_time.elapsed_ticks(),
+                time_info.days() + 1,
+                time_info.hours(),
+                time_info.minutes()
+            );
+            output += &format!(
+                "Last played: {}\n\n",
+                world.resource::<StoryRealWorldTime>().as_datetime()
+            );
+            output += &format!(
+                "Settings: {}\n\n",
+                ron::ser::to_string_pretty(&inspection.settings, default())
+                    .map_err(|error| error.to_string())?
+            );
+
+            output += &format!("Ants: {}\n", inspection.ants.len());
+            for ant in inspection.ants.iter() {
+                output += &format!(
+                    "  {:<16} {:<6} hunger {:>5.1}% at ({}, {}) in {}{}\n",
+                    ant.name,
+                    format!("{:?}", ant.role),
+                    ant.hunger,
+                    ant.position.x,
+                    ant.position.y,
+                    ant.zone,
+                    if ant.is_dead { " (dead)" } else { "" }
+                );
+            }
+
+            output += &format!("\nNest:\n{}\n", inspection.nest.join("\n"));
+            output += &format!("\nCrater:\n{}\n", inspection.crater.join("\n"));
+
+            Ok(output)
+        }
+    }
+}
+
+fn get_ants(world: &mut World) -> Vec<AntInspection> {
+    let mut ant_query = world.query::<(
+        &AntName,
+        &AntRole,
+        &Hunger,
+        &Position,
+        Has<AtNest>,
+        Has<Dead>,
+    )>();
+
+    let mut ants = ant_query
+        .iter(world)
+        .map(
+            |(ant_name, ant_role, hunger, position, is_at_nest, is_dead)| AntInspection {
+                name: ant_name.0.clone(),
+                role: *ant_role,
+                hunger: hunger.value(),
+                position: *position,
+                zone: if is_at_nest { "nest" } else { "crater" },
+                is_dead,
+            },
+        )
+        .collect::<Vec<_>>();
+
+    // Entity order isn't meaningful so sort to keep output stable between runs.
+    ants.sort_by(|a, b| a.name.cmp(&b.name));
+
+    ants
+}
+
+fn render_grid<Z: Component>(
+    world: &mut World,
+    get_size: impl Fn(&Settings) -> (isize, isize),
+) -> Vec<String> {
+    let (width, height) = get_size(world.resource::<Settings>());
+    let mut grid = vec![vec![' '; width as usize]; height as usize];
+
+    let mut set_cell = |position: &Position, cell: char| {
+        if position.x >= 0 && position.x < width && position.y >= 0 && position.y < height {
+            grid[position.y as usize][position.x as usize] = cell;
+        }
+    };
+
+    for element_grid in world.query_filtered::<&ElementGrid, With<Z>>().iter(world) {
+        for (position, element) in element_grid.iter() {
+            set_cell(&position, get_element_cell(&element));
+        }
+    }
+
+    // Ants are drawn over elements because they're standing in air.
+    for (ant_role, position, is_dead) in world
+        .query_filtered::<(&AntRole, &Position, Has<Dead>), (With<AntName>, With<Z>)>()
+        .iter(world)
+    {
+        let cell = match (ant_role, is_dead) {
+            (_, true) => 'x',
+            (AntRole::Queen, false) => 'Q',
+            (AntRole::Worker, false) => 'a',
+        };
+
+        set_cell(position, cell);
+    }
+
+    grid.into_iter()
+        .map(|row| row.into_iter().collect::<String>().trim_end().to_string())
+        .collect()
+}
+
+fn get_element_cell(element: &Element) -> char {
+    match element {
+        Element::Air => ' ',
+        Element::Dirt => '#',
+        Element::Sand => ':',
+        Element::Food => '*',
+        Element::Rock => '@',
+        Element::Water => '~',
+        Element::Waste => '%',
+    }
+}
@@ -1,9 +1,30 @@
-use symbiants_lib::SymbiantsPlugin;
 use bevy::app::App;
+#[cfg(not(target_arch = "wasm32"))]
+use simulation::settings::SettingsSource;
+use symbiants_lib::SymbiantsPlugin;
 
 fn main() {
     #[cfg(target_arch = "wasm32")]
     console_error_panic_hook::set_once();
 
-    App::new().add_plugins(SymbiantsPlugin).run();
+    let mut app = App::new();
+
+    // Check settings up front so mistakes are reported before a window opens rather than when a story begins.
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let settings_source = SettingsSource::from_args(std::env::args().skip(1))
+            .and_then(|settings_source| settings_source.load().map(|_| settings_source));
+
+        match settings_source {
+            Ok(settings_source) => {
+                app.insert_resource(settings_source);
+            }
+            Err(message) => {
+                eprintln!("{}\nUsage: symbiants {}", message, SettingsSource::USAGE);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    app.add_plugins(SymbiantsPlugin).run();
 }
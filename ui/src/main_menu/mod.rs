@@ -4,12 +4,28 @@ use bevy_egui::{
     EguiContexts,
 };
 
-use simulation::app_state::AppState;
+use simulation::{
+    app_state::AppState,
+    save::{
+        initialize_save_slot_resources, read_save_slots, ActiveSaveSlot, SaveSlotId, SaveSlots,
+    },
+    scenario::{find_scenarios, Scenario},
+    story_mode::StoryMode,
+};
+
+const DATE_FORMAT: &str = "%b %-d, %Y %H:%M";
 
 pub struct MainMenuUIPlugin;
 
 impl Plugin for MainMenuUIPlugin {
     fn build(&self, app: &mut App) {
+        app.add_systems(
+            Startup,
+            (read_save_slots, continue_most_recent_save_slot)
+                .chain()
+                .after(initialize_save_slot_resources),
+        );
+
         app.add_systems(
             Update,
             update_main_menu.run_if(in_state(AppState::MainMenu)),
@@ -17,11 +33,54 @@ impl Plugin for MainMenuUIPlugin {
     }
 }
 
+/// Players expect to pick up where they left off so skip the main menu, on launch, if there's a story to continue.
+pub fn continue_most_recent_save_slot(
+    save_slots: Res<SaveSlots>,
+    mut active_save_slot: ResMut<ActiveSaveSlot>,
+) {
+    active_save_slot.0 = save_slots.most_recent().map(|save_slot| save_slot.id());
+}
+
+#[derive(Default)]
+pub struct MainMenuState {
+    new_colony_name: String,
+    renaming: Option<(SaveSlotId, String)>,
+    pending_delete: Option<SaveSlotId>,
+    /// Found when the main menu is first shown rather than every frame.
+    scenarios: Option<Vec<(String, Result<Scenario, String>)>>,
+    /// New colonies begin from an empty nest unless a scenario is chosen.
+    scenario_index: Option<usize>,
+}
+
+impl MainMenuState {
+    fn selected_scenario(&self) -> Option<&Scenario> {
+        let (_, scenario) = self.scenarios.as_ref()?.get(self.scenario_index?)?;
+
+        scenario.as_ref().ok()
+    }
+}
+
+enum SaveSlotAction {
+    Continue(SaveSlotId),
+    Duplicate(SaveSlotId),
+    BeginRename(SaveSlotId),
+    Rename(SaveSlotId),
+    CancelRename,
+    Delete(SaveSlotId),
+    ConfirmDelete(SaveSlotId),
+    CancelDelete,
+}
+
 pub fn update_main_menu(
     mut contexts: EguiContexts,
     mut next_app_state: ResMut<NextState<AppState>>,
+    mut save_slots: ResMut<SaveSlots>,
+    mut active_save_slot: ResMut<ActiveSaveSlot>,
+    mut main_menu_state: Local<MainMenuState>,
+    mut commands: Commands,
 ) {
     let ctx = contexts.ctx_mut();
+    let mut actions = vec![];
 
     egui::Window::new("Main Menu")
         .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
@@ -31,12 +90,201 @@ pub fn update_main_menu(
             ui.vertical_centered(|ui| {
                 ui.label("Welcome to Symbiants");
 
-                ui.add_enabled(false, egui::Button::new("Story Mode"))
-                    .on_disabled_hover_text("Coming soon™!");
+                if ui.button("Story Mode").clicked() {
+                    commands.insert_resource(StoryMode::default());
+
+                    let save_slot_id = save_slots.create("Story Mode");
+                    active_save_slot.0 = Some(save_slot_id);
+                    next_app_state.set(AppState::CreateNewStory);
+                }
+            });
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut main_menu_state.new_colony_name)
+                        .hint_text("Colony name"),
+                );
+
+                if ui.button("New Colony").clicked() {
+                    if let Some(scenario) = main_menu_state.selected_scenario() {
+                        commands.insert_resource(scenario.clone());
+                    }
 
-                if ui.button("Sandbox Mode").clicked() {
-                    next_app_state.set(AppState::FinishSetup);
+                    let save_slot_id = save_slots.create(&main_menu_state.new_colony_name);
+                    main_menu_state.new_colony_name.clear();
+                    main_menu_state.scenario_index = None;
+
+                    active_save_slot.0 = Some(save_slot_id);
+                    next_app_state.set(AppState::CreateNewStory);
                 }
             });
+
+            ui.horizontal(|ui| {
+                ui.label("Begin from");
+
+                let selected_text = main_menu_state
+                    .selected_scenario()
+                    .map_or(String::from("Empty Nest"), |scenario| scenario.name.clone());
+
+                let MainMenuState {
+                    scenarios,
+                    scenario_index,
+                    ..
+                } = &mut *main_menu_state;
+
+                egui::ComboBox::from_id_source("new_colony_scenario")
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(scenario_index, None, "Empty Nest");
+
+                        for (index, (file_name, scenario)) in scenarios
+                            .get_or_insert_with(find_scenarios)
+                            .iter()
+                            .enumerate()
+                        {
+                            match scenario {
+                                Ok(scenario) => {
+                                    let response = ui.selectable_value(
+                                        scenario_index,
+                                        Some(index),
+                                        &scenario.name,
+                                    );
+
+                                    if !scenario.description.is_empty() {
+                                        response.on_hover_text(&scenario.description);
+                                    }
+                                }
+                                // Show broken scenarios, rather than hiding them, so players know why theirs is missing.
+                                Err(error) => {
+                                    ui.add_enabled(
+                                        false,
+                                        egui::SelectableLabel::new(false, file_name),
+                                    )
+                                    .on_disabled_hover_text(error);
+                                }
+                            }
+                        }
+                    });
+            });
+
+            if save_slots.iter().next().is_none() {
+                return;
+            }
+
+            ui.separator();
+
+            egui::ScrollArea::vertical()
+                .max_height(320.0)
+                .show(ui, |ui| {
+                    for save_slot in save_slots.iter() {
+                        let id = save_slot.id();
+
+                        ui.group(|ui| {
+                            match main_menu_state.renaming.as_mut() {
+                                Some((renaming_id, name)) if *renaming_id == id => {
+                                    ui.horizontal(|ui| {
+                                        ui.text_edit_singleline(name);
+
+                                        if ui.button("Save").clicked() {
+                                            actions.push(SaveSlotAction::Rename(id));
+                                        }
+
+                                        if ui.button("Cancel").clicked() {
+                                            actions.push(SaveSlotAction::CancelRename);
+                                        }
+                                    });
+                                }
+                                _ => {
+                                    ui.strong(save_slot.name());
+                                }
+                            }
+
+                            ui.label(format!("{} ants", save_slot.ant_count()));
+                            ui.small(format!(
+                                "Last played {}",
+                                save_slot.last_played().format(DATE_FORMAT)
+                            ));
+                            ui.small(format!(
+                                "Created {}",
+                                save_slot.created().format(DATE_FORMAT)
+                            ));
+
+                            ui.horizontal(|ui| {
+                                if main_menu_state.pending_delete == Some(id) {
+                                    ui.label("Delete this colony forever?");
+
+                                    if ui.button("Delete").clicked() {
+                                        actions.push(SaveSlotAction::ConfirmDelete(id));
+                                    }
+
+                                    if ui.button("Cancel").clicked() {
+                                        actions.push(SaveSlotAction::CancelDelete);
+                                    }
+
+                                    return;
+                                }
+
+                                if ui.button("Continue").clicked() {
+                                    actions.push(SaveSlotAction::Continue(id));
+                                }
+
+                                if ui.button("Duplicate").clicked() {
+                                    actions.push(SaveSlotAction::Duplicate(id));
+                                }
+
+                                if ui.button("Rename").clicked() {
+                                    actions.push(SaveSlotAction::BeginRename(id));
+                                }
+
+                                if ui.button("Delete").clicked() {
+                                    actions.push(SaveSlotAction::Delete(id));
+                                }
+                            });
+                        });
+                    }
+                });
         });
+
+    // Apply actions after drawing because the slots can't be modified while they're being iterated.
+    for action in actions {
+        match action {
+            SaveSlotAction::Continue(id) => {
+                active_save_slot.0 = Some(id);
+                next_app_state.set(AppState::Loading);
+            }
+            SaveSlotAction::Duplicate(id) => {
+                if save_slots.duplicate(id).is_none() {
+                    error!("Failed to duplicate save slot {}", id);
+                }
+            }
+            SaveSlotAction::BeginRename(id) => {
+                let name = save_slots
+                    .get(id)
+                    .map(|save_slot| save_slot.name().to_string())
+                    .unwrap_or_default();
+
+                main_menu_state.renaming = Some((id, name));
+            }
+            SaveSlotAction::Rename(id) => {
+                if let Some((_, name)) = main_menu_state.renaming.take() {
+                    save_slots.rename(id, &name);
+                }
+            }
+            SaveSlotAction::CancelRename => {
+                main_menu_state.renaming = None;
+            }
+            SaveSlotAction::Delete(id) => {
+                main_menu_state.pending_delete = Some(id);
+            }
+            SaveSlotAction::ConfirmDelete(id) => {
+                save_slots.delete(id);
+                main_menu_state.pending_delete = None;
+            }
+            SaveSlotAction::CancelDelete => {
+                main_menu_state.pending_delete = None;
+            }
+        }
+    }
 }
@@ -67,6 +67,16 @@ pub fn update_action_menu(
                 PointerAction::SpawnDirt,
                 "Place Dirt",
             );
+            ui.selectable_value(
+                pointer_action.as_mut(),
+                PointerAction::SpawnRock,
+                "Place Rock",
+            );
+            ui.selectable_value(
+                pointer_action.as_mut(),
+                PointerAction::SpawnWater,
+                "Place Water",
+            );
             ui.selectable_value(
                 pointer_action.as_mut(),
                 PointerAction::DespawnElement,
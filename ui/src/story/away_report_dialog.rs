@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2},
+    EguiContexts,
+};
+
+use simulation::{
+    away_report::AwayReport,
+    common::ant::death::DeathCause,
+    story_time::{TicksPerSecond, SECONDS_PER_DAY, SECONDS_PER_HOUR},
+};
+
+use super::loading_dialog::MIN_PENDING_TICKS;
+
+pub fn update_away_report_dialog(
+    mut contexts: EguiContexts,
+    away_report: Res<AwayReport>,
+    ticks_per_second: Res<TicksPerSecond>,
+    mut commands: Commands,
+) {
+    // Wait for fast-forwarding to finish, and don't bother reporting on the brief catch-up after returning to a hidden tab.
+    if !away_report.is_complete() || away_report.ticks < MIN_PENDING_TICKS {
+        return;
+    }
+
+    egui::Window::new("While You Were Away")
+        .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let seconds_gone = away_report.ticks / ticks_per_second.0;
+
+            ui.label(&format!("{} passed.", describe_duration(seconds_gone)));
+
+            ui.separator();
+
+            ui.label(&format!("Births: {}", away_report.births));
+            ui.label(&format!(
+                "Deaths by Starvation: {}",
+                away_report.deaths_by(DeathCause::Starvation)
+            ));
+            ui.label(&format!(
+                "Deaths by Player: {}",
+                away_report.deaths_by(DeathCause::Killed)
+            ));
+            ui.label(&format!(
+                "Deaths by Drowning: {}",
+                away_report.deaths_by(DeathCause::Drowned)
+            ));
+            ui.label(&format!("Food Eaten: {}", away_report.food_eaten));
+            ui.label(&format!("Tiles Dug: {}", away_report.tiles_dug));
+            ui.label(&format!("Pheromones Laid: {}", away_report.pheromones_laid));
+
+            ui.separator();
+
+            ui.vertical_centered(|ui| {
+                if ui.button("Continue").clicked() {
+                    commands.remove_resource::<AwayReport>();
+                }
+            });
+        });
+}
+
+fn describe_duration(seconds: isize) -> String {
+    let days = seconds / SECONDS_PER_DAY;
+    let hours = (seconds % SECONDS_PER_DAY) / SECONDS_PER_HOUR;
+    let minutes = (seconds % SECONDS_PER_HOUR) / 60;
+
+    let parts = [(days, "day"), (hours, "hour"), (minutes, "minute")]
+        .into_iter()
+        .filter(|&(value, _)| value > 0)
+        .map(|(value, unit)| format!("{} {}{}", value, unit, pluralize(value)))
+        .collect::<Vec<_>>();
+
+    match parts.len() {
+        0 => "Less than a minute".to_string(),
+        _ => parts.join(", "),
+    }
+}
+
+fn pluralize(value: isize) -> &'static str {
+    if value != 1 {
+        "s"
+    } else {
+        ""
+    }
+}
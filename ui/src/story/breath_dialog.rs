@@ -8,8 +8,8 @@ use bevy_turborand::{DelegatedRng, GlobalRng};
 use simulation::{
     common::{
         ant::{hunger::Hunger, Dead},
-        element::{commands::ElementCommandsExt, Air, Element, Food},
-        grid::GridElements,
+        element::{commands::ElementCommandsExt, Element},
+        grid::{ElementGrid, GridElements},
         position::Position,
     },
     nest_simulation::nest::AtNest,
@@ -28,8 +28,7 @@ pub fn update_breath_dialog(
     mut contexts: EguiContexts,
     mut is_showing_breath_dialog: ResMut<IsShowingBreathDialog>,
     ant_query: Query<&Hunger, Without<Dead>>,
-    food_query: Query<&Food>,
-    air_query: Query<&Position, With<Air>>,
+    element_grid_query: Query<&ElementGrid>,
     mut rng: ResMut<GlobalRng>,
     grid_elements: GridElements<AtNest>,
     mut commands: Commands,
@@ -43,7 +42,11 @@ pub fn update_breath_dialog(
     let ant_count = ant_query.iter().len() as isize;
     // TODO: Should infer this from rate of hunger?
     let total_ant_food_needed = ant_count * 5;
-    let food_count = food_query.iter().len();
+    let food_count = element_grid_query
+        .iter()
+        .flat_map(|element_grid| element_grid.iter())
+        .filter(|&(_, element)| element == Element::Food)
+        .count();
     let ant_food_needed = isize::max(total_ant_food_needed as isize - food_count as isize, 0);
     let labels = ["Inhale", "Hold", "Exhale", "Hold"];
     let duration = 4.;
@@ -122,10 +125,13 @@ pub fn update_breath_dialog(
                             *is_running = false;
                             *timer = 0.;
 
-                            let northern_air_positions = air_query
+                            let northern_air_positions = grid_elements
                                 .iter()
-                                .filter(|position| position.y < 20)
-                                .collect::<Vec<&Position>>();
+                                .filter(|&(position, element)| {
+                                    element == Element::Air && position.y < 20
+                                })
+                                .map(|(position, _)| position)
+                                .collect::<Vec<Position>>();
 
                             let mut spawn_positions: HashSet<Position> = HashSet::new();
 
@@ -133,18 +139,16 @@ pub fn update_breath_dialog(
                                 let offset = rng.usize(0..northern_air_positions.len());
                                 let position = northern_air_positions[offset];
 
-                                spawn_positions.insert(*position);
+                                spawn_positions.insert(position);
                             }
 
                             for position in spawn_positions.iter() {
-                                if let Some(entity) = grid_elements.get_entity(*position) {
-                                    commands.replace_element(
-                                        *position,
-                                        Element::Food,
-                                        *entity,
-                                        AtNest,
-                                    );
-                                }
+                                commands.replace_element(
+                                    *position,
+                                    Element::Food,
+                                    Element::Air,
+                                    AtNest,
+                                );
                             }
                         }
                     });
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use simulation::{
+    chronicle::{Chronicle, ChronicleEntry, ChronicleEvent, SandboxEdit},
+    common::ant::death::DeathCause,
+};
+
+pub fn update_chronicle_window(mut contexts: EguiContexts, chronicle: Res<Chronicle>) {
+    egui::Window::new("Chronicle")
+        .default_pos(egui::Pos2::new(0.0, 200.0))
+        .default_open(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            if chronicle.entries().is_empty() {
+                ui.label("Nothing has happened yet.");
+                return;
+            }
+
+            egui::ScrollArea::vertical()
+                .max_height(320.0)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for entry in chronicle.entries() {
+                        ui.label(&describe_entry(entry));
+                    }
+                });
+        });
+}
+
+fn describe_entry(entry: &ChronicleEntry) -> String {
+    // Add one to the days label because days don't start at 0 in real life
+    let when = format!("Day {}, {:02}:00", entry.day + 1, entry.hour);
+
+    let what = match &entry.event {
+        ChronicleEvent::NestFounded { queen } => format!("{} founded the nest.", queen),
+        ChronicleEvent::Birth { mother, child } => format!("{} gave birth to {}.", mother, child),
+        ChronicleEvent::Death {
+            name,
+            cause: DeathCause::Starvation,
+        } => format!("{} starved.", name),
+        ChronicleEvent::Death {
+            name,
+            cause: DeathCause::Killed,
+        } => format!("{} was killed.", name),
+        ChronicleEvent::Death {
+            name,
+            cause: DeathCause::Drowned,
+        } => format!("{} drowned.", name),
+        ChronicleEvent::FirstCraterTrip { name } => {
+            format!("{} made the first trip to the crater.", name)
+        }
+        ChronicleEvent::SandboxEdit { edit, count } => describe_sandbox_edit(*edit, *count),
+        ChronicleEvent::FirstFoodFromCrater { name } => {
+            format!("{} brought the first food home from the crater.", name)
+        }
+    };
+
+    format!("{} — {}", when, what)
+}
+
+fn describe_sandbox_edit(edit: SandboxEdit, count: usize) -> String {
+    let (action, one, many) = match edit {
+        SandboxEdit::SpawnFood => ("placed", "a piece of food", "pieces of food"),
+        SandboxEdit::SpawnDirt => ("placed", "a tile of dirt", "tiles of dirt"),
+        SandboxEdit::SpawnSand => ("placed", "a tile of sand", "tiles of sand"),
+        SandboxEdit::SpawnRock => ("placed", "a rock", "rocks"),
+        SandboxEdit::SpawnWater => ("poured", "a drop of water", "drops of water"),
+        SandboxEdit::DespawnElement => ("removed", "a tile", "tiles"),
+        SandboxEdit::KillAnt => ("killed", "an ant", "ants"),
+        SandboxEdit::SpawnWorkerAnt => ("added", "a worker ant", "worker ants"),
+        SandboxEdit::DespawnWorkerAnt => ("removed", "a worker ant", "worker ants"),
+    };
+
+    if count == 1 {
+        format!("You {} {}.", action, one)
+    } else {
+        format!("You {} {} {}.", action, count, many)
+    }
+}
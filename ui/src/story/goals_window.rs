@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use simulation::story_mode::{Goal, StoryMode, CHAPTERS};
+
+pub fn update_goals_window(mut contexts: EguiContexts, story_mode: Res<StoryMode>) {
+    egui::Window::new("Goals")
+        .default_pos(egui::Pos2::new(0.0, 600.0))
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let Some(chapter) = story_mode.chapter() else {
+                ui.label("Every chapter is complete.");
+                return;
+            };
+
+            ui.strong(format!(
+                "Chapter {} of {}: {}",
+                story_mode.chapter_index() + 1,
+                CHAPTERS.len(),
+                chapter.title
+            ));
+
+            for (goal, mut is_met) in story_mode.goals() {
+                ui.add_enabled(false, egui::Checkbox::new(&mut is_met, describe_goal(goal)));
+            }
+        });
+}
+
+pub fn describe_goal(goal: Goal) -> String {
+    match goal {
+        Goal::QueenNested => "The queen finishes nesting".to_string(),
+        Goal::Workers(count) => format!("The colony has {} workers", count),
+        Goal::FoodFromCrater => "A worker brings food home from the crater".to_string(),
+        Goal::SurviveDays(days) => format!("The colony survives for {} days", days),
+    }
+}
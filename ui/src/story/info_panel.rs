@@ -4,7 +4,8 @@ use bevy_egui::{egui, EguiContexts};
 use simulation::{
     common::{
         ant::{hunger::Hunger, AntRole, Dead},
-        element::Food,
+        element::Element,
+        grid::ElementGrid,
     },
     nest_simulation::ant::birthing::Birthing,
     story_time::StoryTime,
@@ -13,9 +14,15 @@ use simulation::{
 pub fn update_info_window(
     mut contexts: EguiContexts,
     ant_query: Query<(&AntRole, &Hunger, Option<&Birthing>), Without<Dead>>,
-    food_query: Query<&Food>,
+    element_grid_query: Query<&ElementGrid>,
     story_time: Res<StoryTime>,
 ) {
+    let food_count = element_grid_query
+        .iter()
+        .flat_map(|element_grid| element_grid.iter())
+        .filter(|&(_, element)| element == Element::Food)
+        .count();
+
     let queen_ant = ant_query
         .iter()
         .find(|(&role, _, _)| role == AntRole::Queen);
@@ -61,6 +68,6 @@ pub fn update_info_window(
             ));
             ui.label(&format!("Queen Hunger: {:.0}%", queen_ant_hunger));
             ui.label(&format!("Queen Birthing: {:.0}%", queen_ant_birthing));
-            ui.label(&format!("Food: {}", food_query.iter().count()));
+            ui.label(&format!("Food: {}", food_count));
         });
 }
@@ -7,7 +7,7 @@ use bevy_egui::{
 use simulation::story_time::{FastForwardPendingTicks, TicksPerSecond, SECONDS_PER_DAY};
 
 // Don't flicker the dialogs visibility when processing a small number of ticks
-const MIN_PENDING_TICKS: isize = 6000;
+pub(super) const MIN_PENDING_TICKS: isize = 6000;
 
 pub fn update_loading_dialog(
     mut contexts: EguiContexts,
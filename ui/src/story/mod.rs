@@ -1,18 +1,29 @@
 mod action_menu;
+mod away_report_dialog;
 mod breath_dialog;
+mod chronicle_window;
+mod goals_window;
 mod info_panel;
 mod loading_dialog;
 mod selection_menu;
 mod settings_menu;
+mod stats_window;
+mod story_mode_dialog;
 mod story_over_dialog;
 
 use self::{
-    action_menu::*, breath_dialog::update_breath_dialog, info_panel::*, loading_dialog::*,
+    action_menu::*, away_report_dialog::update_away_report_dialog,
+    breath_dialog::update_breath_dialog, chronicle_window::update_chronicle_window,
+    goals_window::update_goals_window, info_panel::*, loading_dialog::*,
     selection_menu::update_selection_menu, settings_menu::update_settings_menu,
+    stats_window::update_stats_window, story_mode_dialog::update_story_mode_dialog,
     story_over_dialog::*,
 };
 use bevy::prelude::*;
-use simulation::{app_state::AppState, story_time::StoryPlaybackState};
+use simulation::{
+    app_state::AppState, away_report::AwayReport, chronicle::Chronicle, colony_stats::ColonyStats,
+    story_mode::StoryMode, story_time::StoryPlaybackState,
+};
 
 pub struct StoryUIPlugin;
 
@@ -25,7 +36,16 @@ impl Plugin for StoryUIPlugin {
             Update,
             (
                 update_info_window,
+                update_chronicle_window.run_if(resource_exists::<Chronicle>()),
+                update_stats_window.run_if(resource_exists::<ColonyStats>()),
                 update_loading_dialog.run_if(in_state(StoryPlaybackState::FastForwarding)),
+                update_away_report_dialog.run_if(resource_exists::<AwayReport>()),
+                update_goals_window.run_if(resource_exists::<StoryMode>()),
+                // Wait until fast-forwarding finishes so narration isn't hidden behind the loading dialog.
+                update_story_mode_dialog.run_if(
+                    resource_exists::<StoryMode>()
+                        .and_then(not(in_state(StoryPlaybackState::FastForwarding))),
+                ),
                 update_settings_menu,
                 update_action_menu,
                 update_selection_menu,
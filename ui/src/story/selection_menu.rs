@@ -1,16 +1,21 @@
 use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_egui::{egui, EguiContexts};
 
-use rendering::common::selection::SelectedEntity;
+use rendering::common::{selection::Selection, visible_grid::VisibleGrid};
 
 use simulation::{
     common::{
         ant::{hunger::Hunger, AntInventory, AntName, AntRole, Dead},
         element::Element,
+        grid::GridElements,
         pheromone::{Pheromone, PheromoneStrength},
         position::Position,
     },
-    nest_simulation::ant::{birthing::Birthing, sleep::Asleep},
+    crater_simulation::crater::AtCrater,
+    nest_simulation::{
+        ant::{birthing::Birthing, sleep::Asleep},
+        nest::{AtNest, Nest},
+    },
 };
 
 #[derive(Component, Default, PartialEq, Copy, Clone, Debug)]
@@ -28,23 +33,37 @@ pub fn update_selection_menu(
         Option<&Dead>,
         Option<&Asleep>,
     )>,
-    selected_element_query: Query<(&Element, &Position)>,
+    nest_grid_elements: GridElements<AtNest>,
+    crater_grid_elements: GridElements<AtCrater>,
+    nest_query: Query<(), With<Nest>>,
+    visible_grid: Res<VisibleGrid>,
     pheromone_query: Query<(&Position, &Pheromone, &PheromoneStrength)>,
     elements_query: Query<&Element>,
-    selected_entity: Res<SelectedEntity>,
+    selection: Res<Selection>,
 ) {
     let window = primary_window_query.single();
     let ctx = contexts.ctx_mut();
 
-    let selected_entity = match selected_entity.0 {
-        Some(entity) => entity,
-        None => return,
+    // Selection is cleared when switching zones so the selected element is in the visible zone.
+    let selected_element = match (*selection, visible_grid.0) {
+        (Selection::Element(position), Some(visible_grid_entity)) => {
+            let element = if nest_query.contains(visible_grid_entity) {
+                nest_grid_elements.get_element(position)
+            } else {
+                crater_grid_elements.get_element(position)
+            };
+
+            element.map(|element| (element, position))
+        }
+        _ => None,
     };
 
-    let selected_element = selected_element_query.get(selected_entity);
-    let selected_ant = selected_ant_query.get(selected_entity);
+    let selected_ant = match *selection {
+        Selection::Ant(entity) => selected_ant_query.get(entity).ok(),
+        _ => None,
+    };
 
-    if selected_element.is_err() && selected_ant.is_err() {
+    if selected_element.is_none() && selected_ant.is_none() {
         return;
     }
 
@@ -52,12 +71,12 @@ pub fn update_selection_menu(
         .default_pos(egui::Pos2::new(0.0, window.height()))
         .resizable(false)
         .show(ctx, |ui| {
-            if let Ok((element, element_position)) = selected_element {
+            if let Some((element, element_position)) = selected_element {
                 ui.label("Element");
                 ui.label(&format!("Type: {:?}", element));
 
                 for (pheromone_position, pheromone, pheromone_strength) in pheromone_query.iter() {
-                    if pheromone_position == element_position {
+                    if *pheromone_position == element_position {
                         ui.label(&format!("Pheromone Type: {:?}", pheromone));
                         ui.label(&format!(
                             "Pheromone Strength: {:.0}",
@@ -65,7 +84,7 @@ pub fn update_selection_menu(
                         ));
                     }
                 }
-            } else if let Ok((hunger, name, ant_role, inventory, birthing, dead, asleep)) =
+            } else if let Some((hunger, name, ant_role, inventory, birthing, dead, asleep)) =
                 selected_ant
             {
                 ui.label("Ant");
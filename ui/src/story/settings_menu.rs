@@ -5,6 +5,7 @@ use rendering::common::pheromone::PheromoneVisibility;
 use simulation::{
     app_state::AppState,
     common::ant::AntColor,
+    save::{ExportSaveEvent, SaveTransferStatus},
     settings::Settings,
     story_time::{
         StoryPlaybackState, StoryTime, TicksPerSecond, DEFAULT_TICKS_PER_SECOND,
@@ -23,6 +24,12 @@ pub fn update_settings_menu(
     mut story_time: ResMut<StoryTime>,
     mut settings: ResMut<Settings>,
     mut ant_query: Query<&mut AntColor>,
+    mut export_save_events: EventWriter<ExportSaveEvent>,
+    save_transfer_status: Res<SaveTransferStatus>,
+    #[cfg(not(target_arch = "wasm32"))] mut import_save_events: EventWriter<
+        simulation::save::ImportSaveEvent,
+    >,
+    #[cfg(not(target_arch = "wasm32"))] mut import_path: Local<String>,
 ) {
     let window = primary_window_query.single();
     let ctx = contexts.ctx_mut();
@@ -144,7 +151,40 @@ pub fn update_settings_menu(
                 }
             });
 
-            if ui.button("Reset Sandbox").clicked() {
+            ui.collapsing("Save File", |ui| {
+                if ui.button("Export Colony").clicked() {
+                    export_save_events.send(ExportSaveEvent);
+                }
+
+                // Browsers provide a file picker, but native builds need to be told where the file is.
+                #[cfg(target_arch = "wasm32")]
+                if ui.button("Import Colony").clicked() {
+                    simulation::save::open_import_file_picker();
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut *import_path)
+                            .hint_text("Path to .symbiants file")
+                            .desired_width(200.0),
+                    );
+
+                    if ui.button("Import Colony").clicked() && !import_path.trim().is_empty() {
+                        import_save_events.send(simulation::save::ImportSaveEvent(
+                            import_path.trim().into(),
+                        ));
+                        import_path.clear();
+                    }
+                });
+
+                if let Some(status) = &save_transfer_status.0 {
+                    ui.label(status);
+                }
+            });
+
+            // The story is saved to its slot during cleanup so it can be continued from the main menu.
+            if ui.button("Main Menu").clicked() {
                 next_app_state.set(AppState::Cleanup);
             }
         });
@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2, Color32, FontId, Sense, Shape, Stroke},
+    EguiContexts,
+};
+
+use simulation::colony_stats::{ColonyStats, ColonyStatsSample};
+
+const CHART_SIZE: egui::Vec2 = egui::Vec2::new(360.0, 160.0);
+
+#[derive(Default, PartialEq, Clone, Copy, Debug)]
+pub enum Chart {
+    #[default]
+    Population,
+    Food,
+    AverageHunger,
+    QueenBirthing,
+    TunnelArea,
+    Pheromones,
+}
+
+impl Chart {
+    const ALL: [Chart; 6] = [
+        Chart::Population,
+        Chart::Food,
+        Chart::AverageHunger,
+        Chart::QueenBirthing,
+        Chart::TunnelArea,
+        Chart::Pheromones,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Chart::Population => "Population",
+            Chart::Food => "Food",
+            Chart::AverageHunger => "Colony Average Hunger",
+            Chart::QueenBirthing => "Queen Birthing",
+            Chart::TunnelArea => "Tunnel Area",
+            Chart::Pheromones => "Pheromones",
+        }
+    }
+
+    /// Each line to plot: its name, color, and value for a sample.
+    fn lines(&self) -> Vec<(&'static str, Color32, fn(&ColonyStatsSample) -> f32)> {
+        match self {
+            Chart::Population => vec![
+                ("Workers", Color32::LIGHT_BLUE, |sample| {
+                    sample.workers as f32
+                }),
+                ("Queens", Color32::GOLD, |sample| sample.queens as f32),
+            ],
+            Chart::Food => vec![("Food", Color32::LIGHT_GREEN, |sample| sample.food as f32)],
+            Chart::AverageHunger => vec![("Hunger %", Color32::LIGHT_RED, |sample| {
+                sample.average_hunger
+            })],
+            Chart::QueenBirthing => {
+                vec![("Birthing %", Color32::GOLD, |sample| sample.queen_birthing)]
+            }
+            Chart::TunnelArea => {
+                vec![("Tiles", Color32::BROWN, |sample| sample.tunnel_area as f32)]
+            }
+            Chart::Pheromones => vec![("Pheromones", Color32::LIGHT_YELLOW, |sample| {
+                sample.pheromones as f32
+            })],
+        }
+    }
+}
+
+pub fn update_stats_window(
+    mut contexts: EguiContexts,
+    colony_stats: Res<ColonyStats>,
+    mut chart: Local<Chart>,
+) {
+    egui::Window::new("Stats")
+        .default_pos(egui::Pos2::new(0.0, 400.0))
+        .default_open(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::ComboBox::from_id_source("stats_chart")
+                .selected_text(chart.label())
+                .show_ui(ui, |ui| {
+                    for option in Chart::ALL {
+                        ui.selectable_value(&mut *chart, option, option.label());
+                    }
+                });
+
+            let samples = colony_stats.samples();
+
+            if samples.len() < 2 {
+                ui.label("Check back in an hour or two.");
+                return;
+            }
+
+            draw_chart(ui, samples, &chart.lines());
+
+            ui.horizontal(|ui| {
+                for (name, color, _) in chart.lines() {
+                    ui.colored_label(color, name);
+                }
+            });
+        });
+}
+
+/// Plot each line over days, from the first sample to the last, scaled so the largest value reaches the top.
+fn draw_chart(
+    ui: &mut egui::Ui,
+    samples: &[ColonyStatsSample],
+    lines: &[(&'static str, Color32, fn(&ColonyStatsSample) -> f32)],
+) {
+    let (response, painter) = ui.allocate_painter(CHART_SIZE, Sense::hover());
+    let rect = response.rect;
+    let text_color = ui.visuals().text_color();
+
+    let first_day = samples[0].days();
+    let last_day = samples[samples.len() - 1].days();
+    let max_value = lines
+        .iter()
+        .flat_map(|&(_, _, value)| samples.iter().map(value))
+        .fold(0.0, f32::max)
+        .max(1.0);
+
+    painter.rect_stroke(rect, 0.0, Stroke::new(1.0, text_color));
+
+    for &(_, color, value) in lines {
+        let points = samples
+            .iter()
+            .map(|sample| {
+                let x = (sample.days() - first_day) / (last_day - first_day);
+                let y = value(sample) / max_value;
+
+                egui::pos2(
+                    rect.left() + x * rect.width(),
+                    rect.bottom() - y * rect.height(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        painter.add(Shape::line(points, Stroke::new(1.5, color)));
+    }
+
+    let font_id = FontId::monospace(10.0);
+
+    painter.text(
+        rect.left_top() + egui::vec2(4.0, 2.0),
+        Align2::LEFT_TOP,
+        format!("{:.0}", max_value),
+        font_id.clone(),
+        text_color,
+    );
+    // Add one to the days label because days don't start at 0 in real life
+    painter.text(
+        rect.left_bottom() + egui::vec2(4.0, -2.0),
+        Align2::LEFT_BOTTOM,
+        format!("Day {:.1}", first_day + 1.0),
+        font_id.clone(),
+        text_color,
+    );
+    painter.text(
+        rect.right_bottom() + egui::vec2(-4.0, -2.0),
+        Align2::RIGHT_BOTTOM,
+        format!("Day {:.1}", last_day + 1.0),
+        font_id,
+        text_color,
+    );
+}
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2},
+    EguiContexts,
+};
+
+use simulation::story_mode::{Narration, StoryMode, CHAPTERS};
+
+use super::goals_window::describe_goal;
+
+/// Tell each chapter's introduction as it begins, and its conclusion once it's complete, one at a time.
+pub fn update_story_mode_dialog(mut contexts: EguiContexts, mut story_mode: ResMut<StoryMode>) {
+    let Some(narration) = story_mode.unread_narration() else {
+        return;
+    };
+
+    let (chapter_index, button_text) = match narration {
+        Narration::ChapterStarted(chapter_index) => (chapter_index, "Begin"),
+        Narration::ChapterCompleted(chapter_index) => (chapter_index, "Continue"),
+    };
+
+    // Narration is loaded along with the story, so its chapter might no longer exist. Skip it rather than panic.
+    let Some(chapter) = CHAPTERS.get(chapter_index) else {
+        story_mode.read_narration();
+        return;
+    };
+
+    let text = match narration {
+        Narration::ChapterStarted(_) => chapter.introduction,
+        Narration::ChapterCompleted(_) => chapter.conclusion,
+    };
+
+    egui::Window::new(format!("Chapter {}: {}", chapter_index + 1, chapter.title))
+        .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.set_max_width(320.0);
+            ui.label(text);
+
+            if narration == Narration::ChapterStarted(chapter_index) {
+                ui.separator();
+
+                for &goal in chapter.goals {
+                    ui.label(format!("• {}", describe_goal(goal)));
+                }
+            }
+
+            ui.separator();
+
+            ui.vertical_centered(|ui| {
+                if ui.button(button_text).clicked() {
+                    story_mode.read_narration();
+                }
+            });
+        });
+}
@@ -4,11 +4,16 @@ use bevy_egui::{
     EguiContexts,
 };
 
-use simulation::app_state::AppState;
+use simulation::{
+    app_state::AppState,
+    save::{ActiveSaveSlot, SaveSlots},
+};
 
 pub fn update_story_over_dialog(
     mut contexts: EguiContexts,
     mut next_app_state: ResMut<NextState<AppState>>,
+    mut save_slots: ResMut<SaveSlots>,
+    mut active_save_slot: ResMut<ActiveSaveSlot>,
 ) {
     let ctx = contexts.ctx_mut();
 
@@ -21,6 +26,11 @@ pub fn update_story_over_dialog(
 
             ui.vertical_centered(|ui| {
                 if ui.button("Begin New Story").clicked() {
+                    // There's nothing left to continue so don't leave the finished story in the main menu.
+                    if let Some(save_slot_id) = active_save_slot.0.take() {
+                        save_slots.delete(save_slot_id);
+                    }
+
                     next_app_state.set(AppState::Cleanup);
                 }
             });